@@ -6,6 +6,7 @@ use crate::{
     retry::Retrier,
     vm::{PaymentMode, VmClient, VmClientConfig},
 };
+use build_info::BuildInfo;
 use grpc_channel::{token::TokenAuthenticator, AuthenticatedGrpcChannel, GrpcChannelConfig, GrpcChannelError};
 use nillion_client_core::values::{PartyId, SecretMasker};
 use node_api::{
@@ -33,6 +34,7 @@ pub struct VmClientBuilder {
     nilchain_payer: Option<Arc<dyn NilChainPayer>>,
     max_payload_size: Option<usize>,
     payment_mode: PaymentMode,
+    user_agent: Option<String>,
 }
 
 impl VmClientBuilder {
@@ -94,6 +96,14 @@ impl VmClientBuilder {
         self
     }
 
+    /// Set the user agent to send on every request.
+    ///
+    /// This defaults to one that identifies this client's version.
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
     /// Build a [VmClient] using the provided configuration.
     pub async fn build(mut self) -> Result<VmClient, BuilderError> {
         use BuilderError::MissingProperty;
@@ -152,7 +162,8 @@ impl VmClientBuilder {
     }
 
     fn build_channel_config(&self, endpoint: String) -> GrpcChannelConfig {
-        let mut config = GrpcChannelConfig::new(endpoint);
+        let user_agent = self.user_agent.clone().unwrap_or_else(default_user_agent);
+        let mut config = GrpcChannelConfig::new(endpoint).user_agent(user_agent);
         if let Some(cert) = self.ca_cert.clone() {
             config = config.ca_certificate(&cert);
             if let Some(domain) = self.certificate_domain.clone() {
@@ -219,6 +230,12 @@ impl VmClientBuilder {
     }
 }
 
+fn default_user_agent() -> String {
+    let info = BuildInfo::default();
+    let version = info.release_candidate_version.unwrap_or(info.git_commit_hash);
+    format!("{}/{version}", env!("CARGO_PKG_NAME"))
+}
+
 /// An error during the construction of the client.
 #[derive(Debug, thiserror::Error)]
 pub enum BuilderError {
@@ -25,6 +25,7 @@ pub mod vm;
 pub use nilchain_client::transactions::TokenAmount;
 pub use nillion_client_core::values::{Clear, NadaType, NadaValue};
 pub use node_api::auth::rust::UserId;
+pub use payments::TokenAmountExt;
 pub use tonic::async_trait;
 pub use user_keypair::{ed25519::*, secp256k1::*, SigningKey};
 pub use uuid::Uuid;
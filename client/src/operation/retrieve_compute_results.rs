@@ -34,7 +34,7 @@ impl RetrieveComputeResultsOperation {
                     match delays.next() {
                         Some(delay) => {
                             info!("Sleeping for {delay:?}");
-                            sleep(*delay).await;
+                            sleep(delay).await;
                         }
                         None => return Err(InvokeError(e.to_string())),
                     };
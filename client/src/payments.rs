@@ -7,6 +7,72 @@ use tonic::async_trait;
 
 pub use nilchain_client::{client::NillionChainClient, key::NillionChainPrivateKey};
 
+// The number of unil in a nil.
+const UNIL_PER_NIL: u64 = 1_000_000;
+
+/// Overflow-safe arithmetic on [TokenAmount]s.
+///
+/// Every operation returns `None` instead of overflowing or going negative. When both operands are expressed in nil the
+/// result is expressed in nil as well, otherwise it's expressed in unil.
+pub trait TokenAmountExt: Sized {
+    /// Adds two amounts.
+    fn checked_add(self, other: TokenAmount) -> Option<TokenAmount>;
+
+    /// Subtracts an amount from this one.
+    fn checked_sub(self, other: TokenAmount) -> Option<TokenAmount>;
+
+    /// Multiplies this amount by a factor.
+    fn checked_mul_u64(self, factor: u64) -> Option<TokenAmount>;
+
+    /// Renders this amount in NIL regardless of how it's expressed, e.g. `1.5 NIL` for 1500000 unil.
+    ///
+    /// Up to 6 decimal places are shown, trailing zeros are trimmed but at least one decimal is always kept.
+    fn to_human_string(&self) -> String;
+}
+
+impl TokenAmountExt for TokenAmount {
+    fn checked_add(self, other: TokenAmount) -> Option<TokenAmount> {
+        match (self, other) {
+            (TokenAmount::Nil(left), TokenAmount::Nil(right)) => left.checked_add(right).map(TokenAmount::Nil),
+            (left, right) => checked_unil(left)?.checked_add(checked_unil(right)?).map(TokenAmount::Unil),
+        }
+    }
+
+    fn checked_sub(self, other: TokenAmount) -> Option<TokenAmount> {
+        match (self, other) {
+            (TokenAmount::Nil(left), TokenAmount::Nil(right)) => left.checked_sub(right).map(TokenAmount::Nil),
+            (left, right) => checked_unil(left)?.checked_sub(checked_unil(right)?).map(TokenAmount::Unil),
+        }
+    }
+
+    fn checked_mul_u64(self, factor: u64) -> Option<TokenAmount> {
+        match self {
+            TokenAmount::Nil(amount) => amount.checked_mul(factor).map(TokenAmount::Nil),
+            TokenAmount::Unil(amount) => amount.checked_mul(factor).map(TokenAmount::Unil),
+        }
+    }
+
+    fn to_human_string(&self) -> String {
+        let (nil, unil) = match self {
+            TokenAmount::Nil(amount) => (*amount, 0),
+            TokenAmount::Unil(amount) => (amount / UNIL_PER_NIL, amount % UNIL_PER_NIL),
+        };
+        let decimals = format!("{unil:06}");
+        let decimals = match decimals.trim_end_matches('0') {
+            "" => "0",
+            decimals => decimals,
+        };
+        format!("{nil}.{decimals} NIL")
+    }
+}
+
+fn checked_unil(amount: TokenAmount) -> Option<u64> {
+    match amount {
+        TokenAmount::Nil(amount) => amount.checked_mul(UNIL_PER_NIL),
+        TokenAmount::Unil(amount) => Some(amount),
+    }
+}
+
 /// A payer that uses the nilchain to submit payments.
 #[async_trait]
 pub trait NilChainPayer: Send + Sync + 'static {
@@ -52,3 +118,52 @@ impl NilChainPayer for NillionChainClientPayer {
         Ok(TxHash(tx_hash))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_add() {
+        assert!(matches!(TokenAmount::Nil(1).checked_add(TokenAmount::Nil(2)), Some(TokenAmount::Nil(3))));
+        assert!(matches!(TokenAmount::Nil(1).checked_add(TokenAmount::Unil(5)), Some(TokenAmount::Unil(1_000_005))));
+    }
+
+    #[test]
+    fn checked_add_overflow() {
+        assert!(TokenAmount::Unil(u64::MAX).checked_add(TokenAmount::Unil(1)).is_none());
+        assert!(TokenAmount::Nil(u64::MAX).checked_add(TokenAmount::Unil(0)).is_none());
+    }
+
+    #[test]
+    fn checked_sub_negative() {
+        assert!(TokenAmount::Unil(1).checked_sub(TokenAmount::Unil(2)).is_none());
+        assert!(TokenAmount::Unil(999_999).checked_sub(TokenAmount::Nil(1)).is_none());
+        assert!(matches!(TokenAmount::Nil(3).checked_sub(TokenAmount::Nil(3)), Some(TokenAmount::Nil(0))));
+    }
+
+    #[test]
+    fn checked_mul_u64() {
+        assert!(matches!(TokenAmount::Nil(2).checked_mul_u64(3), Some(TokenAmount::Nil(6))));
+        assert!(TokenAmount::Unil(u64::MAX).checked_mul_u64(2).is_none());
+    }
+
+    #[test]
+    fn human_string_exact() {
+        assert_eq!(TokenAmount::Nil(3).to_human_string(), "3.0 NIL");
+        assert_eq!(TokenAmount::Unil(2_000_000).to_human_string(), "2.0 NIL");
+    }
+
+    #[test]
+    fn human_string_fractional() {
+        assert_eq!(TokenAmount::Unil(1_500_000).to_human_string(), "1.5 NIL");
+        assert_eq!(TokenAmount::Unil(1).to_human_string(), "0.000001 NIL");
+        assert_eq!(TokenAmount::Unil(12_345_678).to_human_string(), "12.345678 NIL");
+    }
+
+    #[test]
+    fn human_string_zero() {
+        assert_eq!(TokenAmount::Nil(0).to_human_string(), "0.0 NIL");
+        assert_eq!(TokenAmount::Unil(0).to_human_string(), "0.0 NIL");
+    }
+}
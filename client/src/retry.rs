@@ -1,6 +1,7 @@
 //! Request retry utilities.
 
 use futures::{future, FutureExt};
+use grpc_channel::backoff::Backoff;
 use nillion_client_core::values::PartyId;
 use node_api::errors::StatusExt;
 use std::{fmt, future::Future, iter, mem, time::Duration};
@@ -10,7 +11,7 @@ use tracing::{info, warn};
 const DEFAULT_MAX_RETRIES: usize = 10;
 pub(crate) const RETRY_CODES: &[Code] =
     &[Code::DeadlineExceeded, Code::ResourceExhausted, Code::Unavailable, Code::Unknown];
-const RETRY_DELAYS: &[Duration] = &[Duration::from_secs(1), Duration::from_secs(3), Duration::from_secs(5)];
+const RETRY_BACKOFF: Backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5)).with_jitter(0.2);
 
 struct PartyRequest<'a, P, C, R> {
     party: P,
@@ -45,11 +46,11 @@ where
         self
     }
 
-    pub(crate) fn retry_delays() -> impl Iterator<Item = &'static Duration> {
-        #[allow(clippy::unwrap_used)]
-        // SAFETY: this is a non empty slice so `last` can't fail
-        // use all retry delays, then repeat the last one forever
-        RETRY_DELAYS.iter().chain(iter::repeat(RETRY_DELAYS.last().unwrap()))
+    pub(crate) fn retry_delays() -> impl Iterator<Item = Duration> {
+        // delays are capped so once the max is reached it's repeated for every remaining attempt, attempts saturate
+        // rather than run out so this never ends
+        iter::successors(Some(0u32), |attempt| Some(attempt.saturating_add(1)))
+            .map(|attempt| RETRY_BACKOFF.next_delay(attempt))
     }
 
     pub(crate) fn add_request(&mut self, party: P, client: &'a C, request: R) {
@@ -100,9 +101,9 @@ where
                         delay
                     }
                     None => {
-                        // SAFETY: `delays` is an infinite iterator
+                        // SAFETY: `delays` is an infinite iterator, see `retry_delays`
                         #[allow(clippy::expect_used)]
-                        *delays.next().expect("no more delays")
+                        delays.next().expect("no more delays")
                     }
                 };
                 retries = retries.saturating_add(1);
@@ -1,9 +1,10 @@
 use anyhow::Result;
 use instant::SystemTime;
-use reqwest::Client;
+use reqwest::{Client, ClientBuilder, Proxy};
 use serde::Serialize;
 use std::time::Duration;
 
+#[derive(Clone)]
 pub struct PiwikClient {
     url: String,
     site_id: String,
@@ -12,8 +13,25 @@ pub struct PiwikClient {
 
 impl PiwikClient {
     pub fn new(instance_name: String, site_id: String) -> Result<PiwikClient> {
-        let client = Client::builder().user_agent("RustPiwikClient/0.1").timeout(Duration::from_secs(10)).build()?;
-        Ok(PiwikClient { url: format!("https://{instance_name}.piwik.pro/ppms.php"), site_id, client })
+        Self::with_url(format!("https://{instance_name}.piwik.pro/ppms.php"), site_id)
+    }
+
+    /// Creates a client that sends events to the given URL.
+    ///
+    /// Requests go through the proxies set in the `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment variables.
+    pub fn with_url(url: String, site_id: String) -> Result<PiwikClient> {
+        let client = Self::client_builder().build()?;
+        Ok(PiwikClient { url, site_id, client })
+    }
+
+    /// Sends requests through the given HTTP proxy, ignoring any proxy set in the environment.
+    pub fn with_proxy(self, proxy: &str) -> Result<PiwikClient> {
+        let client = Self::client_builder().proxy(Proxy::all(proxy)?).build()?;
+        Ok(PiwikClient { client, ..self })
+    }
+
+    fn client_builder() -> ClientBuilder {
+        Client::builder().user_agent("RustPiwikClient/0.1").timeout(Duration::from_secs(10))
     }
 
     pub async fn track(&self, event: TrackEvent) -> Result<()> {
@@ -1448,6 +1466,11 @@ impl TrackEvent {
 pub mod test {
 
     use crate::{PiwikClient, TrackEvent};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
     pub const SITE_ID: &str = "51a6273d-6bbe-4ca3-abb2-0561c0c7fe6c";
     pub const INSTANCE_NAME: &str = "test-122269960";
 
@@ -1460,4 +1483,27 @@ pub mod test {
             .action_name("my-command".to_string());
         client.track(event).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy = format!("http://{}", listener.local_addr().unwrap());
+        let proxy_handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut request_line = String::new();
+            BufReader::new(&stream).read_line(&mut request_line).unwrap();
+            (&stream).write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+            request_line
+        });
+
+        let client = PiwikClient::with_url("http://piwik.invalid/ppms.php".to_string(), SITE_ID.to_string())
+            .unwrap()
+            .with_proxy(&proxy)
+            .unwrap();
+        client.track(TrackEvent::new().action_name("my-command".to_string())).await.unwrap();
+
+        // Requests sent through a proxy use the absolute URL as their target.
+        let request_line = proxy_handle.join().unwrap();
+        assert!(request_line.starts_with("GET http://piwik.invalid/ppms.php?"), "{request_line}");
+    }
 }
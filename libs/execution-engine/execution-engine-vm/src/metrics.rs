@@ -11,6 +11,7 @@ use indexmap::IndexMap;
 use instant::{Duration, Instant};
 use jit_compiler::models::protocols::{memory::ProtocolAddress, Protocol};
 use log::warn;
+use metrics::{prelude::*, Counter, FloatCounter, Histogram};
 use serde::Serialize;
 use std::{
     collections::BTreeMap,
@@ -315,6 +316,57 @@ impl ExecutionMetrics {
         })
     }
 
+    /// Exports these metrics as prometheus metrics.
+    ///
+    /// The metrics are registered in the registry returned by [`metrics::initialize`], which must be called first.
+    /// Since a metric can only be registered once, this should be called at most once per process.
+    pub fn register_metrics(&self) {
+        let calls: MaybeMetric<Counter> = Counter::new(
+            "program_execution_protocol_calls_total",
+            "Number of calls to each protocol",
+            &["protocol", "kind"],
+        )
+        .into();
+        let durations: MaybeMetric<FloatCounter> = FloatCounter::new(
+            "program_execution_protocol_duration_seconds_total",
+            "Total time spent executing each protocol",
+            &["protocol", "kind"],
+        )
+        .into();
+        let message_sizes: MaybeMetric<Counter> = Counter::new(
+            "program_execution_protocol_message_size_bytes_total",
+            "Total size of the messages sent by each protocol",
+            &["protocol", "kind"],
+        )
+        .into();
+        let round_message_sizes: MaybeMetric<Histogram<f64>> = Histogram::new(
+            "program_execution_round_message_size_bytes",
+            "Size of the messages sent in each round",
+            &[],
+            &[1e3, 1e4, 1e5, 1e6, 1e7],
+        )
+        .into();
+        let rounds: MaybeMetric<Counter> =
+            Counter::new("program_execution_rounds_total", "Number of communication rounds", &[]).into();
+
+        let protocols = self
+            .summary
+            .local_protocols
+            .values()
+            .map(|metrics| ("local", metrics))
+            .chain(self.summary.online_protocols.values().map(|metrics| ("online", metrics)));
+        for (kind, metrics) in protocols {
+            let labels = [("protocol", metrics.variant), ("kind", kind)];
+            calls.with_labels(labels).inc_by(metrics.calls);
+            durations.with_labels(labels).inc_by(metrics.duration.total.as_secs_f64());
+            message_sizes.with_labels(labels).inc_by(metrics.total_message_size);
+        }
+        for size in self.steps.iter().flat_map(|step| &step.rounds_message_size) {
+            round_message_sizes.with_labels([]).observe(&(*size as f64));
+        }
+        rounds.with_labels([]).inc_by(self.summary.total_rounds);
+    }
+
     /// Return the metrics of the execution in parts.
     fn into_parts(self) -> (ExecutionMetricsConfig, ExecutionPlanSummary, Vec<StepMetrics>) {
         (self.config, self.summary, self.steps)
@@ -794,3 +846,38 @@ pub enum MetricsFormat {
     /// Metrics in YAML format.
     Yaml,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::metrics::metrics::MetricsRegistry;
+    use std::collections::HashMap;
+
+    #[test]
+    fn register_metrics() {
+        let registry = ::metrics::initialize(HashMap::new()).unwrap();
+        let mut metrics = ExecutionMetrics::new(ExecutionMetricsConfig::enabled(true, true));
+        let mut addition = ProtocolVariantMetrics::new("Addition");
+        addition.calls = 3;
+        metrics.summary.local_protocols.insert("Addition", addition);
+        let mut multiplication = ProtocolVariantMetrics::new("MultiplicationShares");
+        multiplication.calls = 2;
+        multiplication.total_message_size = 512;
+        metrics.summary.online_protocols.insert("MultiplicationShares", multiplication);
+        metrics.summary.total_rounds = 2;
+        metrics.steps.push(StepMetrics { index: 0, protocols: BTreeMap::new(), rounds_message_size: vec![256, 256] });
+
+        metrics.register_metrics();
+
+        let output = registry.encode_metrics().unwrap();
+        for expected in [
+            r#"program_execution_protocol_calls_total{kind="local",protocol="Addition"} 3"#,
+            r#"program_execution_protocol_calls_total{kind="online",protocol="MultiplicationShares"} 2"#,
+            r#"program_execution_protocol_message_size_bytes_total{kind="online",protocol="MultiplicationShares"} 512"#,
+            "program_execution_round_message_size_bytes_count 2",
+            "program_execution_rounds_total 2",
+        ] {
+            assert!(output.contains(expected), "{expected} not found in {output}");
+        }
+    }
+}
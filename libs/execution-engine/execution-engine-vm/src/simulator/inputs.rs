@@ -195,6 +195,53 @@ impl From<InputGenerator> for HashMap<String, NadaValue<Clear>> {
     }
 }
 
+/// A builder for an `InputGenerator::Static` that fills in any inputs that aren't explicitly provided with random
+/// values.
+///
+/// Values are generated from a PRNG seeded with a fixed seed so the same seed and program always generate the same
+/// inputs.
+pub struct RandomInputGenerator {
+    seed: u64,
+    inputs: HashMap<String, NadaValue<Clear>>,
+}
+
+impl RandomInputGenerator {
+    /// Create a new generator that uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed, inputs: HashMap::new() }
+    }
+
+    /// Adds many inputs that won't be randomly generated.
+    pub fn extend<S>(&mut self, inputs: HashMap<S, NadaValue<Clear>>)
+    where
+        S: Into<String>,
+    {
+        self.inputs.extend(inputs.into_iter().map(|(s, input)| (s.into(), input)));
+    }
+
+    /// Generate the values for every input in the contract that wasn't explicitly provided.
+    ///
+    /// Inputs are generated in the order they are defined in the contract.
+    pub fn generate(&self, contract: &ProgramContract) -> Result<Vec<(String, NadaValue<Clear>)>, Error> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut generated = Vec::new();
+        for input in &contract.inputs {
+            if !self.inputs.contains_key(&input.name) {
+                generated.push((input.name.clone(), InputGenerator::new_random_value(&input.ty, &mut rng)?));
+            }
+        }
+        Ok(generated)
+    }
+
+    /// Builds the output `InputGenerator` for the given program contract.
+    pub fn build(self, contract: &ProgramContract) -> Result<InputGenerator, Error> {
+        let generated = self.generate(contract)?;
+        let mut inputs = self.inputs;
+        inputs.extend(generated);
+        Ok(InputGenerator::Static(inputs))
+    }
+}
+
 /// A builder for a `InputGenerator::Static`.
 #[derive(Default)]
 pub struct StaticInputGeneratorBuilder {
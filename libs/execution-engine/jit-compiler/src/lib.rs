@@ -32,6 +32,7 @@ use nada_compiler_backend::{
     preprocess::error::MIRPreprocessorError,
     program_contract::{ProgramContract, ProgramContractError},
 };
+use nada_type::NadaType;
 
 /// A program ready to be executed.
 #[derive(Debug, Clone)]
@@ -44,6 +45,20 @@ pub struct Program<P: Protocol> {
     pub body: ProtocolsModel<P>,
 }
 
+impl<P: Protocol> Program<P> {
+    /// Returns the name, party name and type of each of this program's outputs, in declaration order.
+    pub fn outputs(&self) -> Vec<(String, String, NadaType)> {
+        self.contract
+            .outputs
+            .iter()
+            .filter_map(|output| {
+                let party = self.contract.parties.get(output.party)?;
+                Some((output.name.clone(), party.name.clone(), output.ty.clone()))
+            })
+            .collect()
+    }
+}
+
 /// The Jit compiler
 pub trait JitCompiler<P: Protocol> {
     /// Compiles a program from its mir representation.
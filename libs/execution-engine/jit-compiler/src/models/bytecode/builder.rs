@@ -3,7 +3,7 @@
 use crate::models::{
     bytecode::{
         memory::{BytecodeAddress, BytecodeMemoryError},
-        Addition, Input, Load, Multiplication, Operation, Output, ProgramBytecode,
+        Addition, Input, Load, Multiplication, Operation, Output, ProgramBytecode, Random,
     },
     memory::AddressType,
     Party, SourceRefIndex,
@@ -91,6 +91,13 @@ impl ProgramBytecode {
         Ok(self.add_operation(modulo))
     }
 
+    /// Create a new random value
+    pub fn create_new_random(&mut self, ty: NadaType) -> BytecodeAddress {
+        let address = BytecodeAddress(self.operations_count(), AddressType::Heap);
+        let random = Operation::Random(Random { address, ty, source_ref_index: SourceRefIndex::default() });
+        self.add_operation(random)
+    }
+
     /// Create a new output
     pub fn create_new_output(
         &mut self,
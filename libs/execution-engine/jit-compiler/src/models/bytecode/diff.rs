@@ -0,0 +1,146 @@
+//! This module implements the comparison of two bytecodes, used to debug changes in the compiler.
+
+use crate::models::{
+    bytecode::{memory::BytecodeAddress, ProgramBytecode},
+    memory::AddressType,
+};
+use std::fmt::{Display, Formatter};
+
+/// An operation that is in both bytecodes at the same address but differs between them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangedOperation {
+    /// The operation address.
+    pub address: BytecodeAddress,
+    /// The text representation of the operation in the original bytecode.
+    pub before: String,
+    /// The text representation of the operation in the other bytecode.
+    pub after: String,
+}
+
+/// The differences between the operations of two bytecodes, by address.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BytecodeDiff {
+    /// Operations that are only in the other bytecode, with their text representation.
+    pub added: Vec<(BytecodeAddress, String)>,
+    /// Operations that are only in the original bytecode, with their text representation.
+    pub removed: Vec<(BytecodeAddress, String)>,
+    /// Operations that differ between both bytecodes.
+    pub changed: Vec<ChangedOperation>,
+}
+
+impl BytecodeDiff {
+    /// Returns true if both bytecodes have the same operations.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Display for BytecodeDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for ChangedOperation { before, after, .. } in &self.changed {
+            writeln!(f, "- {before}")?;
+            writeln!(f, "+ {after}")?;
+        }
+        for (_, removed) in &self.removed {
+            writeln!(f, "- {removed}")?;
+        }
+        for (_, added) in &self.added {
+            writeln!(f, "+ {added}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ProgramBytecode {
+    /// Compares the operations in this bytecode against the ones in another one.
+    ///
+    /// Operations are compared by address, ignoring their source references, so that two compilations of the same
+    /// program only differ where the compiler produced different operations.
+    pub fn diff(&self, other: &ProgramBytecode) -> BytecodeDiff {
+        let mut diff = BytecodeDiff::default();
+        let mut ours = self.memory.heap.iter();
+        let mut theirs = other.memory.heap.iter();
+        let mut index = 0;
+        loop {
+            let address = BytecodeAddress(index, AddressType::Heap);
+            match (ours.next(), theirs.next()) {
+                (Some(before), Some(after)) => {
+                    if before.to_string() != after.to_string() {
+                        let before = before.text_repr(self);
+                        let after = after.text_repr(other);
+                        diff.changed.push(ChangedOperation { address, before, after });
+                    }
+                }
+                (Some(removed), None) => diff.removed.push((address, removed.text_repr(self))),
+                (None, Some(added)) => diff.added.push((address, added.text_repr(other))),
+                (None, None) => break,
+            }
+            index = index.wrapping_add(1);
+        }
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        bytecode::{memory::BytecodeAddress, ProgramBytecode},
+        memory::AddressType,
+    };
+    use nada_type::NadaType;
+
+    fn bytecode(multiply: bool) -> ProgramBytecode {
+        let mut bytecode = ProgramBytecode::default();
+        let ty = NadaType::new_secret_integer();
+        let party_id = bytecode.create_new_party(String::from("dealer"));
+        let left = bytecode.create_new_input(String::from("left"), party_id, ty.clone()).unwrap();
+        let right = bytecode.create_new_input(String::from("right"), party_id, ty.clone()).unwrap();
+        if multiply {
+            bytecode.create_new_multiplication(left, right, ty);
+        } else {
+            bytecode.create_new_addition(left, right, ty).unwrap();
+        }
+        bytecode
+    }
+
+    #[test]
+    fn diff_same_bytecode() {
+        let bytecode = bytecode(true);
+        let diff = bytecode.diff(&bytecode.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn diff_changed_operation() {
+        let before = bytecode(true);
+        let after = bytecode(false);
+        let diff = before.diff(&after);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+
+        let changed = diff.changed.first().unwrap();
+        assert_eq!(changed.address, BytecodeAddress(2, AddressType::Heap));
+        assert!(changed.before.contains("Multiplication"), "unexpected operation: {}", changed.before);
+        assert!(changed.after.contains("Addition"), "unexpected operation: {}", changed.after);
+    }
+
+    #[test]
+    fn diff_added_operation() {
+        let before = bytecode(true);
+        let mut after = before.clone();
+        let ty = NadaType::new_secret_integer();
+        let left = BytecodeAddress(0, AddressType::Heap);
+        after.create_new_addition(left, left, ty).unwrap();
+
+        let diff = before.diff(&after);
+        assert!(diff.changed.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added.first().unwrap().0, BytecodeAddress(3, AddressType::Heap));
+
+        let diff = after.diff(&before);
+        assert_eq!(diff.removed.len(), 1);
+    }
+}
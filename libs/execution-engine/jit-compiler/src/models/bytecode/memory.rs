@@ -26,6 +26,18 @@ impl BytecodeAddress {
         Ok(Self(self.0.checked_add(offset).ok_or(BytecodeMemoryError::Overflow)?, self.1))
     }
 
+    /// Advance an offset number of addresses, making sure the result stays within a region.
+    ///
+    /// The `bound` is the largest offset allowed, that is, the number of addresses that follow this one in the
+    /// region it belongs to. For instance, the elements of an array of size `n` that lives in this address are
+    /// within offsets `1..=n`.
+    pub fn advance_checked(&self, offset: usize, bound: usize) -> Result<BytecodeAddress, BytecodeMemoryError> {
+        if offset > bound {
+            return Err(BytecodeMemoryError::OutOfBounds { address: *self, offset, bound });
+        }
+        self.advance(offset)
+    }
+
     /// Converts address into heap address
     pub fn as_heap(&self) -> BytecodeAddress {
         Self(self.0, AddressType::Heap)
@@ -57,6 +69,17 @@ pub enum BytecodeMemoryError {
     #[error("out of memory {1:?}: {0}")]
     OutOfMemory(&'static str, BytecodeAddress),
 
+    /// An address was advanced past the end of its region
+    #[error("offset {offset} from address {address} is out of bounds: region ends at offset {bound}")]
+    OutOfBounds {
+        /// The address being advanced.
+        address: BytecodeAddress,
+        /// The offset it was advanced by.
+        offset: usize,
+        /// The largest offset allowed.
+        bound: usize,
+    },
+
     /// An address is used to access to the wrong type of memory
     #[error("illegal memory access")]
     IllegalMemoryAccess,
@@ -71,3 +94,17 @@ impl From<BytecodeAddress> for usize {
         value.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_checked() {
+        let address = BytecodeAddress::new(10, AddressType::Heap);
+        assert_eq!(address.advance_checked(3, 3).unwrap(), BytecodeAddress::new(13, AddressType::Heap));
+
+        let error = address.advance_checked(4, 3).unwrap_err();
+        assert_eq!(error.to_string(), "offset 4 from address addr(10) is out of bounds: region ends at offset 3");
+    }
+}
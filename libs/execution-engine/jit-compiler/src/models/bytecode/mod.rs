@@ -29,6 +29,8 @@ use std::{
 
 #[cfg(any(test, feature = "builder"))]
 pub mod builder;
+#[cfg(feature = "text_repr")]
+pub mod diff;
 pub mod memory;
 #[macro_use]
 pub(crate) mod utils;
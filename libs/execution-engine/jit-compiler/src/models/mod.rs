@@ -46,7 +46,7 @@ impl Deref for SourceFiles {
 #[cfg(feature = "text_repr")]
 impl From<&MIRSourceFiles> for SourceFiles {
     fn from(value: &MIRSourceFiles) -> Self {
-        SourceFiles(value.clone())
+        SourceFiles(BTreeMap::clone(value))
     }
 }
 
@@ -60,7 +60,13 @@ mod tests {
         bytecode2protocol::Bytecode2Protocol,
         mir2bytecode::MIR2Bytecode,
         models::{bytecode::ProgramBytecode, protocols::ProtocolsModel},
+        Program,
     };
+    use nada_compiler_backend::{
+        mir::{Party, SourceRefIndex},
+        program_contract::{Output, ProgramContract},
+    };
+    use nada_value::NadaType;
     use test_programs::PROGRAMS;
 
     pub(crate) fn compile_bytecode(program_id: &str) -> Result<ProgramBytecode, Error> {
@@ -75,4 +81,23 @@ mod tests {
         let program = Bytecode2Protocol::transform(MPCProtocolFactory, &bytecode)?;
         Ok(program)
     }
+
+    #[test]
+    fn program_outputs() {
+        let party = |name: &str| Party { name: name.to_string(), source_ref_index: SourceRefIndex::default() };
+        let output = |name: &str, party, ty| Output { name: name.to_string(), party, ty };
+        let contract = ProgramContract {
+            parties: vec![party("Dealer"), party("Alice"), party("Bob")],
+            outputs: vec![output("sum", 2, NadaType::SecretInteger), output("is_greater", 1, NadaType::Boolean)],
+            ..Default::default()
+        };
+        let program = Program::<MPCProtocol> { contract, body: Default::default() };
+        assert_eq!(
+            program.outputs(),
+            vec![
+                ("sum".to_string(), "Bob".to_string(), NadaType::SecretInteger),
+                ("is_greater".to_string(), "Alice".to_string(), NadaType::Boolean),
+            ]
+        );
+    }
 }
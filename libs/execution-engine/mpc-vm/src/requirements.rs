@@ -1,14 +1,18 @@
 //! Requirements analysis.
 
-use crate::protocols::MPCProtocol;
+use crate::{bytecode2protocol::MPCProtocolFactory, protocols::MPCProtocol};
 use anyhow::{anyhow, Error};
+use indexmap::IndexMap;
 pub use jit_compiler::requirements::ProgramRequirements;
-use jit_compiler::{models::protocols::Protocol, Program};
+use jit_compiler::{
+    bytecode2protocol::Bytecode2Protocol, mir2bytecode::MIR2Bytecode, models::protocols::Protocol, Program,
+};
+use nada_compiler_backend::mir::{proto::ConvertProto, ProgramMIR};
 use std::collections::HashMap;
-use strum::Display;
+use strum::{Display, EnumIter, IntoEnumIterator};
 
 /// The runtime requirement types
-#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Copy, Clone, Display)]
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Copy, Clone, Display, EnumIter)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RuntimeRequirementType {
     /// the type for COMPARE Elements
@@ -63,6 +67,18 @@ impl ProgramRequirements<MPCProtocol> for MPCProgramRequirements {
 }
 
 impl MPCProgramRequirements {
+    /// Calculates the requirements of a program from its serialized MIR.
+    ///
+    /// The requirements depend on the protocols the program is compiled into, so the MIR still has to be lowered
+    /// into bytecode and then into protocols. Only the program contract is not built, as the requirements don't
+    /// depend on it.
+    pub fn from_raw_mir(mir: &[u8]) -> Result<Self, Error> {
+        let mir = ProgramMIR::try_decode(mir).map_err(|e| anyhow!("error while deserializing MIR: {e}"))?;
+        let bytecode = MIR2Bytecode::transform(&mir)?;
+        let body = Bytecode2Protocol::transform(MPCProtocolFactory, &bytecode)?;
+        Self::from_program(&Program { contract: Default::default(), body })
+    }
+
     /// Return the ProgramRequirements instance with the selected compare elements
     pub fn with_compare_elements(self, elements: usize) -> Self {
         self.with_runtime_requirements(RuntimeRequirementType::Compare, elements)
@@ -118,6 +134,17 @@ impl MPCProgramRequirements {
         &self.runtime_elements
     }
 
+    /// Return the number of required elements for every runtime requirement type, including the ones that aren't
+    /// required, in the order the types are declared.
+    pub fn as_map(&self) -> IndexMap<RuntimeRequirementType, u64> {
+        RuntimeRequirementType::iter()
+            .map(|element_type| {
+                let count = u64::try_from(self.runtime_requirement(&element_type)).unwrap_or(u64::MAX);
+                (element_type, count)
+            })
+            .collect()
+    }
+
     /// Combine all requirements into one.
     ///
     /// Given a list of requirements, it combines them, returning an instance of
@@ -183,8 +210,10 @@ mod test {
         requirements::ProgramRequirements,
         JitCompiler, Program,
     };
+    use nada_compiler_backend::mir::proto::{ConvertProto, Message};
     use nada_value::NadaType;
     use rstest::rstest;
+    use strum::IntoEnumIterator;
     use test_programs::PROGRAMS;
 
     #[test]
@@ -364,4 +393,32 @@ mod test {
         assert_eq!(expected_requirements, requirements);
         Ok(())
     }
+
+    #[rstest]
+    #[case("big_recursion")]
+    #[case("greater_equal_mul")]
+    #[case("invalid_program")]
+    fn analyze_raw_mir(#[case] program_name: &str) -> Result<(), Error> {
+        let mir = PROGRAMS.mir(program_name)?;
+        let program = MPCCompiler::compile(mir.clone())?;
+        let requirements = MPCProgramRequirements::from_raw_mir(&mir.into_proto().encode_to_vec())?;
+        assert_eq!(MPCProgramRequirements::from_program(&program)?, requirements);
+        Ok(())
+    }
+
+    #[test]
+    fn requirements_as_map() -> Result<(), Error> {
+        let program = MPCCompiler::compile(PROGRAMS.mir("greater_equal_mul")?)?;
+        let requirements = MPCProgramRequirements::from_program(&program)?;
+        let expected: Vec<_> = RuntimeRequirementType::iter()
+            .map(|element_type| (element_type, if element_type == RuntimeRequirementType::Compare { 1 } else { 0 }))
+            .collect();
+        assert_eq!(requirements.as_map().into_iter().collect::<Vec<_>>(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn analyze_invalid_raw_mir() {
+        assert!(MPCProgramRequirements::from_raw_mir(&[0xff, 0xff]).is_err());
+    }
 }
@@ -14,7 +14,7 @@ use execution_engine_vm::{
 pub use execution_engine_vm::{
     metrics::{ExecutionMetrics, MetricsFormat},
     simulator::{
-        inputs::{InputGenerator, StaticInputGeneratorBuilder},
+        inputs::{InputGenerator, RandomInputGenerator, StaticInputGeneratorBuilder},
         ProgramSimulator, SimulationParameters,
     },
 };
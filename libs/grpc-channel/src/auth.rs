@@ -17,7 +17,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 use tonic::{service::Interceptor, Request, Status};
-use user_keypair::{ed25519::Ed25519PublicKey, secp256k1::Secp256k1PublicKey};
+use user_keypair::{ed25519::Ed25519PublicKey, secp256k1::Secp256k1PublicKey, Signature};
 
 const HEADER_NAME_BIN: &str = "x-nillion-token-bin";
 const HEADER_NAME_BASE64: &str = "x-nillion-token";
@@ -94,7 +94,8 @@ impl ServerAuthInterceptor {
                 .map_err(|_| Status::unauthenticated("invalid public key"))?
                 .into(),
         };
-        key.verify(&signature.into(), message).map_err(|_| Status::unauthenticated("invalid signature"))?;
+        key.verify(&Signature::new(key.kind(), signature), message)
+            .map_err(|_| Status::unauthenticated("invalid signature"))?;
         Ok(())
     }
 }
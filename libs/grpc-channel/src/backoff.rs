@@ -0,0 +1,106 @@
+//! Exponential backoff utilities.
+
+use rand::{thread_rng, Rng};
+use std::time::Duration;
+
+/// An exponential backoff policy.
+///
+/// The delay for each attempt doubles the one for the previous attempt, starting at `base` and capped at `max`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Backoff {
+    /// The delay for the first attempt.
+    pub base: Duration,
+
+    /// The maximum delay.
+    pub max: Duration,
+
+    /// The fraction of each delay that is randomized, between 0.0 and 1.0.
+    ///
+    /// A delay `d` with jitter `j` becomes a random delay in `[d * (1 - j), d]` so the jitter never makes it exceed
+    /// `max`. Values out of range are clamped.
+    pub jitter: f64,
+}
+
+impl Backoff {
+    /// Constructs a new backoff without jitter.
+    pub const fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max, jitter: 0.0 }
+    }
+
+    /// Sets the jitter for this backoff.
+    pub const fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the delay to wait before the given attempt, starting at 0.
+    pub fn next_delay(&self, attempt: u32) -> Duration {
+        self.next_delay_with_rng(attempt, &mut thread_rng())
+    }
+
+    /// Returns the delay to wait before the given attempt using the provided source of randomness for the jitter.
+    ///
+    /// This allows using a seeded rng to get deterministic delays.
+    pub fn next_delay_with_rng<R: Rng>(&self, attempt: u32, rng: &mut R) -> Duration {
+        let delay = 2u32.checked_pow(attempt).and_then(|factor| self.base.checked_mul(factor)).unwrap_or(self.max);
+        let delay = delay.min(self.max);
+        // `clamp` keeps NaN as is so it has to be handled separately.
+        let jitter = if self.jitter.is_nan() { 0.0 } else { self.jitter.clamp(0.0, 1.0) };
+        if jitter == 0.0 {
+            return delay;
+        }
+        let reduction = rng.gen_range(0.0..=jitter);
+        delay.mul_f64(1.0 - reduction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn delays_grow_and_cap() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        let delays: Vec<_> = (0..6).map(|attempt| backoff.next_delay(attempt)).collect();
+        let expected: Vec<_> = [100, 200, 400, 800, 1000, 1000].into_iter().map(Duration::from_millis).collect();
+        assert_eq!(delays, expected);
+        assert_eq!(backoff.next_delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_stays_in_range() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1)).with_jitter(0.5);
+        let mut rng = StdRng::seed_from_u64(42);
+        for attempt in 0..10 {
+            let undelayed = Backoff::new(backoff.base, backoff.max).next_delay(attempt);
+            let delay = backoff.next_delay_with_rng(attempt, &mut rng);
+            assert!(
+                delay <= undelayed && delay >= undelayed.mul_f64(0.5),
+                "delay {delay:?} out of range for {undelayed:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_jitter_is_deterministic() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1)).with_jitter(0.3);
+        let delays = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..5).map(|attempt| backoff.next_delay_with_rng(attempt, &mut rng)).collect::<Vec<_>>()
+        };
+        assert_eq!(delays(1), delays(1));
+    }
+
+    #[test]
+    fn invalid_jitter_is_clamped() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        let mut rng = StdRng::seed_from_u64(42);
+        let nan = backoff.clone().with_jitter(f64::NAN);
+        assert_eq!(nan.next_delay_with_rng(0, &mut rng), Duration::from_millis(100));
+        let negative = backoff.clone().with_jitter(-1.0);
+        assert_eq!(negative.next_delay_with_rng(0, &mut rng), Duration::from_millis(100));
+        let delay = backoff.with_jitter(2.0).next_delay_with_rng(0, &mut rng);
+        assert!(delay <= Duration::from_millis(100));
+    }
+}
@@ -72,6 +72,9 @@ pub struct SafePrimeBitsNotSupported;
 ///
 /// This type allows encoding a [ModularNumber] into a non-generic type that still allows
 /// decoding back into a [ModularNumber] if needed.
+///
+/// The value is always stored, and serialized, in little endian byte order. Use
+/// [ModularNumber::to_bytes_be] and [ModularNumber::from_bytes_be] when big endian bytes are needed.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EncodedModularNumber {
@@ -137,6 +140,10 @@ pub enum DecodeError {
     #[error("invalid value length")]
     ValueLength,
 
+    /// The value is not lower than the modulo.
+    #[error("value out of range")]
+    ValueOutOfRange,
+
     /// Not implemented.
     #[error("not implemented: {0}")]
     Unimplemented(String),
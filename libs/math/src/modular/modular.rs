@@ -1,7 +1,7 @@
 //! Modular Big Integers
 
 use super::{DecodeError, EncodedModularNumber, Generator, Modular, Overflow, ToU8Vec, TryFromU8Slice, UintType};
-use crate::modular::{RemEuclid, ToBigUint};
+use crate::modular::{AsBits, RemEuclid, ToBigUint};
 use crypto_bigint::{rand_core::CryptoRngCore, NonZero, RandomMod};
 use num_bigint::{BigInt, BigUint, Sign};
 use std::{
@@ -9,6 +9,7 @@ use std::{
     hash::Hash,
     str::FromStr,
 };
+use subtle::{Choice, ConstantTimeEq};
 
 /// A number that performs modular arithmetic in every operation.
 ///
@@ -119,6 +120,21 @@ impl<T: Modular> ModularNumber<T> {
         T::to_normal(&self.value)
     }
 
+    /// Checks whether this modular number is equal to another one in constant time.
+    ///
+    /// The `==` operator runs in variable time so this must be used instead whenever either side is derived from
+    /// secret data, like shares or secrets reconstructed from them.
+    pub fn ct_eq(&self, other: &Self) -> Choice {
+        self.value.ct_eq(&other.value)
+    }
+
+    /// Checks whether this modular number is different from another one in constant time.
+    ///
+    /// See [ModularNumber::ct_eq].
+    pub fn ct_ne(&self, other: &Self) -> Choice {
+        !self.ct_eq(other)
+    }
+
     /// Encode this modular number.
     ///
     /// This can be used to turn a `ModularNumber` into a non-generic type.
@@ -137,6 +153,49 @@ impl<T: Modular> ModularNumber<T> {
         Ok(ModularNumber::new(value))
     }
 
+    /// The number of bytes used to represent a number in this modulo.
+    pub fn byte_size() -> usize {
+        T::MODULO.bits().div_ceil(8)
+    }
+
+    /// Encodes this modular number as [ModularNumber::byte_size] little endian bytes.
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.into_value().to_u8_vec();
+        // The value is lower than the modulo so the truncated bytes are always zero.
+        bytes.truncate(Self::byte_size());
+        bytes
+    }
+
+    /// Encodes this modular number as [ModularNumber::byte_size] big endian bytes.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Decodes a modular number from [ModularNumber::byte_size] little endian bytes.
+    ///
+    /// The encoded value must be lower than the modulo, as produced by [ModularNumber::to_bytes_le].
+    pub fn from_bytes_le(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != Self::byte_size() {
+            return Err(DecodeError::ValueLength);
+        }
+        let value = T::Normal::try_from_u8_slice(bytes).map_err(|_| DecodeError::ValueLength)?;
+        if value >= T::MODULO {
+            return Err(DecodeError::ValueOutOfRange);
+        }
+        Ok(ModularNumber::new(value))
+    }
+
+    /// Decodes a modular number from [ModularNumber::byte_size] big endian bytes.
+    ///
+    /// The encoded value must be lower than the modulo, as produced by [ModularNumber::to_bytes_be].
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_bytes_le(&bytes)
+    }
+
     /// Absolute value of the modular number.
     pub fn abs(&self) -> Self {
         let mut r = *self;
@@ -437,4 +496,69 @@ mod test {
         assert!(!minus_two_modular.is_positive());
         assert!(two_modular.is_positive());
     }
+
+    #[rstest]
+    #[case::u64(U64SafePrime)]
+    #[case::u128(U128SafePrime)]
+    #[case::u256(U256SafePrime)]
+    fn constant_time_equality<T: Modular>(#[case] _prime: T) {
+        let value = ModularNumber::<T>::gen_random();
+        let same = ModularNumber::<T>::try_from(&BigUint::from(&value)).unwrap();
+        let other = value + &ModularNumber::ONE;
+        assert_eq!(bool::from(value.ct_eq(&same)), value == same);
+        assert_eq!(bool::from(value.ct_eq(&other)), value == other);
+        assert!(bool::from(value.ct_eq(&same)));
+        assert!(bool::from(value.ct_ne(&other)));
+        assert!(!bool::from(value.ct_ne(&same)));
+    }
+
+    #[rstest]
+    #[case::u64(U64SafePrime, 8)]
+    #[case::u128(U128SafePrime, 16)]
+    #[case::u256(U256SafePrime, 32)]
+    fn bytes_round_trip<T: Modular>(#[case] _prime: T, #[case] byte_size: usize) {
+        let value = ModularNumber::<T>::from_u32(0x01020304);
+        assert_eq!(ModularNumber::<T>::byte_size(), byte_size);
+
+        let le_bytes = value.to_bytes_le();
+        assert_eq!(le_bytes.len(), byte_size);
+        assert!(le_bytes.starts_with(&[4, 3, 2, 1]));
+        assert_eq!(ModularNumber::<T>::from_bytes_le(&le_bytes).unwrap(), value);
+
+        let be_bytes = value.to_bytes_be();
+        assert_eq!(be_bytes.len(), byte_size);
+        assert!(be_bytes.ends_with(&[1, 2, 3, 4]));
+        assert_eq!(ModularNumber::<T>::from_bytes_be(&be_bytes).unwrap(), value);
+
+        let random = ModularNumber::<T>::gen_random();
+        assert_eq!(ModularNumber::<T>::from_bytes_le(&random.to_bytes_le()).unwrap(), random);
+        assert_eq!(ModularNumber::<T>::from_bytes_be(&random.to_bytes_be()).unwrap(), random);
+    }
+
+    #[rstest]
+    #[case::u64(U64SafePrime)]
+    #[case::u128(U128SafePrime)]
+    #[case::u256(U256SafePrime)]
+    fn bytes_invalid_length<T: Modular>(#[case] _prime: T) {
+        let long = vec![0; ModularNumber::<T>::byte_size() + 1];
+        assert!(matches!(ModularNumber::<T>::from_bytes_le(&long), Err(DecodeError::ValueLength)));
+        let short = vec![0; ModularNumber::<T>::byte_size() - 1];
+        assert!(matches!(ModularNumber::<T>::from_bytes_be(&short), Err(DecodeError::ValueLength)));
+    }
+
+    #[rstest]
+    #[case::u64(U64SafePrime)]
+    #[case::u128(U128SafePrime)]
+    #[case::u256(U256SafePrime)]
+    fn bytes_out_of_range<T: Modular>(#[case] _prime: T) {
+        let mut modulo = T::MODULO.to_u8_vec();
+        modulo.truncate(ModularNumber::<T>::byte_size());
+        assert!(matches!(ModularNumber::<T>::from_bytes_le(&modulo), Err(DecodeError::ValueOutOfRange)));
+        modulo.reverse();
+        assert!(matches!(ModularNumber::<T>::from_bytes_be(&modulo), Err(DecodeError::ValueOutOfRange)));
+
+        let max = vec![0xff; ModularNumber::<T>::byte_size()];
+        assert!(matches!(ModularNumber::<T>::from_bytes_le(&max), Err(DecodeError::ValueOutOfRange)));
+        assert!(matches!(ModularNumber::<T>::from_bytes_be(&max), Err(DecodeError::ValueOutOfRange)));
+    }
 }
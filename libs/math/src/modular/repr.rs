@@ -15,6 +15,7 @@ use std::{
     ops::BitAnd,
 };
 pub use subtle::ConditionallySelectable;
+use subtle::ConstantTimeEq;
 use thiserror::Error;
 
 /// A type that defines how modular arithmetic is performed.
@@ -233,7 +234,7 @@ pub trait UintType: ModOps<Self::Arithmetic, Exponent = Self::Normal> + 'static
     ///
     /// This maps to the Montgomery form for a number and therefore should not be used when you
     /// want access to the "real" number, but instead **only** for arithmetic operations.
-    type Arithmetic: PartialEq + Eq + Debug + Clone + Copy + Send + Sync;
+    type Arithmetic: PartialEq + Eq + Debug + Clone + Copy + Send + Sync + ConstantTimeEq;
 
     /// The zero value in arithmetic form.
     const ARITHMETIC_ZERO: Self::Arithmetic;
@@ -0,0 +1,71 @@
+//! Polynomial interpolation.
+
+use crate::{
+    errors::InterpolationError,
+    fields::PrimeField,
+    modular::{ModularNumber, Prime},
+    polynomial::{point::Point, point_sequence::PointSequence},
+};
+
+/// Computes the constant term of the polynomial that goes through the given `(x, y)` points using Lagrange
+/// interpolation.
+///
+/// This is what's needed to reconstruct a secret from its shares, where each point's x coordinate is the abscissa
+/// of the party that holds a share and its y coordinate is the share itself.
+pub fn lagrange_interpolate_at_zero<T: Prime>(
+    points: &[(ModularNumber<T>, ModularNumber<T>)],
+) -> Result<ModularNumber<T>, InterpolationError> {
+    let mut sequence = PointSequence::<PrimeField<T>>::default();
+    for (x, y) in points {
+        sequence.push(Point::new(x.into_value(), *y));
+    }
+    if sequence.has_duplicates() {
+        return Err(InterpolationError::DuplicateAbscissas);
+    }
+    sequence.lagrange_interpolate()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        fields::{Field, PrimeField},
+        modular::U64SafePrime,
+        polynomial::Polynomial,
+    };
+
+    type Prime = U64SafePrime;
+
+    #[test]
+    fn reconstruct_secret() {
+        let secret = ModularNumber::<Prime>::from_u32(42);
+        let degree = 3;
+        let mut rng = rand::thread_rng();
+        let mut coefficients = vec![secret];
+        coefficients.extend((0..degree).map(|_| PrimeField::<Prime>::gen_random_element(&mut rng)));
+        let polynomial = Polynomial::<PrimeField<Prime>>::new(coefficients);
+
+        let points: Vec<_> = (1..=degree + 1)
+            .map(|x| {
+                let x = ModularNumber::from_u32(x);
+                (x, polynomial.eval(&x).unwrap())
+            })
+            .collect();
+        assert_eq!(lagrange_interpolate_at_zero(&points).unwrap(), secret);
+    }
+
+    #[test]
+    fn duplicate_abscissas() {
+        let points = vec![
+            (ModularNumber::<Prime>::from_u32(1), ModularNumber::from_u32(10)),
+            (ModularNumber::from_u32(2), ModularNumber::from_u32(20)),
+            (ModularNumber::from_u32(1), ModularNumber::from_u32(30)),
+        ];
+        assert!(matches!(lagrange_interpolate_at_zero(&points), Err(InterpolationError::DuplicateAbscissas)));
+    }
+
+    #[test]
+    fn empty_points() {
+        assert!(matches!(lagrange_interpolate_at_zero::<Prime>(&[]), Err(InterpolationError::EmptySequence)));
+    }
+}
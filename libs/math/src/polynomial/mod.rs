@@ -1,11 +1,13 @@
 //! Polynomial operations
 
 pub mod bivariate;
+pub mod interpolation;
 pub mod ops;
 pub mod point;
 pub mod point_sequence;
 pub mod polynomial;
 
+pub use interpolation::*;
 #[allow(unused_imports)]
 pub use ops::*;
 pub use polynomial::*;
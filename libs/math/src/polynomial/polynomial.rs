@@ -68,6 +68,19 @@ impl<F: Field> Polynomial<F> {
         Ok(eval)
     }
 
+    /// Evaluates the polynomial at every given x using Horner's method.
+    ///
+    /// The results are returned in the same order as the input points.
+    pub fn evaluate_many(&self, points: &[F::Element]) -> Vec<F::Element> {
+        let mut evals = vec![F::ZERO; points.len()];
+        for coefficient in self.coefficients.iter().rev() {
+            for (eval, x) in evals.iter_mut().zip(points) {
+                *eval = *eval * x + coefficient;
+            }
+        }
+        evals
+    }
+
     /// Get coefficient at index.
     pub fn get_coefficient(&self, idx: usize) -> Result<&F::Element, PolynomialError> {
         return self.coefficients.get(idx).ok_or(PolynomialError::CoefficientNotFound);
@@ -118,6 +131,19 @@ mod test {
         assert_eq!(result, ModularNumber::from_u32(4));
     }
 
+    #[test]
+    fn test_evaluate_many() {
+        type SafePrimeField = PrimeField<crate::modular::U64SafePrime>;
+        let mut rng = rand::thread_rng();
+        let coefficients = (0..10).map(|_| SafePrimeField::gen_random_element(&mut rng)).collect();
+        let polynomial = Polynomial::<SafePrimeField>::new(coefficients);
+        let points: Vec<_> = (1..=20).map(ModularNumber::from_u32).collect();
+
+        let results = polynomial.evaluate_many(&points);
+        let expected: Vec<_> = points.iter().map(|x| polynomial.eval(x).unwrap()).collect();
+        assert_eq!(results, expected);
+    }
+
     #[test]
     fn test_encode_decode() {
         /// We need to check on a prime field, prime test numbers can't be encoded
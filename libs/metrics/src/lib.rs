@@ -103,7 +103,17 @@ pub type InitializeError = <Metrics as MetricsEngine>::InitializeError;
 
 /// Initialize the system. See [`metrics::MetricsEngine::initialize`].
 pub fn initialize(static_labels: HashMap<String, String>) -> Result<Registry, InitializeError> {
-    Metrics::initialize(static_labels)
+    Metrics::initialize(static_labels, None)
+}
+
+/// Initialize the system using a namespace. See [`metrics::MetricsEngine::initialize`].
+///
+/// The name of every metric created after this call is prefixed with `<namespace>_`.
+pub fn initialize_with_namespace<S: Into<String>>(
+    static_labels: HashMap<String, String>,
+    namespace: S,
+) -> Result<Registry, InitializeError> {
+    Metrics::initialize(static_labels, Some(namespace.into()))
 }
 
 /// A prelude that imports all important types.
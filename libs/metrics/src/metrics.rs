@@ -198,7 +198,13 @@ pub trait MetricsEngine {
     /// chosen so that metrics don't break tests, as a single metric can only be defined once,
     /// which means any type that defines a metric can only be instantiated once during tests which
     /// is an undesirable restriction.
-    fn initialize(static_labels: HashMap<String, String>) -> Result<Self::Registry, Self::InitializeError>;
+    ///
+    /// If a namespace is provided, the name of every metric created afterwards is prefixed with
+    /// `<namespace>_`.
+    fn initialize(
+        static_labels: HashMap<String, String>,
+        namespace: Option<String>,
+    ) -> Result<Self::Registry, Self::InitializeError>;
 }
 
 #[cfg(test)]
@@ -186,7 +186,10 @@ impl MetricsEngine for NoopMetricsEngine {
     type Registry = NoopRegistry;
     type InitializeError = NoopError;
 
-    fn initialize(_static_labels: HashMap<String, String>) -> Result<Self::Registry, Self::InitializeError> {
+    fn initialize(
+        _static_labels: HashMap<String, String>,
+        _namespace: Option<String>,
+    ) -> Result<Self::Registry, Self::InitializeError> {
         Ok(NoopRegistry)
     }
 }
@@ -12,6 +12,7 @@ static GLOBALS: OnceCell<Globals> = OnceCell::new();
 struct Globals {
     registry: prometheus::Registry,
     static_labels: HashMap<String, String>,
+    namespace: Option<String>,
 }
 
 fn register<T>(metric: &T) -> Result<(), prometheus::Error>
@@ -32,6 +33,9 @@ where
     let mut options = prometheus::Opts::new(name, help);
     if let Some(globals) = GLOBALS.get() {
         options = options.const_labels(globals.static_labels.clone());
+        if let Some(namespace) = &globals.namespace {
+            options = options.namespace(namespace);
+        }
     }
     options
 }
@@ -258,9 +262,12 @@ impl MetricsEngine for PrometheusMetricsEngine {
     type Registry = PrometheusRegistry;
     type InitializeError = InitializeError;
 
-    fn initialize(static_labels: HashMap<String, String>) -> Result<Self::Registry, Self::InitializeError> {
+    fn initialize(
+        static_labels: HashMap<String, String>,
+        namespace: Option<String>,
+    ) -> Result<Self::Registry, Self::InitializeError> {
         let registry = prometheus::Registry::default();
-        let globals = Globals { registry: registry.clone(), static_labels };
+        let globals = Globals { registry: registry.clone(), static_labels, namespace };
         GLOBALS.set(globals).ok().ok_or(InitializeError::AlreadyInitialized)?;
         Ok(PrometheusRegistry { registry })
     }
@@ -0,0 +1,28 @@
+#![cfg(feature = "prometheus-backend")]
+
+use metrics::{metrics::MetricsRegistry, prelude::*};
+use std::{collections::HashMap, time::Duration};
+
+#[test]
+fn metric_names_are_namespaced() {
+    let registry = metrics::initialize_with_namespace(HashMap::new(), "nilvm").expect("initialization failed");
+
+    let counter = Counter::new("foo_total", "Total number of foos", &[]).expect("creation failed");
+    counter.with_labels(&Default::default()).unwrap().inc();
+    let gauge = Gauge::new("foo_available_total", "Total number of available foos", &[]).expect("creation failed");
+    gauge.with_labels(&Default::default()).unwrap().set(42);
+    let histogram = Histogram::<Duration>::new(
+        "foo_latency_seconds",
+        "Latency taken by each foo request",
+        &[],
+        &[Duration::from_millis(100), Duration::from_secs(2)],
+    )
+    .expect("creation failed");
+    histogram.with_labels(&Default::default()).unwrap().observe(&Duration::from_millis(800));
+
+    let output = registry.encode_metrics().expect("encoding failed");
+    assert!(output.contains("nilvm_foo_total 1"), "{output}");
+    assert!(output.contains("nilvm_foo_available_total 42"), "{output}");
+    assert!(output.contains("nilvm_foo_latency_seconds_count 1"), "{output}");
+    assert!(!output.lines().any(|line| line.starts_with("foo_")), "{output}");
+}
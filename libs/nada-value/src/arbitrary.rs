@@ -0,0 +1,120 @@
+//! Proptest strategies to generate arbitrary Nada types and clear values.
+//!
+//! [`NadaType`] is defined in another crate so it can't implement [`Arbitrary`] here, [`any_nada_type`] can be used
+//! instead. Values can be generated for a given type using [`any_clear_value`], or alongside a random type using
+//! `any::<NadaValue<Clear>>()`.
+
+use crate::{clear::Clear, NadaValue};
+use indexmap::IndexMap;
+use nada_type::{NadaType, MAX_RECURSION_DEPTH};
+use proptest::{
+    collection::{vec, SizeRange},
+    prelude::*,
+};
+
+/// The maximum nesting depth of the generated compound types.
+const MAX_DEPTH: u32 = 4;
+
+/// The maximum number of elements in each of the generated compound types.
+const MAX_ELEMENTS: usize = 4;
+
+const _: () = assert!((MAX_DEPTH as usize) < MAX_RECURSION_DEPTH);
+
+fn elements() -> SizeRange {
+    (1..=MAX_ELEMENTS).into()
+}
+
+/// Returns a strategy that generates types that have clear values.
+///
+/// Generated types are at most [`MAX_DEPTH`] levels deep and compound types have at most [`MAX_ELEMENTS`] elements,
+/// which keeps them well below the recursion limits.
+pub fn any_nada_type() -> impl Strategy<Value = NadaType> {
+    let leaf = prop_oneof![
+        Just(NadaType::Integer),
+        Just(NadaType::UnsignedInteger),
+        Just(NadaType::Boolean),
+        Just(NadaType::SecretInteger),
+        Just(NadaType::SecretUnsignedInteger),
+        Just(NadaType::SecretBoolean),
+        Just(NadaType::SecretBlob),
+    ];
+    leaf.prop_recursive(MAX_DEPTH, 64, MAX_ELEMENTS as u32, |inner| {
+        prop_oneof![
+            (inner.clone(), 1..=MAX_ELEMENTS)
+                .prop_filter_map("invalid array", |(inner_type, size)| NadaType::new_array(inner_type, size).ok()),
+            (inner.clone(), inner.clone())
+                .prop_filter_map("invalid tuple", |(left, right)| NadaType::new_tuple(left, right).ok()),
+            vec(inner.clone(), elements()).prop_filter_map("invalid ntuple", |types| NadaType::new_n_tuple(types).ok()),
+            vec(("[a-z]{1,8}", inner), elements())
+                .prop_filter_map("invalid object", |types| NadaType::new_object(types.into_iter().collect()).ok()),
+        ]
+    })
+}
+
+/// Returns a strategy that generates clear values of the given type.
+///
+/// Returns `None` if the type, or any type it contains, has no clear values, like shares or keys.
+pub fn any_clear_value(ty: &NadaType) -> Option<BoxedStrategy<NadaValue<Clear>>> {
+    let strategy = match ty {
+        NadaType::Integer => any::<i64>().prop_map(NadaValue::<Clear>::new_integer).boxed(),
+        NadaType::UnsignedInteger => any::<u64>().prop_map(NadaValue::<Clear>::new_unsigned_integer).boxed(),
+        NadaType::Boolean => any::<bool>().prop_map(NadaValue::<Clear>::new_boolean).boxed(),
+        NadaType::SecretInteger => any::<i64>().prop_map(NadaValue::<Clear>::new_secret_integer).boxed(),
+        NadaType::SecretUnsignedInteger => {
+            any::<u64>().prop_map(NadaValue::<Clear>::new_secret_unsigned_integer).boxed()
+        }
+        NadaType::SecretBoolean => any::<bool>().prop_map(NadaValue::<Clear>::new_secret_boolean).boxed(),
+        NadaType::SecretBlob => vec(any::<u8>(), 0..64).prop_map(NadaValue::<Clear>::new_secret_blob).boxed(),
+        NadaType::ShamirShareInteger
+        | NadaType::ShamirShareUnsignedInteger
+        | NadaType::ShamirShareBoolean
+        | NadaType::EcdsaPrivateKey
+        | NadaType::EcdsaDigestMessage
+        | NadaType::EcdsaSignature
+        | NadaType::EcdsaPublicKey
+        | NadaType::StoreId
+        | NadaType::EddsaPrivateKey
+        | NadaType::EddsaPublicKey
+        | NadaType::EddsaSignature
+        | NadaType::EddsaMessage => return None,
+        NadaType::Array { inner_type, size } => {
+            let inner_type = inner_type.as_ref().clone();
+            vec(any_clear_value(&inner_type)?, *size)
+                .prop_filter_map("invalid array", move |values| NadaValue::new_array(inner_type.clone(), values).ok())
+                .boxed()
+        }
+        NadaType::Tuple { left_type, right_type } => (any_clear_value(left_type)?, any_clear_value(right_type)?)
+            .prop_filter_map("invalid tuple", |(left, right)| NadaValue::new_tuple(left, right).ok())
+            .boxed(),
+        NadaType::NTuple { types } => types
+            .iter()
+            .map(any_clear_value)
+            .collect::<Option<Vec<_>>>()?
+            .prop_filter_map("invalid ntuple", |values| NadaValue::new_n_tuple(values).ok())
+            .boxed(),
+        NadaType::Object { types } => types
+            .iter()
+            .map(|(key, ty)| {
+                let key = key.clone();
+                any_clear_value(ty).map(|values| values.prop_map(move |value| (key.clone(), value)))
+            })
+            .collect::<Option<Vec<_>>>()?
+            .prop_filter_map("invalid object", |values| {
+                NadaValue::new_object(values.into_iter().collect::<IndexMap<_, _>>()).ok()
+            })
+            .boxed(),
+    };
+    Some(strategy)
+}
+
+impl Arbitrary for NadaValue<Clear> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any_nada_type()
+            .prop_filter_map("type without clear values", |ty| any_clear_value(&ty))
+            .prop_flat_map(|values| values)
+            .boxed()
+    }
+}
@@ -5,8 +5,8 @@
 
 use crate::{NadaInt, NadaUint, NadaValue, NeverPrimitiveType};
 use generic_ec::curves::{Ed25519, Secp256k1};
-use nada_type::PrimitiveTypes;
-use std::fmt::Display;
+use nada_type::{NadaType, PrimitiveTypes, TypeError};
+use std::fmt::{self, Display, Write};
 use threshold_keypair::{
     privatekey::ThresholdPrivateKey,
     publickey::EcdsaPublicKeyArray,
@@ -53,6 +53,13 @@ impl PrimitiveTypes for Clear {
     type EcdsaSignature = EcdsaSignature;
 }
 
+/// Formats clear values in a stable, human readable format.
+///
+/// Primitive values are rendered as `<type>(<value>)`, e.g. `SecretInteger(13)`, and byte based values list their
+/// bytes, e.g. `Blob(1, 2, 3)`. Compound values wrap their elements: `Array(Integer(1), Integer(2))`,
+/// `Tuple(Integer(1), Boolean(true))`, `NTuple(Integer(1), Integer(2))` and `Object(a:Integer(1))`.
+///
+/// Unlike the [`Debug`](std::fmt::Debug) output, this format is stable and tools and tests can rely on it.
 impl Display for NadaValue<Clear> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -122,3 +129,298 @@ impl Display for NadaValue<Clear> {
         }
     }
 }
+
+impl NadaValue<Clear> {
+    /// Formats this value, replacing any secret primitive value with `<secret>`.
+    ///
+    /// Public values are rendered the same way as the [`Display`] implementation does. Compound values keep their
+    /// structure and display their size so the output is still useful when logging values that contain secrets.
+    pub fn fmt_redacted(&self) -> String {
+        let mut output = String::new();
+        // Writing into a `String` can't fail.
+        let _ = self.write_redacted(&mut output);
+        output
+    }
+
+    fn write_redacted(&self, f: &mut String) -> fmt::Result {
+        use NadaValue::*;
+        match self {
+            Integer(_)
+            | UnsignedInteger(_)
+            | Boolean(_)
+            | EcdsaDigestMessage(_)
+            | EcdsaPublicKey(_)
+            | EddsaPublicKey(_)
+            | EddsaSignature(_)
+            | EddsaMessage(_)
+            | StoreId(_) => write!(f, "{self}"),
+            SecretInteger(_)
+            | SecretUnsignedInteger(_)
+            | SecretBoolean(_)
+            | SecretBlob(_)
+            | ShamirShareInteger(_)
+            | ShamirShareUnsignedInteger(_)
+            | ShamirShareBoolean(_)
+            | EcdsaPrivateKey(_)
+            | EcdsaSignature(_)
+            | EddsaPrivateKey(_) => write!(f, "{}(<secret>)", self.to_type_kind()),
+            Array { values, .. } => {
+                write!(f, "Array[{}](", values.len())?;
+                Self::write_redacted_list(f, values)?;
+                f.write_char(')')
+            }
+            Tuple { left, right } => {
+                f.write_str("Tuple(")?;
+                left.write_redacted(f)?;
+                f.write_str(", ")?;
+                right.write_redacted(f)?;
+                f.write_char(')')
+            }
+            NTuple { values } => {
+                write!(f, "NTuple[{}](", values.len())?;
+                Self::write_redacted_list(f, values)?;
+                f.write_char(')')
+            }
+            Object { values } => {
+                write!(f, "Object[{}](", values.len())?;
+                for (index, (key, value)) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}:")?;
+                    value.write_redacted(f)?;
+                }
+                f.write_char(')')
+            }
+        }
+    }
+
+    /// Returns the zero value for a type.
+    ///
+    /// Numeric types are zero, booleans are false and compound types are built out of the zero values of their
+    /// inner types. Types that have no sensible zero value, like blobs or keys, return an error.
+    pub fn zeroed(ty: &NadaType) -> Result<Self, TypeError> {
+        use NadaType::*;
+        match ty {
+            Integer => Ok(Self::new_integer(0)),
+            UnsignedInteger => Ok(Self::new_unsigned_integer(0u64)),
+            Boolean => Ok(Self::new_boolean(false)),
+            SecretInteger => Ok(Self::new_secret_integer(0)),
+            SecretUnsignedInteger => Ok(Self::new_secret_unsigned_integer(0u64)),
+            SecretBoolean => Ok(Self::new_secret_boolean(false)),
+            Array { inner_type, size } => {
+                let values = (0..*size).map(|_| Self::zeroed(inner_type)).collect::<Result<_, _>>()?;
+                Self::new_array(inner_type.as_ref().clone(), values)
+            }
+            Tuple { left_type, right_type } => Self::new_tuple(Self::zeroed(left_type)?, Self::zeroed(right_type)?),
+            NTuple { types } => Self::new_n_tuple(types.iter().map(Self::zeroed).collect::<Result<_, _>>()?),
+            Object { types } => {
+                Self::new_object(types.iter().map(|(key, ty)| Ok((key.clone(), Self::zeroed(ty)?))).collect::<Result<
+                    _,
+                    TypeError,
+                >>(
+                )?)
+            }
+            SecretBlob
+            | ShamirShareInteger
+            | ShamirShareUnsignedInteger
+            | ShamirShareBoolean
+            | EcdsaDigestMessage
+            | EcdsaPrivateKey
+            | EcdsaSignature
+            | EcdsaPublicKey
+            | StoreId
+            | EddsaPrivateKey
+            | EddsaPublicKey
+            | EddsaSignature
+            | EddsaMessage => Err(TypeError::NoZeroValue(ty.clone())),
+        }
+    }
+
+    /// Checks whether two values are logically equal.
+    ///
+    /// Unlike `==`, this ignores how values are represented internally: signed and unsigned integers with the same
+    /// visibility are compared by their numeric value and arrays are compared element by element, regardless of the
+    /// inner type they were declared with. The structure of compound values, as well as whether primitive values are
+    /// public or secret, must still match.
+    pub fn semantic_eq(&self, other: &Self) -> bool {
+        use NadaValue::*;
+        match (self, other) {
+            (Integer(left), UnsignedInteger(right)) | (SecretInteger(left), SecretUnsignedInteger(right)) => {
+                left.to_biguint().as_ref() == Some(&**right)
+            }
+            (UnsignedInteger(left), Integer(right)) | (SecretUnsignedInteger(left), SecretInteger(right)) => {
+                right.to_biguint().as_ref() == Some(&**left)
+            }
+            (Array { values: left, .. }, Array { values: right, .. })
+            | (NTuple { values: left }, NTuple { values: right }) => Self::semantic_eq_list(left, right),
+            (Tuple { left: left_first, right: left_second }, Tuple { left: right_first, right: right_second }) => {
+                left_first.semantic_eq(right_first) && left_second.semantic_eq(right_second)
+            }
+            (Object { values: left }, Object { values: right }) => {
+                left.len() == right.len()
+                    && left.iter().all(|(key, value)| right.get(key).is_some_and(|other| value.semantic_eq(other)))
+            }
+            _ => self == other,
+        }
+    }
+
+    fn semantic_eq_list(left: &[Self], right: &[Self]) -> bool {
+        left.len() == right.len() && left.iter().zip(right).all(|(left, right)| left.semantic_eq(right))
+    }
+
+    fn write_redacted_list(f: &mut String, values: &[Self]) -> fmt::Result {
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            value.write_redacted(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zeroed_nested_array() {
+        let inner_type = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: 2 };
+        let ty = NadaType::Array { inner_type: Box::new(inner_type.clone()), size: 3 };
+        let value = NadaValue::<Clear>::zeroed(&ty).unwrap();
+        assert_eq!(value.to_type(), ty);
+
+        let zeros = NadaValue::new_array(
+            NadaType::SecretInteger,
+            vec![NadaValue::new_secret_integer(0), NadaValue::new_secret_integer(0)],
+        )
+        .unwrap();
+        let expected = NadaValue::new_array(inner_type, vec![zeros.clone(), zeros.clone(), zeros]).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn zeroed_blob() {
+        let result = NadaValue::<Clear>::zeroed(&NadaType::SecretBlob);
+        assert!(matches!(result, Err(TypeError::NoZeroValue(NadaType::SecretBlob))));
+    }
+
+    #[test]
+    fn display_scalars() {
+        assert_eq!(NadaValue::<Clear>::new_secret_integer(-13).to_string(), "SecretInteger(-13)");
+        assert_eq!(NadaValue::<Clear>::new_unsigned_integer(13u32).to_string(), "UnsignedInteger(13)");
+        assert_eq!(NadaValue::<Clear>::new_secret_boolean(true).to_string(), "SecretBoolean(true)");
+        assert_eq!(NadaValue::<Clear>::new_secret_blob(vec![1, 2, 3]).to_string(), "Blob(1, 2, 3)");
+    }
+
+    #[test]
+    fn display_array() {
+        let value = NadaValue::<Clear>::new_array(
+            NadaType::SecretInteger,
+            vec![NadaValue::new_secret_integer(1), NadaValue::new_secret_integer(2), NadaValue::new_secret_integer(3)],
+        )
+        .unwrap();
+        assert_eq!(value.to_string(), "Array(SecretInteger(1), SecretInteger(2), SecretInteger(3))");
+    }
+
+    #[test]
+    fn display_tuples() {
+        let value =
+            NadaValue::<Clear>::new_tuple(NadaValue::new_integer(1), NadaValue::new_secret_boolean(false)).unwrap();
+        assert_eq!(value.to_string(), "Tuple(Integer(1), SecretBoolean(false))");
+
+        let value =
+            NadaValue::<Clear>::new_n_tuple(vec![NadaValue::new_integer(1), NadaValue::new_boolean(true)]).unwrap();
+        assert_eq!(value.to_string(), "NTuple(Integer(1), Boolean(true))");
+
+        let value = NadaValue::<Clear>::new_object([("a".to_string(), NadaValue::new_integer(1))].into()).unwrap();
+        assert_eq!(value.to_string(), "Object(a:Integer(1))");
+    }
+
+    #[test]
+    fn fmt_redacted_array() {
+        let value = NadaValue::<Clear>::new_array(
+            NadaType::Integer,
+            vec![NadaValue::new_integer(1), NadaValue::new_integer(2)],
+        )
+        .unwrap();
+        assert_eq!(value.fmt_redacted(), "Array[2](Integer(1), Integer(2))");
+
+        let value = NadaValue::<Clear>::new_array(
+            NadaType::SecretInteger,
+            vec![NadaValue::new_secret_integer(1), NadaValue::new_secret_integer(2)],
+        )
+        .unwrap();
+        assert_eq!(value.fmt_redacted(), "Array[2](SecretInteger(<secret>), SecretInteger(<secret>))");
+    }
+
+    #[test]
+    fn fmt_redacted_compound() {
+        let value = NadaValue::<Clear>::new_tuple(
+            NadaValue::new_integer(42),
+            NadaValue::new_object(
+                [
+                    ("a".to_string(), NadaValue::new_secret_boolean(true)),
+                    ("b".to_string(), NadaValue::new_boolean(false)),
+                ]
+                .into(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(value.fmt_redacted(), "Tuple(Integer(42), Object[2](a:SecretBoolean(<secret>), b:Boolean(false)))");
+    }
+
+    #[test]
+    fn semantic_eq_integers() {
+        let signed = NadaValue::<Clear>::new_integer(42);
+        let unsigned = NadaValue::<Clear>::new_unsigned_integer(42u32);
+        assert!(signed.semantic_eq(&unsigned));
+        assert!(unsigned.semantic_eq(&signed));
+        assert!(NadaValue::<Clear>::new_secret_integer(7).semantic_eq(&NadaValue::new_secret_unsigned_integer(7u32)));
+        assert!(!NadaValue::<Clear>::new_integer(-1).semantic_eq(&NadaValue::new_unsigned_integer(1u32)));
+        assert!(!signed.semantic_eq(&NadaValue::new_secret_unsigned_integer(42u32)));
+    }
+
+    #[test]
+    fn semantic_eq_compound() {
+        let left = NadaValue::<Clear>::new_array(
+            NadaType::Integer,
+            vec![NadaValue::new_integer(1), NadaValue::new_integer(2)],
+        )
+        .unwrap();
+        let right = NadaValue::<Clear>::new_array(
+            NadaType::UnsignedInteger,
+            vec![NadaValue::new_unsigned_integer(1u32), NadaValue::new_unsigned_integer(2u32)],
+        )
+        .unwrap();
+        assert!(left.semantic_eq(&right));
+
+        let object = |a: NadaValue<Clear>, b: NadaValue<Clear>| {
+            NadaValue::<Clear>::new_object([("a".to_string(), a), ("b".to_string(), b)].into()).unwrap()
+        };
+        assert!(
+            object(NadaValue::new_integer(3), NadaValue::new_boolean(true))
+                .semantic_eq(&object(NadaValue::new_unsigned_integer(3u32), NadaValue::new_boolean(true)))
+        );
+        assert!(
+            !object(NadaValue::new_integer(3), NadaValue::new_boolean(true))
+                .semantic_eq(&object(NadaValue::new_integer(3), NadaValue::new_boolean(false)))
+        );
+    }
+
+    #[test]
+    fn semantic_eq_different_shapes() {
+        let elements = vec![NadaValue::<Clear>::new_integer(1), NadaValue::new_integer(2)];
+        let array = NadaValue::<Clear>::new_array(NadaType::Integer, elements.clone()).unwrap();
+        let ntuple = NadaValue::<Clear>::new_n_tuple(elements).unwrap();
+        assert!(!array.semantic_eq(&ntuple));
+
+        let shorter = NadaValue::<Clear>::new_array(NadaType::Integer, vec![NadaValue::new_integer(1)]).unwrap();
+        assert!(!array.semantic_eq(&shorter));
+
+        let tuple = NadaValue::<Clear>::new_tuple(NadaValue::new_integer(1), NadaValue::new_integer(2)).unwrap();
+        assert!(!tuple.semantic_eq(&ntuple));
+    }
+}
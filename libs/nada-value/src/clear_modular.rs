@@ -8,9 +8,12 @@ use crate::{
     errors::{ClearModularError, NonPrimitiveValue},
     NadaValue, NeverPrimitiveType,
 };
-use math_lib::modular::{Modular, ModularNumber};
+use math_lib::{
+    conversions::boolean_from_bigint,
+    modular::{Modular, ModularNumber},
+};
 use nada_type::{NadaType, PrimitiveTypes};
-use num_bigint::BigUint;
+use num_bigint::{BigInt, BigUint};
 use std::{fmt::Debug, marker::PhantomData, ops::Mul};
 
 /// Clear modular values are the values provided by the user, in modular form.
@@ -136,6 +139,45 @@ impl<T: Modular> NadaValue<ClearModular<T>> {
         }
         resultant_values.pop().ok_or(ClearModularError::NotEnoughValues)
     }
+
+    /// Converts this value back into its clear form, keeping its structure.
+    ///
+    /// Integers above `prime / 2` are interpreted as negative numbers, and booleans must be either 0 or 1.
+    pub fn to_clear(&self) -> Result<NadaValue<Clear>, ClearModularError> {
+        let value = match self {
+            NadaValue::Integer(value) => NadaValue::new_integer(BigInt::from(value)),
+            NadaValue::SecretInteger(value) => NadaValue::new_secret_integer(BigInt::from(value)),
+            NadaValue::UnsignedInteger(value) => NadaValue::new_unsigned_integer(BigUint::from(value)),
+            NadaValue::SecretUnsignedInteger(value) => NadaValue::new_secret_unsigned_integer(BigUint::from(value)),
+            NadaValue::Boolean(value) => NadaValue::new_boolean(boolean_from_bigint(BigInt::from(value))?),
+            NadaValue::SecretBoolean(value) => NadaValue::new_secret_boolean(boolean_from_bigint(BigInt::from(value))?),
+            NadaValue::Array { inner_type, values } => NadaValue::new_array(
+                inner_type.clone(),
+                values.iter().map(NadaValue::to_clear).collect::<Result<_, _>>()?,
+            )?,
+            NadaValue::Tuple { left, right } => NadaValue::new_tuple(left.to_clear()?, right.to_clear()?)?,
+            NadaValue::NTuple { values } => {
+                NadaValue::new_n_tuple(values.iter().map(NadaValue::to_clear).collect::<Result<_, _>>()?)?
+            }
+            NadaValue::Object { values } => NadaValue::new_object(
+                values.iter().map(|(key, value)| Ok((key.clone(), value.to_clear()?))).collect::<Result<_, _>>()?,
+            )?,
+            NadaValue::SecretBlob(_)
+            | NadaValue::ShamirShareInteger(_)
+            | NadaValue::ShamirShareUnsignedInteger(_)
+            | NadaValue::ShamirShareBoolean(_)
+            | NadaValue::EcdsaPrivateKey(_)
+            | NadaValue::EcdsaDigestMessage(_)
+            | NadaValue::EcdsaSignature(_)
+            | NadaValue::EcdsaPublicKey(_)
+            | NadaValue::StoreId(_)
+            | NadaValue::EddsaPrivateKey(_)
+            | NadaValue::EddsaPublicKey(_)
+            | NadaValue::EddsaSignature(_)
+            | NadaValue::EddsaMessage(_) => unreachable!(),
+        };
+        Ok(value)
+    }
 }
 
 impl<T: Modular> TryFrom<NadaValue<ClearModular<T>>> for ModularNumber<T> {
@@ -169,37 +211,55 @@ impl<T: Modular> TryFrom<NadaValue<ClearModular<T>>> for ModularNumber<T> {
     }
 }
 
-impl<T: Modular> TryFrom<NadaValue<Clear>> for NadaValue<ClearModular<T>> {
-    type Error = ClearModularError;
-
-    fn try_from(value: NadaValue<Clear>) -> Result<Self, Self::Error> {
-        let ty = value.to_type();
-        let mut inner_values = vec![value];
+impl NadaValue<Clear> {
+    /// Converts this value into its modular form.
+    ///
+    /// If a primitive value doesn't fit in the modular form, the error contains the path to it starting at `name`,
+    /// e.g. `my_array[2]` or `my_object.field.0`.
+    pub fn try_into_modular_named<T: Modular>(
+        self,
+        name: &str,
+    ) -> Result<NadaValue<ClearModular<T>>, ClearModularError> {
+        let ty = self.to_type();
+        let mut inner_values = vec![(self, name.to_string())];
         let mut modular_values = vec![];
-        while let Some(value) = inner_values.pop() {
+        while let Some((value, path)) = inner_values.pop() {
             match value {
                 NadaValue::Integer(value) | NadaValue::SecretInteger(value) => {
-                    modular_values.push(ModularNumber::try_from(&value)?);
+                    modular_values.push(
+                        ModularNumber::try_from(&value)
+                            .map_err(|source| ClearModularError::OutOfRange { path, source })?,
+                    );
                 }
                 NadaValue::UnsignedInteger(value) | NadaValue::SecretUnsignedInteger(value) => {
-                    modular_values.push(ModularNumber::try_from(&value)?);
+                    modular_values.push(
+                        ModularNumber::try_from(&value)
+                            .map_err(|source| ClearModularError::OutOfRange { path, source })?,
+                    );
                 }
                 NadaValue::Array { values, .. } => {
-                    inner_values.extend(values.into_iter().rev());
+                    inner_values.extend(
+                        values.into_iter().enumerate().rev().map(|(index, value)| (value, format!("{path}[{index}]"))),
+                    );
                 }
                 NadaValue::Tuple { left, right } => {
-                    inner_values.push(*right);
-                    inner_values.push(*left);
+                    inner_values.push((*right, format!("{path}.1")));
+                    inner_values.push((*left, format!("{path}.0")));
                 }
                 NadaValue::NTuple { values } => {
-                    inner_values.extend(values.into_iter().rev());
+                    inner_values.extend(
+                        values.into_iter().enumerate().rev().map(|(index, value)| (value, format!("{path}.{index}"))),
+                    );
                 }
                 NadaValue::Object { values } => {
-                    inner_values.extend(values.into_values().rev());
+                    inner_values.extend(values.into_iter().rev().map(|(key, value)| (value, format!("{path}.{key}"))));
                 }
                 NadaValue::Boolean(value) | NadaValue::SecretBoolean(value) => {
                     let value = BigUint::from(value as u32);
-                    modular_values.push(ModularNumber::try_from(&value)?);
+                    modular_values.push(
+                        ModularNumber::try_from(&value)
+                            .map_err(|source| ClearModularError::OutOfRange { path, source })?,
+                    );
                 }
                 NadaValue::SecretBlob(_)
                 | NadaValue::ShamirShareInteger(_)
@@ -220,6 +280,14 @@ impl<T: Modular> TryFrom<NadaValue<Clear>> for NadaValue<ClearModular<T>> {
     }
 }
 
+impl<T: Modular> TryFrom<NadaValue<Clear>> for NadaValue<ClearModular<T>> {
+    type Error = ClearModularError;
+
+    fn try_from(value: NadaValue<Clear>) -> Result<Self, Self::Error> {
+        value.try_into_modular_named("value")
+    }
+}
+
 impl<T: Modular> Mul<NadaValue<ClearModular<T>>> for NadaValue<ClearModular<T>> {
     type Output = Result<NadaValue<ClearModular<T>>, ClearModularError>;
 
@@ -252,7 +320,7 @@ impl<T: Modular> Mul<NadaValue<ClearModular<T>>> for NadaValue<ClearModular<T>>
 }
 #[cfg(test)]
 mod tests {
-    use crate::{clear_modular::ClearModular, NadaValue};
+    use crate::{clear::Clear, clear_modular::ClearModular, errors::ClearModularError, NadaValue};
     use anyhow::Error;
     use math_lib::modular::{ModularNumber, U64SafePrime};
     use nada_type::NadaType;
@@ -360,4 +428,47 @@ mod tests {
             (into_unsigned_integer_nada_value(left) * into_unsigned_integer_nada_value(right)).unwrap()
         );
     }
+
+    #[test]
+    fn to_clear_negative_integer() -> Result<(), Error> {
+        let clear = NadaValue::<Clear>::new_tuple(
+            NadaValue::new_secret_integer(-42),
+            NadaValue::new_array(NadaType::Integer, vec![NadaValue::new_integer(-1), NadaValue::new_integer(7)])?,
+        )?;
+        let modular: NadaValue<ClearModular<Prime>> = clear.clone().try_into()?;
+        assert_eq!(modular.to_clear()?, clear);
+        Ok(())
+    }
+
+    #[test]
+    fn to_clear_boolean() -> Result<(), Error> {
+        let modular: NadaValue<ClearModular<Prime>> = NadaValue::new_secret_boolean(ModularNumber::ONE);
+        assert_eq!(modular.to_clear()?, NadaValue::new_secret_boolean(true));
+
+        let modular: NadaValue<ClearModular<Prime>> = NadaValue::new_boolean(ModularNumber::from_u32(2));
+        assert!(matches!(modular.to_clear(), Err(ClearModularError::Overflow(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn try_into_modular_out_of_range_path() {
+        let values = vec![
+            NadaValue::<Clear>::new_secret_integer(1),
+            NadaValue::new_secret_integer(2),
+            NadaValue::new_secret_integer(BigInt::from(u64::MAX)),
+        ];
+        let array = NadaValue::new_array_non_empty(values).unwrap();
+        let tuple = NadaValue::new_tuple(NadaValue::new_integer(1), array.clone()).unwrap();
+
+        let err = array.try_into_modular_named::<Prime>("my_array").unwrap_err();
+        assert!(
+            matches!(&err, ClearModularError::OutOfRange { path, .. } if path == "my_array[2]"),
+            "unexpected error: {err}"
+        );
+        let err = tuple.try_into_modular_named::<Prime>("my_tuple").unwrap_err();
+        assert!(
+            matches!(&err, ClearModularError::OutOfRange { path, .. } if path == "my_tuple.1[2]"),
+            "unexpected error: {err}"
+        );
+    }
 }
@@ -171,6 +171,16 @@ pub enum ClearModularError {
     /// Unsupported type error
     #[error("unsupported type {0}")]
     Unsupported(String),
+
+    /// A primitive value doesn't fit in the modular form.
+    #[error("value at {path} is out of range")]
+    OutOfRange {
+        /// The path to the primitive value, e.g. `my_array[2]`.
+        path: String,
+        /// The underlying error.
+        #[source]
+        source: Overflow,
+    },
 }
 
 /// ModularValue is not a primitive value.
@@ -2,11 +2,15 @@
 
 use crate::{clear::Clear, NadaInt, NadaUint, NadaValue};
 use anyhow::{anyhow, Context, Result};
+use generic_ec::curves::Ed25519;
 use nada_type::NadaType;
 use num_bigint::{BigInt, BigUint};
 use num_traits::ToPrimitive;
-use serde_json::{Number, Value as JsonValue};
+use serde_json::{json, Number, Value as JsonValue};
 use std::collections::HashMap;
+use threshold_keypair::{
+    privatekey::ThresholdPrivateKey, signature::EddsaSignature, EDDSA_PUBLIC_KEY_LENGTH, PRIVATE_KEY_LENGTH,
+};
 
 /// Creates a map of Nada values from an untyped JSON object.
 /// it uses the provided types to parse the JSON values.
@@ -125,25 +129,24 @@ impl NadaValue<Clear> {
                     };
                     Some(NadaValue::new_secret_boolean(b))
                 }
-                NadaType::SecretBlob => {
-                    let JsonValue::Array(values) = value else {
-                        return Err(anyhow!("Invalid json value for secret blob, expected array"));
-                    };
-                    let blob = values
-                        .into_iter()
-                        .map(|v| match v {
-                            JsonValue::Number(n) => {
-                                let n = n
-                                    .as_u64()
-                                    .ok_or_else(|| anyhow!("Invalid json value for secret blob, expect number"))?;
-                                Ok(u8::try_from(n)
-                                    .context("Invalid json value for secret blob, expect number 0-255")?)
-                            }
-                            _ => Err(anyhow!("Invalid json value for secret blob")),
-                        })
-                        .collect::<Result<Vec<_>>>()?;
-                    Some(NadaValue::new_secret_blob(blob))
+                NadaType::SecretBlob => Some(NadaValue::new_secret_blob(bytes_from_json(value, "secret blob")?)),
+                NadaType::EddsaPrivateKey => {
+                    let bytes = bytes_from_json(value, "eddsa private key")?;
+                    let key = ThresholdPrivateKey::<Ed25519>::from_le_bytes(&bytes)
+                        .context("Invalid json value for eddsa private key")?;
+                    Some(NadaValue::new_eddsa_private_key(key))
+                }
+                NadaType::EddsaPublicKey => {
+                    let key = <[u8; 32]>::try_from(bytes_from_json(value, "eddsa public key")?)
+                        .map_err(|_| anyhow!("Invalid json value for eddsa public key, expected 32 bytes"))?;
+                    Some(NadaValue::new_eddsa_public_key(key))
+                }
+                NadaType::EddsaSignature => {
+                    let signature = EddsaSignature::from_bytes(&bytes_from_json(value, "eddsa signature")?)
+                        .context("Invalid json value for eddsa signature")?;
+                    Some(NadaValue::new_eddsa_signature(signature))
                 }
+                NadaType::EddsaMessage => Some(NadaValue::new_eddsa_message(bytes_from_json(value, "eddsa message")?)),
                 NadaType::ShamirShareInteger
                 | NadaType::ShamirShareUnsignedInteger
                 | NadaType::ShamirShareBoolean
@@ -151,11 +154,7 @@ impl NadaValue<Clear> {
                 | NadaType::EcdsaDigestMessage
                 | NadaType::EcdsaSignature
                 | NadaType::EcdsaPublicKey
-                | NadaType::StoreId
-                | NadaType::EddsaPrivateKey
-                | NadaType::EddsaPublicKey
-                | NadaType::EddsaSignature
-                | NadaType::EddsaMessage => return Err(anyhow!("Unsupported type: {:?}", nada_type)),
+                | NadaType::StoreId => return Err(anyhow!("Unsupported type: {:?}", nada_type)),
                 NadaType::Array { inner_type, size } => {
                     let JsonValue::Array(inner_values) = value else {
                         return Err(anyhow!("Invalid json value for {nada_type:?}, expected array",));
@@ -173,14 +172,14 @@ impl NadaValue<Clear> {
                     let JsonValue::Array(mut inner_values) = value else {
                         return Err(anyhow!("Invalid json value for {nada_type:?}, expected array of two elements"));
                     };
-                    let left = inner_values.pop().ok_or_else(|| {
+                    let right = inner_values.pop().ok_or_else(|| {
                         anyhow!("Invalid json value for {nada_type:?}, expected array of two elements")
                     })?;
-                    let right = inner_values.pop().ok_or_else(|| {
+                    let left = inner_values.pop().ok_or_else(|| {
                         anyhow!("Invalid json value for {nada_type:?}, expected array of two elements")
                     })?;
-                    values.push((left_type.as_ref(), left));
                     values.push((right_type.as_ref(), right));
+                    values.push((left_type.as_ref(), left));
                     None
                 }
                 NadaType::NTuple { types } => {
@@ -197,7 +196,7 @@ impl NadaValue<Clear> {
                     None
                 }
                 NadaType::Object { types } => {
-                    let JsonValue::Object(inner_values) = value else {
+                    let JsonValue::Object(mut inner_values) = value else {
                         return Err(anyhow!("Invalid json value for {nada_type:?}, expected object"));
                     };
                     for key in inner_values.keys() {
@@ -220,8 +219,11 @@ impl NadaValue<Clear> {
                     if json_len != types.len() {
                         return Err(anyhow!("Invalid size for {nada_type:?}, expected {} got {json_len}", types.len()));
                     }
-                    for (v, inner_type) in inner_values.values().zip(types.values()).rev() {
-                        values.push((inner_type, v.clone()));
+                    for (key, inner_type) in types.iter().rev() {
+                        let value = inner_values.remove(key).ok_or_else(|| {
+                            anyhow!("Expected key {key} in json object, expected keys: {:?}", types.keys())
+                        })?;
+                        values.push((inner_type, value));
                     }
                     None
                 }
@@ -237,22 +239,22 @@ impl NadaValue<Clear> {
                 | NadaType::Boolean
                 | NadaType::SecretInteger
                 | NadaType::SecretUnsignedInteger
-                | NadaType::SecretBoolean => {
+                | NadaType::SecretBoolean
+                | NadaType::SecretBlob
+                | NadaType::EddsaPrivateKey
+                | NadaType::EddsaPublicKey
+                | NadaType::EddsaSignature
+                | NadaType::EddsaMessage => {
                     values.push(value.ok_or_else(|| anyhow!("This should not happen it is a bug"))?);
                 }
-                NadaType::SecretBlob
-                | NadaType::ShamirShareInteger
+                NadaType::ShamirShareInteger
                 | NadaType::ShamirShareUnsignedInteger
                 | NadaType::ShamirShareBoolean
                 | NadaType::EcdsaPrivateKey
                 | NadaType::EcdsaDigestMessage
                 | NadaType::EcdsaSignature
                 | NadaType::EcdsaPublicKey
-                | NadaType::StoreId
-                | NadaType::EddsaPrivateKey
-                | NadaType::EddsaPublicKey
-                | NadaType::EddsaSignature
-                | NadaType::EddsaMessage => return Err(anyhow!("Unsupported type: {:?}", nada_type)),
+                | NadaType::StoreId => return Err(anyhow!("Unsupported type: {:?}", nada_type)),
                 NadaType::Array { inner_type, size } => {
                     let mut array_values = vec![];
                     for _ in 0..*size {
@@ -310,9 +312,10 @@ impl NadaValue<Clear> {
                 }
             }
             NadaValue::Boolean(boolean) | NadaValue::SecretBoolean(boolean) => JsonValue::Bool(*boolean),
-            NadaValue::SecretBlob(blob) => {
-                JsonValue::Array(blob.iter().map(|b| JsonValue::Number(Number::from(*b))).collect())
-            }
+            NadaValue::SecretBlob(bytes) | NadaValue::EddsaMessage(bytes) => bytes_to_json(bytes),
+            NadaValue::EddsaPrivateKey(key) => bytes_to_json(&key.clone().to_le_bytes()),
+            NadaValue::EddsaPublicKey(key) => bytes_to_json(key),
+            NadaValue::EddsaSignature(signature) => bytes_to_json(&signature.to_bytes()),
             NadaValue::ShamirShareInteger(_)
             | NadaValue::ShamirShareUnsignedInteger(_)
             | NadaValue::ShamirShareBoolean(_)
@@ -320,11 +323,7 @@ impl NadaValue<Clear> {
             | NadaValue::EcdsaDigestMessage(_)
             | NadaValue::EcdsaSignature(_)
             | NadaValue::EcdsaPublicKey(_)
-            | NadaValue::StoreId(_)
-            | NadaValue::EddsaPrivateKey(_)
-            | NadaValue::EddsaPublicKey(_)
-            | NadaValue::EddsaSignature(_)
-            | NadaValue::EddsaMessage(_) => return Err(anyhow!("Unsupported type: {:?}", self)),
+            | NadaValue::StoreId(_) => return Err(anyhow!("Unsupported type: {:?}", self)),
             NadaValue::Array { values, .. } => {
                 JsonValue::Array(values.iter().map(|v| v.to_json_value()).collect::<Result<_, _>>()?)
             }
@@ -344,16 +343,126 @@ impl NadaValue<Clear> {
     }
 }
 
+/// Parses an array of bytes out of a JSON array of numbers.
+fn bytes_from_json(value: JsonValue, name: &str) -> Result<Vec<u8>> {
+    let JsonValue::Array(values) = value else {
+        return Err(anyhow!("Invalid json value for {name}, expected array"));
+    };
+    values
+        .into_iter()
+        .map(|v| match v {
+            JsonValue::Number(n) => {
+                let n = n.as_u64().ok_or_else(|| anyhow!("Invalid json value for {name}, expect number"))?;
+                Ok(u8::try_from(n).with_context(|| format!("Invalid json value for {name}, expect number 0-255"))?)
+            }
+            _ => Err(anyhow!("Invalid json value for {name}")),
+        })
+        .collect()
+}
+
+/// Transforms an array of bytes into a JSON array of numbers.
+fn bytes_to_json(bytes: &[u8]) -> JsonValue {
+    JsonValue::Array(bytes.iter().map(|b| JsonValue::Number(Number::from(*b))).collect())
+}
+
+/// Describes an array of bytes, optionally with a fixed length, representing a value of the given type.
+fn bytes_json_schema(nada_type: &NadaType, length: Option<usize>) -> JsonValue {
+    let mut schema = json!({
+        "type": "array",
+        "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+        "nadaType": nada_type.to_string(),
+    });
+    if let (Some(length), JsonValue::Object(schema)) = (length, &mut schema) {
+        schema.insert("minItems".to_string(), json!(length));
+        schema.insert("maxItems".to_string(), json!(length));
+    }
+    schema
+}
+
+/// Allows describing a Nada type as a JSON schema.
+pub trait NadaTypeJsonSchema {
+    /// Builds a JSON-Schema-like description of the JSON representation of values of this type.
+    ///
+    /// Compound types are described using the standard `object` and `array` schemas, with tuples being fixed-length
+    /// arrays. Leaf types carry a `nadaType` annotation with the name of the Nada type they represent.
+    fn to_json_schema(&self) -> JsonValue;
+}
+
+impl NadaTypeJsonSchema for NadaType {
+    fn to_json_schema(&self) -> JsonValue {
+        match self {
+            // Integers that don't fit in an i64 are represented as strings.
+            NadaType::Integer
+            | NadaType::UnsignedInteger
+            | NadaType::SecretInteger
+            | NadaType::SecretUnsignedInteger => json!({ "type": ["integer", "string"], "nadaType": self.to_string() }),
+            NadaType::Boolean | NadaType::SecretBoolean => json!({ "type": "boolean", "nadaType": self.to_string() }),
+            NadaType::SecretBlob | NadaType::EddsaSignature | NadaType::EddsaMessage => bytes_json_schema(self, None),
+            NadaType::EddsaPrivateKey => bytes_json_schema(self, Some(PRIVATE_KEY_LENGTH)),
+            NadaType::EddsaPublicKey => bytes_json_schema(self, Some(EDDSA_PUBLIC_KEY_LENGTH)),
+            NadaType::ShamirShareInteger
+            | NadaType::ShamirShareUnsignedInteger
+            | NadaType::ShamirShareBoolean
+            | NadaType::EcdsaPrivateKey
+            | NadaType::EcdsaDigestMessage
+            | NadaType::EcdsaSignature
+            | NadaType::EcdsaPublicKey
+            | NadaType::StoreId => json!({ "nadaType": self.to_string() }),
+            NadaType::Array { inner_type, size } => json!({
+                "type": "array",
+                "items": inner_type.to_json_schema(),
+                "minItems": size,
+                "maxItems": size,
+            }),
+            NadaType::Tuple { left_type, right_type } => json!({
+                "type": "array",
+                "prefixItems": [left_type.to_json_schema(), right_type.to_json_schema()],
+                "minItems": 2,
+                "maxItems": 2,
+            }),
+            NadaType::NTuple { types } => json!({
+                "type": "array",
+                "prefixItems": types.iter().map(|ty| ty.to_json_schema()).collect::<Vec<_>>(),
+                "minItems": types.len(),
+                "maxItems": types.len(),
+            }),
+            NadaType::Object { types } => {
+                let properties: serde_json::Map<_, _> =
+                    types.iter().map(|(name, ty)| (name.clone(), ty.to_json_schema())).collect();
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": types.keys().collect::<Vec<_>>(),
+                    "additionalProperties": false,
+                })
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        json::{nada_values_from_untyped_json, nada_values_to_json},
+        clear::Clear,
+        json::{nada_values_from_untyped_json, nada_values_to_json, NadaTypeJsonSchema},
         NadaValue,
     };
     use anyhow::Result;
+    use generic_ec::curves::Ed25519;
     use indexmap::IndexMap;
     use nada_type::NadaType;
     use std::collections::HashMap;
+    use threshold_keypair::privatekey::ThresholdPrivateKey;
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn json_round_trip(value in proptest::prelude::any::<NadaValue<crate::clear::Clear>>()) {
+            let json = value.to_json_value().unwrap();
+            let decoded = NadaValue::from_untyped_json(&value.to_type(), json).unwrap();
+            proptest::prop_assert_eq!(decoded, value);
+        }
+    }
 
     #[test]
     fn test_from_json_untyped() -> Result<()> {
@@ -446,6 +555,40 @@ mod tests {
         assert_eq!(expected_result, result);
         Ok(())
     }
+    #[test]
+    fn test_from_json_untyped_tuple_element_order() -> Result<()> {
+        let ty = NadaType::Tuple { left_type: Box::new(NadaType::Integer), right_type: Box::new(NadaType::Boolean) };
+        let value = NadaValue::from_untyped_json(&ty, serde_json::json!([5, true]))?;
+        assert_eq!(value, NadaValue::new_tuple(NadaValue::new_integer(5), NadaValue::new_boolean(true))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_json_untyped_object_matches_keys() -> Result<()> {
+        // The type's fields are in a different order than the JSON object's.
+        let ty = NadaType::Object {
+            types: IndexMap::from([("b".to_string(), NadaType::Boolean), ("a".to_string(), NadaType::Integer)]).into(),
+        };
+        let value = NadaValue::from_untyped_json(&ty, serde_json::json!({"a": 42, "b": true}))?;
+        let expected = NadaValue::new_object(IndexMap::from([
+            ("b".to_string(), NadaValue::new_boolean(true)),
+            ("a".to_string(), NadaValue::new_integer(42)),
+        ]))?;
+        assert_eq!(value, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_json_untyped_secret_blob() -> Result<()> {
+        let value = NadaValue::from_untyped_json(&NadaType::SecretBlob, serde_json::json!([1, 2, 3]))?;
+        assert_eq!(value, NadaValue::new_secret_blob(vec![1, 2, 3]));
+
+        let ty = NadaType::Tuple { left_type: Box::new(NadaType::SecretBlob), right_type: Box::new(NadaType::Integer) };
+        let value = NadaValue::from_untyped_json(&ty, serde_json::json!([[4, 5], 6]))?;
+        assert_eq!(value, NadaValue::new_tuple(NadaValue::new_secret_blob(vec![4, 5]), NadaValue::new_integer(6))?);
+        Ok(())
+    }
+
     #[test]
     fn test_to_json() -> Result<()> {
         let json = serde_json::json!({
@@ -495,4 +638,105 @@ mod tests {
         assert_eq!(result, json);
         Ok(())
     }
+
+    #[test]
+    fn test_to_json_schema() {
+        let nada_type = NadaType::Object {
+            types: IndexMap::from([
+                (
+                    "matrix".to_string(),
+                    NadaType::Array {
+                        inner_type: Box::new(NadaType::Array {
+                            inner_type: Box::new(NadaType::SecretInteger),
+                            size: 2,
+                        }),
+                        size: 3,
+                    },
+                ),
+                (
+                    "pair".to_string(),
+                    NadaType::Tuple {
+                        left_type: Box::new(NadaType::Boolean),
+                        right_type: Box::new(NadaType::UnsignedInteger),
+                    },
+                ),
+            ])
+            .into(),
+        };
+        let expected = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "matrix": {
+                    "type": "array",
+                    "items": {
+                        "type": "array",
+                        "items": { "type": ["integer", "string"], "nadaType": "SecretInteger" },
+                        "minItems": 2,
+                        "maxItems": 2,
+                    },
+                    "minItems": 3,
+                    "maxItems": 3,
+                },
+                "pair": {
+                    "type": "array",
+                    "prefixItems": [
+                        { "type": "boolean", "nadaType": "Boolean" },
+                        { "type": ["integer", "string"], "nadaType": "UnsignedInteger" },
+                    ],
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+            },
+            "required": ["matrix", "pair"],
+            "additionalProperties": false,
+        });
+        assert_eq!(nada_type.to_json_schema(), expected);
+    }
+
+    #[test]
+    fn test_eddsa_json_schema() {
+        let bytes = serde_json::json!({ "type": "integer", "minimum": 0, "maximum": 255 });
+        assert_eq!(
+            NadaType::EddsaPrivateKey.to_json_schema(),
+            serde_json::json!({
+                "type": "array", "items": bytes, "minItems": 32, "maxItems": 32, "nadaType": "EddsaPrivateKey"
+            })
+        );
+        assert_eq!(
+            NadaType::EddsaPublicKey.to_json_schema(),
+            serde_json::json!({
+                "type": "array", "items": bytes, "minItems": 32, "maxItems": 32, "nadaType": "EddsaPublicKey"
+            })
+        );
+        assert_eq!(
+            NadaType::EddsaSignature.to_json_schema(),
+            serde_json::json!({ "type": "array", "items": bytes, "nadaType": "EddsaSignature" })
+        );
+        assert_eq!(
+            NadaType::EddsaMessage.to_json_schema(),
+            serde_json::json!({ "type": "array", "items": bytes, "nadaType": "EddsaMessage" })
+        );
+    }
+
+    fn assert_json_round_trip(value: NadaValue<Clear>) {
+        let json = value.to_json_value().unwrap();
+        let decoded = NadaValue::from_untyped_json(&value.to_type(), json).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn test_eddsa_json_round_trip() {
+        let private_key = ThresholdPrivateKey::<Ed25519>::from_le_bytes(&[1; 32]).unwrap();
+        let signature = private_key.sign(b"hello").unwrap();
+        assert_json_round_trip(NadaValue::new_eddsa_private_key(private_key));
+        assert_json_round_trip(NadaValue::new_eddsa_public_key([2; 32]));
+        assert_json_round_trip(NadaValue::new_eddsa_signature(signature));
+        assert_json_round_trip(NadaValue::new_eddsa_message(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn test_eddsa_public_key_invalid_length() {
+        let json = serde_json::json!([1, 2, 3]);
+        assert!(NadaValue::from_untyped_json(&NadaType::EddsaPublicKey, json).is_err());
+    }
 }
@@ -15,6 +15,8 @@
 )]
 extern crate core;
 
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
 pub mod classify;
 pub mod clear;
 pub mod clear_modular;
@@ -149,37 +149,13 @@ pub(crate) fn nada_value_from_protobuf(
                 nada_type_from_protobuf(&inner_type)?,
                 array.values.into_iter().map(|v| nada_value_from_protobuf(v, modulo)).collect::<Result<_, _>>()?,
             );
-            result.map_err(|e| {
-                match e {
-                    TypeError::HomogeneousVecOnly => {
-                        ValueDecodeError::InvalidArray("arrays must only contain one type")
-                    }
-                    TypeError::MaxRecursionDepthExceeded => {
-                        ValueDecodeError::InvalidArray("array nested depth is too large")
-                    }
-                    // These should not happen here so we fall back to some generic error.
-                    TypeError::NonEmptyVecOnly | TypeError::ZeroValue | TypeError::Unimplemented(_) => {
-                        ValueDecodeError::InvalidArray("unknown error")
-                    }
-                }
-            })?
+            result.map_err(array_error)?
         }
         Value::Tuple(tuple) => {
             let left = tuple.left.ok_or(ValueDecodeError::NoValue)?;
             let right = tuple.right.ok_or(ValueDecodeError::NoValue)?;
             NadaValue::new_tuple(nada_value_from_protobuf(*left, modulo)?, nada_value_from_protobuf(*right, modulo)?)
-                .map_err(|e| {
-                    match e {
-                        TypeError::MaxRecursionDepthExceeded => {
-                            ValueDecodeError::InvalidTuple("tuple nested depth is too large")
-                        }
-                        // These should not happen here so we fall back to some generic error.
-                        TypeError::HomogeneousVecOnly
-                        | TypeError::NonEmptyVecOnly
-                        | TypeError::ZeroValue
-                        | TypeError::Unimplemented(_) => ValueDecodeError::InvalidTuple("unknown error"),
-                    }
-                })?
+                .map_err(tuple_error)?
         }
         Value::ShamirSharesBlob(shares) => NadaValue::new_secret_blob(BlobPrimitiveType {
             value: shares.shares.into_iter().map(|s| EncodedModularNumber::new_unchecked(s.value, *modulo)).collect(),
@@ -306,18 +282,14 @@ fn nada_type_from_protobuf(value_type: &value::ValueType) -> Result<NadaType, Va
         value::value_type::ValueType::ShamirShareBoolean(()) => NadaType::ShamirShareBoolean,
         value::value_type::ValueType::Array(array) => {
             let inner_type = array.inner_type.as_ref().ok_or(ValueDecodeError::NoType)?;
-            NadaType::Array {
-                inner_type: Box::new(nada_type_from_protobuf(inner_type)?),
-                size: array.size.try_into().map_err(|_| ValueDecodeError::ArraySizeOverflow(array.size))?,
-            }
+            let size = array.size.try_into().map_err(|_| ValueDecodeError::ArraySizeOverflow(array.size))?;
+            // Go through the checked constructors so a crafted type can't exceed the maximum depth or arity.
+            NadaType::new_array(nada_type_from_protobuf(inner_type)?, size).map_err(array_error)?
         }
         value::value_type::ValueType::Tuple(tuple) => {
             let left = tuple.left.as_ref().ok_or(ValueDecodeError::NoType)?;
             let right = tuple.right.as_ref().ok_or(ValueDecodeError::NoType)?;
-            NadaType::Tuple {
-                left_type: Box::new(nada_type_from_protobuf(left)?),
-                right_type: Box::new(nada_type_from_protobuf(right)?),
-            }
+            NadaType::new_tuple(nada_type_from_protobuf(left)?, nada_type_from_protobuf(right)?).map_err(tuple_error)?
         }
         value::value_type::ValueType::EcdsaPrivateKeyShare(()) => NadaType::EcdsaPrivateKey,
         value::value_type::ValueType::EcdsaMessageDigest(()) => NadaType::EcdsaDigestMessage,
@@ -332,6 +304,43 @@ fn nada_type_from_protobuf(value_type: &value::ValueType) -> Result<NadaType, Va
     Ok(nada_type)
 }
 
+fn array_error(e: TypeError) -> ValueDecodeError {
+    match e {
+        TypeError::HomogeneousVecOnly | TypeError::HeterogeneousArray { .. } => {
+            ValueDecodeError::InvalidArray("arrays must only contain one type")
+        }
+        TypeError::MaxRecursionDepthExceeded => ValueDecodeError::InvalidArray("array nested depth is too large"),
+        TypeError::MaxArityExceeded => ValueDecodeError::InvalidArray("array is too large"),
+        // These should not happen here so we fall back to some generic error.
+        TypeError::NonEmptyVecOnly
+        | TypeError::ObjectKeyTooLong { .. }
+        | TypeError::ZeroValue
+        | TypeError::Unimplemented(_)
+        | TypeError::NoZeroValue(_)
+        | TypeError::ValueCountMismatch { .. }
+        | TypeError::TooManyValues { .. }
+        | TypeError::UnexpectedValueType { .. } => ValueDecodeError::InvalidArray("unknown error"),
+    }
+}
+
+fn tuple_error(e: TypeError) -> ValueDecodeError {
+    match e {
+        TypeError::MaxRecursionDepthExceeded => ValueDecodeError::InvalidTuple("tuple nested depth is too large"),
+        // These should not happen here so we fall back to some generic error.
+        TypeError::HomogeneousVecOnly
+        | TypeError::HeterogeneousArray { .. }
+        | TypeError::MaxArityExceeded
+        | TypeError::NonEmptyVecOnly
+        | TypeError::ObjectKeyTooLong { .. }
+        | TypeError::ZeroValue
+        | TypeError::Unimplemented(_)
+        | TypeError::NoZeroValue(_)
+        | TypeError::ValueCountMismatch { .. }
+        | TypeError::TooManyValues { .. }
+        | TypeError::UnexpectedValueType { .. } => ValueDecodeError::InvalidTuple("unknown error"),
+    }
+}
+
 fn non_zero_point_from_bytes<E: Curve>(bytes: &[u8]) -> Result<NonZero<Point<E>>, ValueDecodeError> {
     let point =
         Point::from_bytes(bytes).map_err(|_| ValueDecodeError::InvalidThresholdPrivateKeyPoint("invalid bytes"))?;
@@ -429,25 +438,13 @@ pub enum ValueDecodeError {
     DuplicateValue(String),
 }
 
-impl From<TypeError> for ValueDecodeError {
-    fn from(e: TypeError) -> Self {
-        match e {
-            TypeError::HomogeneousVecOnly => Self::InvalidArray("arrays must only contain one type"),
-            TypeError::MaxRecursionDepthExceeded => Self::InvalidArray("array nested depth is too large"),
-            // These should not happen here so we fall back to some generic error.
-            TypeError::NonEmptyVecOnly | TypeError::ZeroValue | TypeError::Unimplemented(_) => {
-                Self::InvalidArray("unknown error")
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{clear::Clear, encoders::EncodableWithP, encrypted::nada_values_clear_to_nada_values_encrypted};
     use basic_types::PartyId;
     use math_lib::modular::U64SafePrime;
+    use nada_type::MAX_RECURSION_DEPTH;
     use rand::thread_rng;
     use shamir_sharing::secret_sharer::ShamirSecretSharer;
     use threshold_keypair::{privatekey::ThresholdPrivateKey, signature::EcdsaSignature};
@@ -512,4 +509,36 @@ mod tests {
             assert_eq!(decoded, &value, "{name} differs");
         }
     }
+
+    #[test]
+    fn decode_too_deep_type() {
+        use value::value_type::ValueType;
+
+        let nest = |depth: usize, wrap: fn(value::ValueType) -> ValueType| {
+            let mut value_type = value::ValueType { value_type: Some(ValueType::PublicInteger(())) };
+            for _ in 0..depth {
+                value_type = value::ValueType { value_type: Some(wrap(value_type)) };
+            }
+            value_type
+        };
+        let array = |inner: value::ValueType| {
+            ValueType::Array(Box::new(value::ArrayType { inner_type: Some(Box::new(inner)), size: 1 }))
+        };
+        let tuple = |inner: value::ValueType| {
+            ValueType::Tuple(Box::new(value::TupleType {
+                left: Some(Box::new(inner)),
+                right: Some(Box::new(value::ValueType { value_type: Some(ValueType::PublicBoolean(())) })),
+            }))
+        };
+
+        assert!(nada_type_from_protobuf(&nest(MAX_RECURSION_DEPTH - 1, array)).is_ok());
+        assert!(matches!(
+            nada_type_from_protobuf(&nest(MAX_RECURSION_DEPTH, array)),
+            Err(ValueDecodeError::InvalidArray("array nested depth is too large"))
+        ));
+        assert!(matches!(
+            nada_type_from_protobuf(&nest(MAX_RECURSION_DEPTH, tuple)),
+            Err(ValueDecodeError::InvalidTuple("tuple nested depth is too large"))
+        ));
+    }
 }
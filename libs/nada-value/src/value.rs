@@ -220,6 +220,68 @@ impl<T: PrimitiveTypes> NadaValue<T> {
         Ok(value)
     }
 
+    /// Builds a value of the given type out of its primitive values.
+    ///
+    /// The values must be provided in the same order the primitive elements appear in the type, left to right. Both
+    /// the number of values and the type of each of them are checked against the type before building the value.
+    pub fn try_from_flat(values: Vec<Self>, ty: &NadaType) -> Result<Self, TypeError> {
+        let expected = ty.primitive_elements_count();
+        if values.len() != expected {
+            return Err(TypeError::ValueCountMismatch { expected, actual: values.len() });
+        }
+        let mut values = FlatValues { values: values.into_iter(), expected, consumed: 0 };
+        Self::build_from_flat(&mut values, ty)
+    }
+
+    /// Builds a value of the given type consuming its primitive values from an iterator.
+    ///
+    /// This behaves like [NadaValue::try_from_flat] but doesn't require the values to be collected beforehand: each
+    /// value is checked against the type expected at its position as soon as it's consumed, so building stops at the
+    /// first mismatching value.
+    pub fn build_for_type(ty: &NadaType, leaves: impl Iterator<Item = Self>) -> Result<Self, TypeError> {
+        let expected = ty.primitive_elements_count();
+        let mut leaves = FlatValues { values: leaves, expected, consumed: 0 };
+        let value = Self::build_from_flat(&mut leaves, ty)?;
+        if leaves.values.next().is_some() {
+            return Err(TypeError::TooManyValues { expected });
+        }
+        Ok(value)
+    }
+
+    fn build_from_flat(values: &mut FlatValues<impl Iterator<Item = Self>>, ty: &NadaType) -> Result<Self, TypeError> {
+        match ty {
+            NadaType::Array { inner_type, size } => {
+                let values =
+                    (0..*size).map(|_| Self::build_from_flat(values, inner_type)).collect::<Result<Vec<_>, _>>()?;
+                Self::new_array(inner_type.as_ref().clone(), values)
+            }
+            NadaType::Tuple { left_type, right_type } => {
+                let left = Self::build_from_flat(values, left_type)?;
+                let right = Self::build_from_flat(values, right_type)?;
+                Self::new_tuple(left, right)
+            }
+            NadaType::NTuple { types } => {
+                let values = types.iter().map(|ty| Self::build_from_flat(values, ty)).collect::<Result<Vec<_>, _>>()?;
+                Self::new_n_tuple(values)
+            }
+            NadaType::Object { types } => {
+                let values = types
+                    .iter()
+                    .map(|(key, ty)| Ok((key.clone(), Self::build_from_flat(values, ty)?)))
+                    .collect::<Result<IndexMap<_, _>, TypeError>>()?;
+                Self::new_object(values)
+            }
+            _ => {
+                let (index, value) = values.next_value()?;
+                let actual = value.to_type();
+                if &actual != ty {
+                    return Err(TypeError::UnexpectedValueType { index, expected: ty.clone(), actual });
+                }
+                Ok(value)
+            }
+        }
+    }
+
     /// Returns an iterator over this NadaValue.
     /// This iterator goes over any compound types.
     pub fn iter(&self) -> NadaValueIter<T> {
@@ -346,6 +408,26 @@ impl<T: PrimitiveTypes> NadaValue<T> {
     }
 }
 
+/// The primitive values a value is being built from, in the order they appear in its type.
+struct FlatValues<I> {
+    values: I,
+    expected: usize,
+    consumed: usize,
+}
+
+impl<I: Iterator> FlatValues<I> {
+    /// Returns the next value along with its position, failing if there are no values left.
+    fn next_value(&mut self) -> Result<(usize, I::Item), TypeError> {
+        let value = self
+            .values
+            .next()
+            .ok_or(TypeError::ValueCountMismatch { expected: self.expected, actual: self.consumed })?;
+        let index = self.consumed;
+        self.consumed = self.consumed.saturating_add(1);
+        Ok((index, value))
+    }
+}
+
 /// Iterator over a NadaValue.
 /// This iterator goes over any compound types.
 pub struct NadaValueIter<'a, T: PrimitiveTypes> {
@@ -1009,4 +1091,109 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn try_from_flat() -> Result<()> {
+        let ty = NadaType::new_tuple(
+            NadaType::new_array(NadaType::SecretInteger, 2)?,
+            NadaType::new_object([("a".to_string(), NadaType::Boolean)].into())?,
+        )?;
+        let values = vec![
+            NadaValue::<Clear>::new_secret_integer(1),
+            NadaValue::new_secret_integer(2),
+            NadaValue::new_boolean(true),
+        ];
+        let value = NadaValue::try_from_flat(values, &ty)?;
+        let expected = NadaValue::new_tuple(
+            NadaValue::new_array(
+                NadaType::SecretInteger,
+                vec![NadaValue::new_secret_integer(1), NadaValue::new_secret_integer(2)],
+            )?,
+            NadaValue::new_object([("a".to_string(), NadaValue::new_boolean(true))].into())?,
+        )?;
+        assert_eq!(value, expected);
+        assert_eq!(value.to_type(), ty);
+        Ok(())
+    }
+
+    #[test]
+    fn try_from_flat_too_few_values() -> Result<()> {
+        let ty = NadaType::new_array(NadaType::SecretInteger, 3)?;
+        let values = vec![NadaValue::<Clear>::new_secret_integer(1), NadaValue::new_secret_integer(2)];
+        let result = NadaValue::try_from_flat(values, &ty);
+        assert_eq!(result, Err(TypeError::ValueCountMismatch { expected: 3, actual: 2 }));
+        Ok(())
+    }
+
+    #[test]
+    fn try_from_flat_wrong_leaf_type() -> Result<()> {
+        let ty = NadaType::new_n_tuple(vec![NadaType::SecretInteger, NadaType::SecretUnsignedInteger])?;
+        let values = vec![NadaValue::<Clear>::new_secret_integer(1), NadaValue::new_secret_integer(2)];
+        let result = NadaValue::try_from_flat(values, &ty);
+        assert_eq!(
+            result,
+            Err(TypeError::UnexpectedValueType {
+                index: 1,
+                expected: NadaType::SecretUnsignedInteger,
+                actual: NadaType::SecretInteger,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn build_for_type() -> Result<()> {
+        let ty = NadaType::new_n_tuple(vec![
+            NadaType::new_array(NadaType::SecretUnsignedInteger, 2)?,
+            NadaType::new_tuple(NadaType::Integer, NadaType::SecretBoolean)?,
+        ])?;
+        let leaves = [
+            NadaValue::<Clear>::new_secret_unsigned_integer(1u32),
+            NadaValue::new_secret_unsigned_integer(2u32),
+            NadaValue::new_integer(3),
+            NadaValue::new_secret_boolean(false),
+        ];
+        let value = NadaValue::build_for_type(&ty, leaves.into_iter())?;
+        let expected = NadaValue::new_n_tuple(vec![
+            NadaValue::new_array(
+                NadaType::SecretUnsignedInteger,
+                vec![NadaValue::new_secret_unsigned_integer(1u32), NadaValue::new_secret_unsigned_integer(2u32)],
+            )?,
+            NadaValue::new_tuple(NadaValue::new_integer(3), NadaValue::new_secret_boolean(false))?,
+        ])?;
+        assert_eq!(value, expected);
+        assert_eq!(value.to_type(), ty);
+        Ok(())
+    }
+
+    #[test]
+    fn build_for_type_wrong_leaf_type() -> Result<()> {
+        let ty = NadaType::new_array(NadaType::SecretInteger, 3)?;
+        // The third leaf must never be consumed since building stops at the mismatching one.
+        let leaves = [NadaValue::<Clear>::new_secret_integer(1), NadaValue::new_integer(2)]
+            .into_iter()
+            .chain(std::iter::once_with(|| unreachable!("leaf consumed after a mismatch")));
+        let result = NadaValue::build_for_type(&ty, leaves);
+        assert_eq!(
+            result,
+            Err(TypeError::UnexpectedValueType {
+                index: 1,
+                expected: NadaType::SecretInteger,
+                actual: NadaType::Integer,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn build_for_type_value_count_mismatch() -> Result<()> {
+        let ty = NadaType::new_array(NadaType::Integer, 2)?;
+        let too_few = NadaValue::<Clear>::build_for_type(&ty, [NadaValue::new_integer(1)].into_iter());
+        assert_eq!(too_few, Err(TypeError::ValueCountMismatch { expected: 2, actual: 1 }));
+
+        let leaves = (0..4).map(NadaValue::<Clear>::new_integer);
+        let too_many = NadaValue::build_for_type(&ty, leaves);
+        assert_eq!(too_many, Err(TypeError::TooManyValues { expected: 2 }));
+        Ok(())
+    }
 }
@@ -143,4 +143,24 @@ mod test {
         let secrets = args.parse();
         assert!(!secrets.is_err());
     }
+
+    #[test]
+    fn parse_invalid_secret_integer() {
+        let args = NadaValueArgs {
+            integers: vec![],
+            unsigned_integers: vec![],
+            secret_integers: vec!["a=1".to_string(), "b=ten".to_string()],
+            secret_unsigned_integers: vec![],
+            array_integers: vec![],
+            array_unsigned_integers: vec![],
+            array_secret_integers: vec![],
+            array_secret_unsigned_integers: vec![],
+            secret_blobs: vec![],
+            ecdsa_digest_messages: vec![],
+            nada_values_path: None,
+        };
+        let error = args.parse().expect_err("parsing succeeded");
+        assert_eq!(error.to_string(), "invalid value 'ten' for input b: expected SecretInteger");
+        assert!(error.downcast_ref::<crate::InvalidLiteral>().is_some());
+    }
 }
@@ -21,3 +21,4 @@ pub mod named;
 pub(crate) mod parse;
 
 pub use args::NadaValueArgs;
+pub use parse::InvalidLiteral;
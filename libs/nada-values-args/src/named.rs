@@ -1,6 +1,7 @@
 //! Named entities.
 
 /// A named entity.
+#[derive(Debug)]
 pub struct Named<T> {
     /// The entity's name.
     pub name: String,
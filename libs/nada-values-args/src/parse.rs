@@ -4,7 +4,7 @@ use crate::named::Named;
 use anyhow::anyhow;
 use base64::{prelude::BASE64_STANDARD, Engine};
 use nada_value::{clear::Clear, BigInt, BigUint, NadaType, NadaValue};
-use std::str::FromStr;
+use std::fmt;
 
 /// Allows a type to act as a parser for another type.
 pub(crate) trait Parse {
@@ -38,12 +38,58 @@ pub(crate) trait Parse {
     }
 }
 
-pub(crate) fn value_from_str<T>(value: &str) -> anyhow::Result<T>
-where
-    T: FromStr,
-    T::Err: std::error::Error,
-{
-    T::from_str(value).map_err(|e| anyhow!("failed parsing secret '{value}': {e}"))
+/// A value that can't be parsed as the type of the input it was provided for.
+#[derive(Debug, PartialEq)]
+pub struct InvalidLiteral {
+    /// The input name.
+    pub name: String,
+
+    /// The type the value was expected to have.
+    pub expected: NadaType,
+
+    /// The offending value.
+    pub value: String,
+}
+
+impl fmt::Display for InvalidLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value '{}' for input {}: expected {}", self.value, self.name, self.expected)
+    }
+}
+
+impl std::error::Error for InvalidLiteral {}
+
+/// Parses an integer literal.
+///
+/// Underscores can be used to separate digits and a `0x` prefix denotes a hex number, e.g. `1_000` or `-0xff`.
+fn parse_integer(name: &str, expected: &NadaType, value: &str) -> Result<BigInt, InvalidLiteral> {
+    let invalid = || InvalidLiteral { name: name.to_string(), expected: expected.clone(), value: value.to_string() };
+    let (negative, magnitude) = match value.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (radix, digits) = match magnitude.strip_prefix("0x").or_else(|| magnitude.strip_prefix("0X")) {
+        Some(digits) => (16, digits),
+        None => (10, magnitude),
+    };
+    if digits.starts_with('_') || digits.ends_with('_') {
+        return Err(invalid());
+    }
+    let digits: String = digits.chars().filter(|c| *c != '_').collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(invalid());
+    }
+    let digits = if negative { format!("-{digits}") } else { digits };
+    BigInt::parse_bytes(digits.as_bytes(), radix).ok_or_else(invalid)
+}
+
+/// Parses an unsigned integer literal, see [`parse_integer`] for the accepted formats.
+fn parse_unsigned_integer(name: &str, expected: &NadaType, value: &str) -> Result<BigUint, InvalidLiteral> {
+    parse_integer(name, expected, value)?.try_into().map_err(|_| InvalidLiteral {
+        name: name.to_string(),
+        expected: expected.clone(),
+        value: value.to_string(),
+    })
 }
 
 impl Parse for NadaType {
@@ -53,11 +99,11 @@ impl Parse for NadaType {
         use NadaType::*;
 
         let value = match &self {
-            Integer => NadaValue::new_integer(value_from_str::<BigInt>(value)?),
-            UnsignedInteger => NadaValue::new_unsigned_integer(value_from_str::<BigUint>(value)?),
-            SecretInteger => NadaValue::new_secret_integer(value_from_str::<BigInt>(value)?),
-            SecretUnsignedInteger => NadaValue::new_secret_unsigned_integer(value_from_str::<BigUint>(value)?),
-            Array { inner_type, .. } => parse_array(inner_type.as_ref(), value)?,
+            Integer => NadaValue::new_integer(parse_integer(name, self, value)?),
+            UnsignedInteger => NadaValue::new_unsigned_integer(parse_unsigned_integer(name, self, value)?),
+            SecretInteger => NadaValue::new_secret_integer(parse_integer(name, self, value)?),
+            SecretUnsignedInteger => NadaValue::new_secret_unsigned_integer(parse_unsigned_integer(name, self, value)?),
+            Array { inner_type, .. } => parse_array(name, inner_type.as_ref(), value)?,
             SecretBlob => {
                 let value = BASE64_STANDARD.decode(value).map_err(|e| anyhow!("invalid base64 blob: {e}"))?;
                 NadaValue::new_secret_blob(value)
@@ -98,15 +144,17 @@ impl Parse for NadaType {
 /// Utility function that parses an array.
 ///
 /// It expects a string with comma-separated values.
-fn parse_array(inner_type: &NadaType, value: &str) -> anyhow::Result<NadaValue<Clear>> {
+fn parse_array(name: &str, inner_type: &NadaType, value: &str) -> anyhow::Result<NadaValue<Clear>> {
     use NadaType::*;
     let mut values = vec![];
     for element in value.split(',') {
         let element_value = match inner_type {
-            Integer => NadaValue::new_integer(value_from_str::<BigInt>(element)?),
-            UnsignedInteger => NadaValue::new_unsigned_integer(value_from_str::<BigUint>(element)?),
-            SecretInteger => NadaValue::new_secret_integer(value_from_str::<BigInt>(element)?),
-            SecretUnsignedInteger => NadaValue::new_secret_unsigned_integer(value_from_str::<BigUint>(element)?),
+            Integer => NadaValue::new_integer(parse_integer(name, inner_type, element)?),
+            UnsignedInteger => NadaValue::new_unsigned_integer(parse_unsigned_integer(name, inner_type, element)?),
+            SecretInteger => NadaValue::new_secret_integer(parse_integer(name, inner_type, element)?),
+            SecretUnsignedInteger => {
+                NadaValue::new_secret_unsigned_integer(parse_unsigned_integer(name, inner_type, element)?)
+            }
 
             _ => Err(anyhow!("{} secret", element))?,
         };
@@ -123,7 +171,7 @@ fn split_name_value(input: &str) -> anyhow::Result<(&str, &str)> {
 
 #[cfg(test)]
 mod test {
-    use super::Parse;
+    use super::{InvalidLiteral, Parse};
     use crate::named::Named;
     use nada_value::{clear::Clear, NadaType, NadaValue};
     use rstest::rstest;
@@ -157,6 +205,47 @@ mod test {
         assert_eq!(value.value, expected);
     }
 
+    #[rstest]
+    #[case(NadaType::Integer, "1_000_000", NadaValue::new_integer(1_000_000))]
+    #[case(NadaType::Integer, "-1_000", NadaValue::new_integer(-1_000))]
+    #[case(NadaType::SecretUnsignedInteger, "10_000", NadaValue::new_secret_unsigned_integer(10_000u32))]
+    #[case(NadaType::Integer, "0xff", NadaValue::new_integer(255))]
+    #[case(NadaType::SecretInteger, "-0x1F", NadaValue::new_secret_integer(-31))]
+    #[case(NadaType::UnsignedInteger, "0xdead_beef", NadaValue::new_unsigned_integer(0xdead_beef_u32))]
+    fn parse_integer_formats(
+        #[case] nada_type: NadaType,
+        #[case] string_repr: &str,
+        #[case] expected: NadaValue<Clear>,
+    ) {
+        let value = nada_type.parse_named("value", string_repr).expect("parsing failed");
+        assert_eq!(value.value, expected);
+    }
+
+    #[rstest]
+    #[case(NadaType::SecretInteger, "abc")]
+    #[case(NadaType::Integer, "")]
+    #[case(NadaType::Integer, "_1")]
+    #[case(NadaType::Integer, "1_")]
+    #[case(NadaType::Integer, "0x")]
+    #[case(NadaType::Integer, "--1")]
+    #[case(NadaType::Integer, "0xfg")]
+    #[case(NadaType::SecretUnsignedInteger, "-1")]
+    fn parse_invalid_integer(#[case] nada_type: NadaType, #[case] string_repr: &str) {
+        let error = nada_type.parse_named("value", string_repr).expect_err("parsing succeeded");
+        let error = error.downcast::<InvalidLiteral>().expect("not an invalid literal error");
+        assert_eq!(
+            error,
+            InvalidLiteral { name: "value".to_string(), expected: nada_type, value: string_repr.to_string() }
+        );
+    }
+
+    #[test]
+    fn parse_invalid_array_element() {
+        let nada_type = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: 0 };
+        let error = nada_type.parse("my_array=1,two,3").expect_err("parsing succeeded");
+        assert_eq!(error.to_string(), "invalid value 'two' for input my_array: expected SecretInteger");
+    }
+
     #[rstest]
     #[case(NadaType::Integer, "1,2,3", vec![new_integer(1), new_integer(2), new_integer(3)])]
     #[case(NadaType::UnsignedInteger, "4,2,1", vec![new_unsigned_integer(4), new_unsigned_integer(2), new_unsigned_integer(1)],)]
@@ -47,7 +47,7 @@ fn main() {
         .protoc_arg("--fatal_warnings")
         .enum_attribute(
             "nillion.preprocessing.v1.element.PreprocessingElement",
-            "#[derive(strum::EnumIter, strum::EnumString, strum::Display)]",
+            "#[derive(strum::EnumIter, strum::EnumString, strum::Display, strum::IntoStaticStr)]",
         )
         .enum_attribute(
             "nillion.preprocessing.v1.material.AuxiliaryMaterial",
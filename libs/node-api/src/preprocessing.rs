@@ -23,6 +23,18 @@ pub mod rust {
     impl TransparentProto for AuxiliaryMaterial {}
     impl TransparentProto for PreprocessingProtocolStatus {}
 
+    impl PreprocessingElement {
+        /// Get all the preprocessing elements.
+        pub fn all() -> impl Iterator<Item = Self> {
+            <Self as strum::IntoEnumIterator>::iter()
+        }
+
+        /// Get this element's name, e.g. `Compare`.
+        pub fn name(&self) -> &'static str {
+            self.into()
+        }
+    }
+
     /// A request to generate preprocessing material.
     #[derive(Clone, Debug, PartialEq)]
     pub struct GeneratePreprocessingRequest {
@@ -58,7 +58,22 @@ impl Config {
             .add_source(source)
             .add_source(config::Environment::default().separator("__"))
             .build()?;
-        config.try_deserialize()
+        let config: Self = config.try_deserialize()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Validate the configuration values that can't be checked during deserialization.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(tracing) = &self.tracing {
+            tracing.validate()?;
+        }
+        if let Some(preprocessing) = &self.network.preprocessing {
+            preprocessing.validate()?;
+        }
+        self.cluster.validate()?;
+        self.runtime.validate()?;
+        Ok(())
     }
 }
 
@@ -80,12 +95,51 @@ pub struct MetricsConfig {
 /// Configuration for the runtime.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RuntimeConfig {
-    /// The maximum number of concurrent actions allowed.
+    /// The maximum number of concurrent actions allowed, across all operations.
+    ///
+    /// Requests for new actions are rejected as unavailable while this many actions are running.
     #[serde(default = "default_max_concurrent_actions")]
     pub max_concurrent_actions: usize,
 
+    /// The maximum number of concurrent actions allowed for specific operations.
+    ///
+    /// Keys are one of [RuntimeConfig::LIMITABLE_OPERATIONS]. Requests for an operation are rejected as unavailable
+    /// while as many actions as its limit are running. Every operation, with or without an entry, also counts
+    /// towards `max_concurrent_actions`.
+    #[serde(default)]
+    pub max_concurrent_per_operation: HashMap<String, usize>,
+
     /// The gRPC config.
     pub grpc: GrpcConfig,
+
+    /// The maximum amount of time to wait for in-flight actions to finish when shutting down.
+    ///
+    /// Once this period elapses, any actions still running are cancelled.
+    #[serde(with = "humantime_serde", default = "default_shutdown_grace_period")]
+    pub shutdown_grace_period: Duration,
+}
+
+impl RuntimeConfig {
+    /// The operations that can be given their own concurrency limit.
+    pub const LIMITABLE_OPERATIONS: [&'static str; 3] = ["compute", "ecdsa_dkg", "eddsa_dkg"];
+
+    /// Validate the runtime configuration.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (operation, limit) in &self.max_concurrent_per_operation {
+            if !Self::LIMITABLE_OPERATIONS.contains(&operation.as_str()) {
+                return Err(ConfigError::Message(format!(
+                    "unknown operation {operation} in max concurrent actions, expected one of {:?}",
+                    Self::LIMITABLE_OPERATIONS
+                )));
+            }
+            if *limit == 0 {
+                return Err(ConfigError::Message(format!(
+                    "max concurrent actions for operation {operation} must be > 0"
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// The gRPC config.
@@ -118,6 +172,13 @@ pub struct GrpcTlsConfig {
 /// The rate limit configuration.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RateLimitConfig {
+    /// Whether rate limiting is enabled.
+    ///
+    /// This allows turning rate limiting off via environment variables, e.g.
+    /// `RUNTIME__GRPC__RATE_LIMIT__ENABLED=false`, without removing the configuration block.
+    #[serde(default = "default_rate_limit_enabled")]
+    pub enabled: bool,
+
     /// The bucketting strategy for rate limiting.
     pub bucket: RateLimitBucket,
 
@@ -125,6 +186,10 @@ pub struct RateLimitConfig {
     pub max_per_bucket: NonZeroU32,
 }
 
+fn default_rate_limit_enabled() -> bool {
+    true
+}
+
 /// The rate limit configuration.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum RateLimitBucket {
@@ -146,6 +211,36 @@ pub struct StorageConfig {
 
     /// The URL to the SQLite database.
     pub db_url: String,
+
+    /// The timeout for every object storage request, regardless of the backend.
+    ///
+    /// A stalled request fails once this elapses instead of hanging. Setting this to `null` disables the timeout.
+    #[serde(with = "humantime_serde", default = "default_storage_request_timeout")]
+    pub request_timeout: Option<Duration>,
+
+    /// Retries for S3 reads that don't find an object that was just written.
+    ///
+    /// Some S3 compatible backends don't guarantee read-after-write consistency. Setting this to `null` disables
+    /// these retries.
+    #[serde(default = "default_storage_read_after_write_retry")]
+    pub read_after_write_retry: Option<ReadAfterWriteRetryConfig>,
+}
+
+/// Configuration for the retries of reads that don't find an object that was just written.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReadAfterWriteRetryConfig {
+    /// The maximum number of times a read is retried.
+    pub attempts: u32,
+
+    /// The time to wait between retries.
+    #[serde(with = "humantime_serde")]
+    pub delay: Duration,
+
+    /// How long after an object is written a missing read for it is retried.
+    ///
+    /// Reads for objects that weren't written within this window are not found right away.
+    #[serde(with = "humantime_serde")]
+    pub window: Duration,
 }
 
 /// Configuration for the object storage.
@@ -220,14 +315,54 @@ fn default_process_collector_interval() -> Duration {
 }
 
 /// Configuration for tracing.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TracingConfig {
     /// The path where to store the JSON traces.
+    ///
+    /// If this is a directory, traces are written to files within it that are rotated daily.
     pub json_path: Option<PathBuf>,
 
+    /// Whether to write JSON traces as flat JSON lines, with each event's fields at the top level of its line.
+    ///
+    /// This requires `json_path` to be set.
+    #[serde(default)]
+    pub json_lines: bool,
+
     /// Whether to print output to standard output.
     #[serde(default)]
     pub stdout: bool,
+
+    /// The ratio of traces that are recorded, between 0.0 and 1.0. Defaults to 1.0.
+    ///
+    /// Sampling only applies to new root spans: spans and events nested in a root span are recorded only if the
+    /// root span was sampled. Events outside of any span are always recorded.
+    #[serde(default)]
+    pub sample_ratio: Option<f64>,
+
+    /// The targets to be recorded. If empty, all targets are recorded.
+    ///
+    /// A target matches if it starts with any of these, e.g. `node` matches `node::services`.
+    #[serde(default)]
+    pub include_targets: Vec<String>,
+
+    /// The targets not to be recorded, even if they match `include_targets`.
+    #[serde(default)]
+    pub exclude_targets: Vec<String>,
+}
+
+impl TracingConfig {
+    /// Validate the tracing configuration.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.json_lines && self.json_path.is_none() {
+            return Err(ConfigError::Message("tracing json lines requires a json path".into()));
+        }
+        match self.sample_ratio {
+            Some(ratio) if !(0.0..=1.0).contains(&ratio) => {
+                Err(ConfigError::Message(format!("tracing sample ratio must be between 0.0 and 1.0, got {ratio}")))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 /// The payments configuration.
@@ -244,6 +379,15 @@ pub struct PaymentsConfig {
     #[serde(default = "default_receipt_ttl")]
     pub receipt_ttl: Duration,
 
+    /// The tolerated clock skew between clients and nodes when validating quotes and receipts.
+    ///
+    /// This is applied symmetrically. A quote or receipt is still considered valid up to this long after its
+    /// expiration, which covers the node that issued it having a clock that's behind ours. It's also accepted if it
+    /// appears to have been issued up to this long in the future, which covers that node's clock being ahead of ours.
+    /// Their nonces are kept until the extended expiration so they can't be replayed in the meantime.
+    #[serde(default = "default_clock_skew_tolerance")]
+    pub clock_skew_tolerance: Duration,
+
     /// The minimum add funds payment in credits.
     #[serde(default = "default_minimum_add_funds_payment")]
     pub minimum_add_funds_payment: u64,
@@ -303,6 +447,45 @@ pub struct PricingConfig {
     pub invoke_compute_price: u64,
 }
 
+impl PricingConfig {
+    /// Build a new pricing configuration by replacing the prices set in `overrides`.
+    pub fn overlay(&self, overrides: PartialPricingConfig) -> Self {
+        Self {
+            retrieve_permissions_price: overrides.retrieve_permissions_price.unwrap_or(self.retrieve_permissions_price),
+            pool_status_price: overrides.pool_status_price.unwrap_or(self.pool_status_price),
+            overwrite_permissions_price: overrides
+                .overwrite_permissions_price
+                .unwrap_or(self.overwrite_permissions_price),
+            update_permissions_price: overrides.update_permissions_price.unwrap_or(self.update_permissions_price),
+            retrieve_values_price: overrides.retrieve_values_price.unwrap_or(self.retrieve_values_price),
+            store_program_price: overrides.store_program_price.unwrap_or(self.store_program_price),
+            store_values_price: overrides.store_values_price.unwrap_or(self.store_values_price),
+            invoke_compute_price: overrides.invoke_compute_price.unwrap_or(self.invoke_compute_price),
+        }
+    }
+}
+
+/// A pricing configuration where every price is optional, used to override a [PricingConfig].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PartialPricingConfig {
+    /// Price of retrieve permissions operation
+    pub retrieve_permissions_price: Option<u64>,
+    /// Price of pool status operation
+    pub pool_status_price: Option<u64>,
+    /// Price of overwrite permissions operation
+    pub overwrite_permissions_price: Option<u64>,
+    /// Price of update permissions operation
+    pub update_permissions_price: Option<u64>,
+    /// Price of retrieve values operation
+    pub retrieve_values_price: Option<u64>,
+    /// Price of store program operation
+    pub store_program_price: Option<u64>,
+    /// Price of store values operation
+    pub store_values_price: Option<u64>,
+    /// Price of invoke compute operation
+    pub invoke_compute_price: Option<u64>,
+}
+
 /// A cluster's definition.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Cluster {
@@ -322,8 +505,22 @@ pub struct Cluster {
     pub kappa: u32,
 }
 
+impl Cluster {
+    /// Validate the cluster definition.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.members.contains(&self.leader) {
+            return Err(ConfigError::Message(format!(
+                "cluster leader at {} is not one of the cluster members",
+                self.leader.grpc_endpoint
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// A cluster member.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(try_from = "UncheckedClusterMember")]
 pub struct ClusterMember {
     /// The public keys for this member.
     pub public_keys: PublicKeys,
@@ -332,9 +529,32 @@ pub struct ClusterMember {
     pub grpc_endpoint: String,
 }
 
+/// A cluster member whose public keys haven't been validated yet.
+#[derive(Deserialize)]
+struct UncheckedClusterMember {
+    public_keys: PublicKeys,
+    grpc_endpoint: String,
+}
+
+impl TryFrom<UncheckedClusterMember> for ClusterMember {
+    type Error = String;
+
+    fn try_from(member: UncheckedClusterMember) -> Result<Self, Self::Error> {
+        let UncheckedClusterMember { public_keys, grpc_endpoint } = member;
+        let length = public_keys.authentication.len();
+        if !public_keys.kind.public_key_lengths().contains(&length) {
+            return Err(format!(
+                "invalid {:?} authentication public key length for cluster member {grpc_endpoint}: {length} bytes",
+                public_keys.kind
+            ));
+        }
+        Ok(Self { public_keys, grpc_endpoint })
+    }
+}
+
 /// The public keys for a cluster member.
 #[serde_as]
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct PublicKeys {
     /// The authentication public key.
     #[serde(deserialize_with = "hex::serde::deserialize")]
@@ -347,7 +567,7 @@ pub struct PublicKeys {
 }
 
 /// A key kind.
-#[derive(Clone, Debug, Deserialize, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum KeyKind {
     /// An ed25519 key.
@@ -358,6 +578,17 @@ pub enum KeyKind {
     Secp256k1,
 }
 
+impl KeyKind {
+    /// The valid lengths, in bytes, of a public key of this kind.
+    pub fn public_key_lengths(&self) -> &'static [usize] {
+        match self {
+            KeyKind::Ed25519 => &[32],
+            // Compressed and uncompressed SEC1 encodings.
+            KeyKind::Secp256k1 => &[33, 65],
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Prime {
     // A safe 64 bit prime number.
@@ -412,6 +643,12 @@ pub struct PreprocessingConfig {
 
     /// The RandomBit generation protocol configuration.
     pub random_boolean: PreprocessingProtocolConfig,
+
+    /// The configuration for experimental or custom protocols that don't have a dedicated field, keyed by name.
+    ///
+    /// Preprocessing elements without a dedicated field are looked up here by their name, e.g. `Compare`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, PreprocessingProtocolConfig>,
 }
 
 impl PreprocessingConfig {
@@ -427,8 +664,61 @@ impl PreprocessingConfig {
             equals_integer_secret: config.clone(),
             random_integer: config.clone(),
             random_boolean: config.clone(),
+            extra: HashMap::new(),
         }
     }
+
+    /// Get the configuration of every protocol along with the name of the field it's stored in.
+    pub fn protocols(&self) -> [(&'static str, &PreprocessingProtocolConfig); 9] {
+        // Destructure without `..` so adding a field fails to compile until it's listed here.
+        let Self {
+            compare,
+            division_integer_secret,
+            modulo,
+            public_output_equality,
+            truncpr,
+            trunc,
+            equals_integer_secret,
+            random_integer,
+            random_boolean,
+            extra: _,
+        } = self;
+        [
+            ("compare", compare),
+            ("division_integer_secret", division_integer_secret),
+            ("modulo", modulo),
+            ("public_output_equality", public_output_equality),
+            ("truncpr", truncpr),
+            ("trunc", trunc),
+            ("equals_integer_secret", equals_integer_secret),
+            ("random_integer", random_integer),
+            ("random_boolean", random_boolean),
+        ]
+    }
+
+    /// Validate the preprocessing configuration.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let protocols = self.protocols();
+        for name in self.extra.keys() {
+            if protocols.iter().any(|(field, _)| field == name) {
+                return Err(ConfigError::Message(format!(
+                    "extra preprocessing protocol {name} is already configured through its dedicated field"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the configuration of a protocol by name.
+    ///
+    /// Protocols with a dedicated field are looked up by the name of that field, any other protocol is looked up in
+    /// [PreprocessingConfig::extra].
+    pub fn protocol(&self, name: &str) -> Option<&PreprocessingProtocolConfig> {
+        self.protocols()
+            .into_iter()
+            .find_map(|(field, config)| (field == name).then_some(config))
+            .or_else(|| self.extra.get(name))
+    }
 }
 
 /// The configuration for an auxiliary material protocol.
@@ -451,7 +741,7 @@ pub struct AuxiliaryMaterialConfig {
 }
 
 /// The network configuration.
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct NetworkConfig {
     /// The preprocessing configuration.
     ///
@@ -468,14 +758,53 @@ pub struct NetworkConfig {
     /// The maximum request payload size.
     #[serde(default = "default_max_payload_size")]
     pub max_payload_size: u64,
+
+    /// The maximum number of operations in an uploaded program.
+    ///
+    /// Programs are checked against this before being compiled, so that payloads that fit in
+    /// `max_payload_size` but describe very large programs are rejected early.
+    #[serde(default = "default_max_program_operations")]
+    pub max_program_operations: u64,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            preprocessing: None,
+            auxiliary_material: None,
+            max_payload_size: default_max_payload_size(),
+            max_program_operations: default_max_program_operations(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct TokenDollarConversionConfig {
     /// The API key for the CoinGecko API.
     pub coingecko_api_key: String,
     /// Coin Id
     pub coin_id: String,
+
+    /// How often the token price is refreshed.
+    #[serde(with = "humantime_serde", default = "default_token_price_refresh_interval")]
+    pub refresh_interval: Duration,
+
+    /// How long the last known token price is used when refreshing it fails.
+    ///
+    /// After this, `dollar_token_conversion_fixed` is used until a refresh succeeds.
+    #[serde(with = "humantime_serde", default = "default_token_price_max_staleness")]
+    pub max_staleness: Duration,
+}
+
+impl Default for TokenDollarConversionConfig {
+    fn default() -> Self {
+        Self {
+            coingecko_api_key: String::new(),
+            coin_id: String::new(),
+            refresh_interval: default_token_price_refresh_interval(),
+            max_staleness: default_token_price_max_staleness(),
+        }
+    }
 }
 
 /// The default maximum payload size.
@@ -483,6 +812,11 @@ pub fn default_max_payload_size() -> u64 {
     6 * 1024 * 1024
 }
 
+/// The default maximum number of operations in an uploaded program.
+pub fn default_max_program_operations() -> u64 {
+    100_000
+}
+
 /// The default TTL for quotes.
 pub fn default_quote_ttl() -> Duration {
     Duration::from_secs(60 * 60 * 24)
@@ -493,6 +827,26 @@ pub fn default_receipt_ttl() -> Duration {
     Duration::from_secs(60 * 60 * 24)
 }
 
+/// The default clock skew tolerance for quotes and receipts.
+pub fn default_clock_skew_tolerance() -> Duration {
+    Duration::from_secs(5)
+}
+
+/// The default grace period to wait for in-flight actions when shutting down.
+pub fn default_shutdown_grace_period() -> Duration {
+    Duration::from_secs(300)
+}
+
+/// The default timeout for object storage requests.
+pub fn default_storage_request_timeout() -> Option<Duration> {
+    Some(Duration::from_secs(30))
+}
+
+/// The default retries for reads that don't find an object that was just written.
+pub fn default_storage_read_after_write_retry() -> Option<ReadAfterWriteRetryConfig> {
+    Some(ReadAfterWriteRetryConfig { attempts: 3, delay: Duration::from_millis(100), window: Duration::from_secs(10) })
+}
+
 fn default_max_concurrent_actions() -> usize {
     usize::MAX
 }
@@ -510,3 +864,208 @@ fn default_dollar_token_conversion_fixed() -> f64 {
     // 1$
     1.0
 }
+
+fn default_token_price_refresh_interval() -> Duration {
+    Duration::from_secs(60 * 5)
+}
+
+fn default_token_price_max_staleness() -> Duration {
+    Duration::from_secs(60 * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_rate_limit(yaml: &str) -> RateLimitConfig {
+        config::Config::builder()
+            .add_source(config::File::from_str(yaml, config::FileFormat::Yaml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+
+    #[test]
+    fn network_default_matches_serde_defaults() {
+        let config: NetworkConfig = serde_yaml::from_str("{}").unwrap();
+        let default = NetworkConfig::default();
+        assert_eq!(default.max_payload_size, config.max_payload_size);
+        assert_eq!(default.max_program_operations, config.max_program_operations);
+        assert_ne!(default.max_program_operations, 0);
+    }
+
+    #[test]
+    fn token_dollar_conversion_default_matches_serde_defaults() {
+        let config: TokenDollarConversionConfig = serde_yaml::from_str("{coingecko_api_key: '', coin_id: ''}").unwrap();
+        let default = TokenDollarConversionConfig::default();
+        assert_eq!(default.refresh_interval, config.refresh_interval);
+        assert_eq!(default.max_staleness, config.max_staleness);
+        assert_ne!(default.refresh_interval, Duration::ZERO);
+    }
+
+    #[test]
+    fn preprocessing_extra_protocols() {
+        let protocol =
+            PreprocessingProtocolConfig { batch_size: 16, generation_threshold: 100, target_offset_jump: 10 };
+        let mut preprocessing = PreprocessingConfig::new(PreprocessingProtocolConfig::default());
+        preprocessing.extra.insert("experimental".into(), protocol.clone());
+
+        let yaml = serde_yaml::to_string(&preprocessing).unwrap();
+        let parsed: PreprocessingConfig = config::Config::builder()
+            .add_source(config::File::from_str(&yaml, config::FileFormat::Yaml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+        assert_eq!(parsed, preprocessing);
+        assert_eq!(parsed.protocol("experimental"), Some(&protocol));
+        assert_eq!(parsed.protocol("compare"), Some(&parsed.compare));
+        assert_eq!(parsed.protocol("unknown"), None);
+        assert!(parsed.validate().is_ok());
+    }
+
+    #[test]
+    fn preprocessing_extra_shadowing_field() {
+        let mut preprocessing = PreprocessingConfig::new(PreprocessingProtocolConfig::default());
+        preprocessing.extra.insert("compare".into(), PreprocessingProtocolConfig::default());
+        assert!(preprocessing.validate().is_err());
+    }
+
+    #[test]
+    fn runtime_operation_limits() {
+        let parse = |limits: &str| -> RuntimeConfig {
+            let yaml = format!("grpc:\n  bind_endpoint: 127.0.0.1:0\nmax_concurrent_per_operation:\n  {limits}");
+            serde_yaml::from_str(&yaml).unwrap()
+        };
+        assert!(parse("compute: 1").validate().is_ok());
+        assert!(parse("compute: 0").validate().is_err());
+        assert!(parse("/nillion.compute.v1.Compute/InvokeCompute: 1").validate().is_err());
+    }
+
+    #[test]
+    fn program_auditor_unknown_disabled_policy() {
+        let parse = |policy: &str| {
+            let yaml = format!(
+                "max_memory_size: 10\nmax_instructions: 10\nmax_preprocessing:\n  runtime_elements: {{}}\n\
+                 disabled_policies: [{policy}]"
+            );
+            serde_yaml::from_str::<ProgramAuditorConfig>(&yaml)
+        };
+        assert!(parse("max_memory").is_ok());
+        let err = parse("max_memry").unwrap_err();
+        assert!(err.to_string().contains("unknown program auditor policy \"max_memry\""), "{err}");
+    }
+
+    #[test]
+    fn rate_limit_enabled_by_default() {
+        let config = parse_rate_limit("bucket: Second\nmax_per_bucket: 10");
+        assert!(config.enabled);
+    }
+
+    #[test]
+    fn rate_limit_disabled() {
+        let config = parse_rate_limit("enabled: false\nbucket: Minute\nmax_per_bucket: 10");
+        assert!(!config.enabled);
+        assert_eq!(config.max_per_bucket.get(), 10);
+    }
+
+    #[test]
+    fn tracing_sample_ratio_range() {
+        for ratio in [0.0, 0.5, 1.0] {
+            let config = TracingConfig { sample_ratio: Some(ratio), ..Default::default() };
+            assert!(config.validate().is_ok(), "ratio {ratio} rejected");
+        }
+        for ratio in [-0.1, 1.5, f64::NAN] {
+            let config = TracingConfig { sample_ratio: Some(ratio), ..Default::default() };
+            assert!(config.validate().is_err(), "ratio {ratio} accepted");
+        }
+    }
+
+    #[test]
+    fn tracing_json_lines_requires_path() {
+        let config = TracingConfig { json_lines: true, ..Default::default() };
+        assert!(config.validate().is_err());
+
+        let config = TracingConfig { json_lines: true, json_path: Some("/tmp/node.log".into()), ..Default::default() };
+        assert!(config.validate().is_ok());
+    }
+
+    fn member(key: u8, endpoint: &str) -> ClusterMember {
+        ClusterMember {
+            public_keys: PublicKeys { authentication: vec![key; 32], kind: KeyKind::Ed25519 },
+            grpc_endpoint: endpoint.to_string(),
+        }
+    }
+
+    fn cluster(leader: ClusterMember) -> Cluster {
+        Cluster {
+            members: vec![member(1, "http://node-1:14311"), member(2, "http://node-2:14311")],
+            leader,
+            prime: Prime::Safe64Bits,
+            polynomial_degree: 1,
+            kappa: 0,
+        }
+    }
+
+    #[test]
+    fn cluster_leader_is_member() {
+        let cluster = cluster(member(1, "http://node-1:14311"));
+        assert!(cluster.validate().is_ok());
+    }
+
+    #[test]
+    fn cluster_leader_endpoint_mismatch() {
+        let cluster = cluster(member(1, "http://node-3:14311"));
+        assert!(cluster.validate().is_err());
+    }
+
+    fn parse_member(key: &str, kind: &str) -> Result<ClusterMember, ConfigError> {
+        let yaml = format!("grpc_endpoint: http://node-1:14311\npublic_keys:\n  authentication: {key}\n  kind: {kind}");
+        config::Config::builder()
+            .add_source(config::File::from_str(&yaml, config::FileFormat::Yaml))
+            .build()?
+            .try_deserialize()
+    }
+
+    #[test]
+    fn public_key_length_valid() {
+        let member = parse_member(&"ab".repeat(32), "ed25519").unwrap();
+        assert_eq!(member.public_keys.authentication.len(), 32);
+        let member = parse_member(&"ab".repeat(33), "secp256k1").unwrap();
+        assert_eq!(member.public_keys.authentication.len(), 33);
+        let member = parse_member(&"ab".repeat(65), "secp256k1").unwrap();
+        assert_eq!(member.public_keys.authentication.len(), 65);
+    }
+
+    #[test]
+    fn public_key_length_invalid() {
+        let err = parse_member(&"ab".repeat(33), "ed25519").unwrap_err();
+        assert!(err.to_string().contains("http://node-1:14311"), "unexpected error: {err}");
+        assert!(parse_member(&"ab".repeat(32), "secp256k1").is_err());
+    }
+
+    #[test]
+    fn pricing_overlay() {
+        let base = PricingConfig {
+            retrieve_permissions_price: 1,
+            pool_status_price: 2,
+            overwrite_permissions_price: 3,
+            update_permissions_price: 4,
+            retrieve_values_price: 5,
+            store_program_price: 6,
+            store_values_price: 7,
+            invoke_compute_price: 8,
+        };
+        let overrides = PartialPricingConfig { invoke_compute_price: Some(100), ..Default::default() };
+        let pricing = base.overlay(overrides);
+        assert_eq!(pricing.invoke_compute_price, 100);
+        assert_eq!(pricing.retrieve_permissions_price, 1);
+        assert_eq!(pricing.pool_status_price, 2);
+        assert_eq!(pricing.overwrite_permissions_price, 3);
+        assert_eq!(pricing.update_permissions_price, 4);
+        assert_eq!(pricing.retrieve_values_price, 5);
+        assert_eq!(pricing.store_program_price, 6);
+        assert_eq!(pricing.store_values_price, 7);
+    }
+}
@@ -105,10 +105,12 @@ impl<F: Field> Shamir<F> {
             polynomial.add_coefficient(coefficient);
         }
 
+        let abscissas: Vec<_> = self.mapper.abscissas().copied().collect();
+        let points: Vec<_> = abscissas.iter().map(|x| F::as_element(*x)).collect();
+        let ys = polynomial.evaluate_many(&points);
         let mut point_sequence = PointSequence::<F>::default();
-        for x in self.mapper.abscissas() {
-            let y = polynomial.eval_at(x)?;
-            point_sequence.push(Point::new(*x, y))
+        for (x, y) in abscissas.into_iter().zip(ys) {
+            point_sequence.push(Point::new(x, y))
         }
         Ok(point_sequence)
     }
@@ -1,14 +1,26 @@
 //! The threshold private key implementation.
 
-use crate::{publickey::ThresholdPublicKey, PRIVATE_KEY_LENGTH};
-use generic_ec::{errors::InvalidScalar, Curve, NonZero, SecretScalar};
+use crate::{
+    publickey::ThresholdPublicKey,
+    signature::{EddsaSignature, EddsaSignatureError},
+    PRIVATE_KEY_LENGTH,
+};
+use generic_ec::{curves::Ed25519, errors::InvalidScalar, Curve, NonZero, Point, Scalar, SecretScalar};
+use givre::{
+    ciphersuite::{self, Ciphersuite, NormalizedPoint},
+    signing::aggregate::Signature,
+};
 use key_share::{
     self,
     trusted_dealer::{self, TrustedDealerError},
     CoreKeyShare, ReconstructError,
 };
 use rand::rngs::OsRng;
-use std::{cmp::PartialEq, fmt};
+use std::{
+    cmp::PartialEq,
+    fmt,
+    ops::{Add, Mul},
+};
 use subtle::ConstantTimeEq;
 use thiserror::Error;
 
@@ -369,6 +381,48 @@ impl<E: Curve> ThresholdPrivateKey<E> {
     }
 }
 
+impl ThresholdPrivateKey<Ed25519> {
+    /// Produces an EdDSA signature over a message using this private key.
+    ///
+    /// This is a local, non-threshold reference implementation meant to be used on a reconstructed private key, e.g.
+    /// to check the output of the threshold signing protocol. The resulting signature can be verified via
+    /// [`ThresholdPublicKey::verify`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use threshold_keypair::privatekey::ThresholdPrivateKey;
+    /// use generic_ec::curves::Ed25519;
+    /// use generic_ec::SecretScalar;
+    /// use rand::rngs::OsRng;
+    ///
+    /// let mut csprng = OsRng;
+    /// let key = ThresholdPrivateKey::from_scalar(SecretScalar::<Ed25519>::random(&mut csprng)).unwrap();
+    /// let signature = key.sign(b"hello").unwrap();
+    /// assert!(key.public_key().verify(&signature, b"hello").is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`EddsaSignatureError::InvalidPublicKey`] if the public key can't be normalized.
+    /// * [`EddsaSignatureError::InvalidComponentSignature`] if the nonce commitment can't be normalized.
+    pub fn sign(&self, message: &[u8]) -> Result<EddsaSignature, EddsaSignatureError> {
+        let mut csprng = OsRng;
+        let secret: &SecretScalar<Ed25519> = self.as_non_zero_scalar();
+        let secret: &Scalar<Ed25519> = secret.as_ref();
+        let public_key = NormalizedPoint::try_normalize(*self.public_key().as_point())
+            .map_err(|_| EddsaSignatureError::InvalidPublicKey)?;
+        let nonce = SecretScalar::<Ed25519>::random(&mut csprng);
+        let r_point = Point::<Ed25519>::generator().to_point().mul(&nonce);
+        let r = NormalizedPoint::try_normalize(r_point).map_err(|_| {
+            EddsaSignatureError::InvalidComponentSignature("invalid normalization of signature point r".to_string())
+        })?;
+        // z = nonce + challenge * secret
+        let challenge = ciphersuite::Ed25519::compute_challenge(&r, &public_key, message);
+        let z = nonce.as_ref().add(challenge.mul(secret));
+        Ok(EddsaSignature { signature: Signature { r, z } })
+    }
+}
+
 impl<E: Curve> PartialEq for ThresholdPrivateKey<E> {
     fn eq(&self, other: &Self) -> bool {
         let left = self.as_non_zero_scalar().clone();
@@ -1,9 +1,18 @@
 //! The threshold public key implementation.
 
-use crate::privatekey::ThresholdPrivateKey;
+use crate::{
+    privatekey::ThresholdPrivateKey,
+    signature::{BatchVerifyError, EcdsaSignature, EcdsaSignatureError, EddsaSignature, EddsaSignatureError},
+};
 
-use generic_ec::{errors::InvalidPoint, Curve, NonZero, Point};
-use std::ops::Mul;
+use generic_ec::{
+    coords::AlwaysHasAffineX,
+    curves::{Ed25519, Secp256k1},
+    errors::InvalidPoint,
+    Curve, NonZero, Point, Scalar,
+};
+use givre::ciphersuite::NormalizedPoint;
+use std::ops::{Add, Mul};
 use thiserror::Error;
 
 #[cfg(feature = "serde")]
@@ -207,6 +216,78 @@ impl<E: Curve> ThresholdPublicKey<E> {
     }
 }
 
+impl ThresholdPublicKey<Ed25519> {
+    /// Verifies an EdDSA signature over a message against this public key.
+    ///
+    /// # Errors
+    ///
+    /// * [`EddsaSignatureError::InvalidPublicKey`] if this public key can't be used for verification.
+    /// * [`EddsaSignatureError::VerificationFailed`] if the signature is not valid for this key and message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use generic_ec::SecretScalar;
+    /// use generic_ec::curves::Ed25519;
+    /// use threshold_keypair::privatekey::ThresholdPrivateKey;
+    /// use rand::rngs::OsRng;
+    ///
+    /// let mut csprng = OsRng;
+    /// let sk = ThresholdPrivateKey::from_scalar(SecretScalar::<Ed25519>::random(&mut csprng)).unwrap();
+    /// let signature = sk.sign(b"message").unwrap();
+    ///
+    /// let pk = sk.public_key();
+    /// assert!(pk.verify(&signature, b"message").is_ok());
+    /// assert!(pk.verify(&signature, b"another message").is_err());
+    /// ```
+    pub fn verify(&self, signature: &EddsaSignature, message: &[u8]) -> Result<(), EddsaSignatureError> {
+        let public_key =
+            NormalizedPoint::try_normalize(*self.as_point()).map_err(|_| EddsaSignatureError::InvalidPublicKey)?;
+        signature.signature.verify(&public_key, message).map_err(|_| EddsaSignatureError::VerificationFailed)
+    }
+}
+
+impl ThresholdPublicKey<Secp256k1> {
+    /// Verifies an ECDSA signature over a 32 byte message digest against this public key.
+    ///
+    /// The digest is interpreted as a big endian integer reduced modulo the curve order, which is how the
+    /// threshold signing protocol treats the digests it signs.
+    ///
+    /// Verification only operates on public values and is therefore not constant time.
+    ///
+    /// # Errors
+    ///
+    /// * [`EcdsaSignatureError::VerificationFailed`] if the signature is not valid for this key and digest.
+    pub fn verify(&self, signature: &EcdsaSignature, digest: &[u8; 32]) -> Result<(), EcdsaSignatureError> {
+        let z = Scalar::<Secp256k1>::from_be_bytes_mod_order(digest);
+        let r = signature.r.into_inner();
+        let s_inverse = signature.s.into_inner().invert().ok_or(EcdsaSignatureError::VerificationFailed)?;
+
+        // R = (z * s^-1) * G + (r * s^-1) * Pk
+        let u1 = z.mul(&s_inverse);
+        let u2 = r.mul(&s_inverse);
+        let point = Point::<Secp256k1>::generator().to_point().mul(&u1).add(self.as_point().mul(&u2));
+        let point = NonZero::from_point(point).ok_or(EcdsaSignatureError::VerificationFailed)?;
+        if point.x().to_scalar() == r { Ok(()) } else { Err(EcdsaSignatureError::VerificationFailed) }
+    }
+
+    /// Verifies a batch of ECDSA signatures over 32 byte message digests against this public key.
+    ///
+    /// Secp256k1 ECDSA signatures don't carry enough information to be verified as a single combined
+    /// equation, so signatures are verified one by one and verification stops at the first failure.
+    /// Like [`ThresholdPublicKey::verify`], this is not constant time.
+    ///
+    /// # Errors
+    ///
+    /// * [`BatchVerifyError`] containing the index of the first signature that failed verification.
+    pub fn verify_batch(&self, items: &[([u8; 32], EcdsaSignature)]) -> Result<(), BatchVerifyError> {
+        for (index, (digest, signature)) in items.iter().enumerate() {
+            self.verify(signature, digest).map_err(|source| BatchVerifyError { index, source })?;
+        }
+        Ok(())
+    }
+}
+
 /// Represents errors that can occur when working with an [`ThresholdPublicKey`].
 #[derive(Error, Debug)]
 pub enum ThresholdPublicKeyError {
@@ -249,9 +330,14 @@ impl From<[u8; 33]> for EcdsaPublicKeyArray {
 #[cfg(test)]
 pub mod tests {
     use super::*;
-    use crate::privatekey::ThresholdPrivateKey;
+    use crate::{
+        privatekey::ThresholdPrivateKey,
+        signature::tests::{generate_signature_and_shares_test, sign_test, verify},
+    };
+    use cggmp21::signing::DataToSign;
     use generic_ec::{Curve, SecretScalar};
     use rand::rngs::OsRng;
+    use sha2::Sha256;
 
     pub fn generate_locally<E: Curve>() -> Option<ThresholdPrivateKey<E>> {
         let mut csprng = OsRng;
@@ -320,6 +406,66 @@ pub mod tests {
         assert_eq!(pk_decoded_compressed, pk);
     }
 
+    /// The 32 byte big endian digest `verify` expects for a message digest.
+    fn digest_bytes(message_digest: &DataToSign<Secp256k1>) -> [u8; 32] {
+        message_digest.to_scalar().to_be_bytes().as_ref().try_into().unwrap()
+    }
+
+    fn ecdsa_sign(sk: &ThresholdPrivateKey<Secp256k1>, byte: u8) -> ([u8; 32], EcdsaSignature) {
+        let message_digest = DataToSign::digest::<Sha256>(&[byte]);
+        (digest_bytes(&message_digest), sign_test(sk.as_non_zero_scalar().as_ref(), &message_digest))
+    }
+
+    #[test]
+    fn test_ecdsa_verify() {
+        let (message_digest, sk, signature, _) = generate_signature_and_shares_test(1);
+        let pk = sk.public_key();
+        let digest = digest_bytes(&message_digest);
+        assert!(pk.verify(&signature, &digest).is_ok());
+        assert!(pk.verify(&signature, &[2; 32]).is_err());
+
+        let (_, other_sk, _, _) = generate_signature_and_shares_test(1);
+        assert!(other_sk.public_key().verify(&signature, &digest).is_err());
+    }
+
+    #[test]
+    fn test_ecdsa_verify_matches_cggmp21() {
+        for _ in 0..10 {
+            let (message_digest, sk, signature, _) = generate_signature_and_shares_test(1);
+            let pk = sk.public_key();
+            let digest = digest_bytes(&message_digest);
+            assert_eq!(pk.verify(&signature, &digest).is_ok(), verify(pk.clone(), signature, &message_digest));
+
+            // The same signature with a high s must get the same outcome from both implementations.
+            let high_s = EcdsaSignature { r: signature.r, s: NonZero::from_scalar(-signature.s.into_inner()).unwrap() };
+            assert_ne!(high_s.s, signature.s);
+            assert_eq!(pk.verify(&high_s, &digest).is_ok(), verify(pk.clone(), high_s, &message_digest));
+
+            let wrong_digest = DataToSign::digest::<Sha256>(b"another message");
+            assert!(pk.verify(&signature, &digest_bytes(&wrong_digest)).is_err());
+            assert!(!verify(pk, signature, &wrong_digest));
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_verify_batch_valid() {
+        let (_, sk, _, _) = generate_signature_and_shares_test(1);
+        let items: Vec<_> = (0..5u8).map(|byte| ecdsa_sign(&sk, byte)).collect();
+        assert!(sk.public_key().verify_batch(&items).is_ok());
+        assert!(sk.public_key().verify_batch(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_ecdsa_verify_batch_reports_failure_index() {
+        let (_, sk, _, _) = generate_signature_and_shares_test(1);
+        let mut items: Vec<_> = (0..5u8).map(|byte| ecdsa_sign(&sk, byte)).collect();
+        // Swap a signature for one over a different digest.
+        items.get_mut(3).unwrap().1 = ecdsa_sign(&sk, 42).1;
+
+        let err = sk.public_key().verify_batch(&items).unwrap_err();
+        assert_eq!(err.index, 3);
+    }
+
     #[test]
     fn test_from_bytes_to_byte_25519() {
         let sk = generate_locally::<generic_ec::curves::Ed25519>().unwrap();
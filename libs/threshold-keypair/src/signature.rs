@@ -169,6 +169,14 @@ pub enum EddsaSignatureError {
     /// Error when a signature component is invalid
     #[error("Invalid signature component: {0}")]
     InvalidComponentSignature(String),
+
+    /// Error when the public key can't be used to verify a signature.
+    #[error("Invalid public key for signature verification")]
+    InvalidPublicKey,
+
+    /// Error when the signature doesn't match the public key and message.
+    #[error("Signature verification failed")]
+    VerificationFailed,
 }
 
 /// Enum representing errors that can occur when handling ECDSA signature.
@@ -177,6 +185,22 @@ pub enum EcdsaSignatureError {
     /// Error during accumulation process for ecdsa signature generation.
     #[error("Error during accumulation process for ecdsa signature generation.")]
     AccumulateShares,
+
+    /// Error when the signature doesn't match the public key and message digest.
+    #[error("Signature verification failed")]
+    VerificationFailed,
+}
+
+/// Error returned when a signature in a batch fails verification.
+#[derive(Error, Debug)]
+#[error("Signature at index {index} failed verification: {source}")]
+pub struct BatchVerifyError {
+    /// The index of the first signature in the batch that failed verification.
+    pub index: usize,
+
+    /// The reason why the signature failed verification.
+    #[source]
+    pub source: EcdsaSignatureError,
 }
 
 #[cfg(test)]
@@ -192,7 +216,7 @@ pub mod tests {
     use givre::ciphersuite::{Ed25519 as Ed25519Ciphersuite, NormalizedPoint};
     use sha2::Sha256;
 
-    fn generate_signature_and_shares_test(
+    pub fn generate_signature_and_shares_test(
         n: u16,
     ) -> (DataToSign<Secp256k1>, ThresholdPrivateKey<Secp256k1>, EcdsaSignature, Vec<EcdsaSignatureShare>) {
         // 1. Message generation
@@ -203,9 +227,19 @@ pub mod tests {
         let mut csprng = OsRng;
         let sk_val = SecretScalar::<Secp256k1>::random(&mut csprng);
 
-        // 3. Generate signature:
+        // 3. Generate signature
+        let signature = sign_test(&sk_val, &message_digest);
+        let sk: ThresholdPrivateKey<Secp256k1> = ThresholdPrivateKey::<Secp256k1>::from_scalar(sk_val).unwrap();
+
+        // 4. Generate shares of signature
+        let signature_shares = signature.generate_shares(n).unwrap();
+
+        (message_digest, sk, signature, signature_shares)
+    }
+
+    pub fn sign_test(sk: &SecretScalar<Secp256k1>, message_digest: &DataToSign<Secp256k1>) -> EcdsaSignature {
         // random scalar k
-        let k = Scalar::<Secp256k1>::random(&mut csprng);
+        let k = Scalar::<Secp256k1>::random(&mut OsRng);
         // random point R = k * G
         let r_point = Point::<Secp256k1>::generator().to_point() * &k;
         // r = R.x()
@@ -213,18 +247,16 @@ pub mod tests {
         let r = r_point.x().to_scalar();
         let r = NonZero::from_scalar(r).unwrap();
         // s = k.invert() * (h + r * sk)
-        let s = (message_digest.to_scalar() + r * sk_val.clone()) * k.invert().unwrap();
+        let s = (message_digest.to_scalar() + r * sk.clone()) * k.invert().unwrap();
         let s = NonZero::from_scalar(s).unwrap();
-        let signature = EcdsaSignature { r, s }.normalize_s();
-        let sk: ThresholdPrivateKey<Secp256k1> = ThresholdPrivateKey::<Secp256k1>::from_scalar(sk_val).unwrap();
-
-        // 4. Generate shares of signature
-        let signature_shares = signature.generate_shares(n).unwrap();
-
-        (message_digest, sk, signature, signature_shares)
+        EcdsaSignature { r, s }.normalize_s()
     }
 
-    fn verify(pk: ThresholdPublicKey<Secp256k1>, signature: EcdsaSignature, message: &DataToSign<Secp256k1>) -> bool {
+    pub fn verify(
+        pk: ThresholdPublicKey<Secp256k1>,
+        signature: EcdsaSignature,
+        message: &DataToSign<Secp256k1>,
+    ) -> bool {
         let EcdsaSignature { r, s } = signature;
         let cggmp_sig = Cggmp21Signature { r, s };
 
@@ -265,4 +297,36 @@ pub mod tests {
         assert_eq!(signature.signature.r.to_bytes(), r_bytes);
         assert_eq!(signature.signature.z.to_le_bytes(), z_bytes);
     }
+
+    #[test]
+    fn test_eddsa_sign_and_verify() {
+        let message = b"Transaction with plenty of solana";
+        let sk = ThresholdPrivateKey::from_scalar(SecretScalar::<Ed25519>::random(&mut OsRng)).unwrap();
+        let signature = sk.sign(message).unwrap();
+
+        // Round trip it through its byte representation too
+        let signature = EddsaSignature::from_bytes(&signature.to_bytes()).unwrap();
+        let pk = ThresholdPublicKey::<Ed25519>::from_private_key(&sk);
+        assert!(pk.verify(&signature, message).is_ok());
+    }
+
+    #[test]
+    fn test_eddsa_verify_tampered() {
+        let message = b"Transaction with plenty of solana";
+        let sk = ThresholdPrivateKey::from_scalar(SecretScalar::<Ed25519>::random(&mut OsRng)).unwrap();
+        let signature = sk.sign(message).unwrap();
+        let pk = sk.public_key();
+
+        // Tampered message
+        assert!(pk.verify(&signature, b"Transaction with plenty of bitcoin").is_err());
+
+        // Tampered signature
+        let mut tampered = signature;
+        tampered.signature.z = tampered.signature.z + Scalar::<Ed25519>::one();
+        assert!(pk.verify(&tampered, message).is_err());
+
+        // Signature from another key
+        let other_sk = ThresholdPrivateKey::from_scalar(SecretScalar::<Ed25519>::random(&mut OsRng)).unwrap();
+        assert!(other_sk.public_key().verify(&signature, message).is_err());
+    }
 }
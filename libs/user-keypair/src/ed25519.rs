@@ -1,6 +1,6 @@
 //! ed25519 keys.
 
-use crate::{InvalidKey, InvalidSignature, Signature};
+use crate::{InvalidKey, InvalidSignature, Signature, SignatureKind};
 use ed25519_dalek::{ed25519::signature::Signer, Digest, SigningKey, Verifier};
 use sha2::Sha256;
 use std::sync::Arc;
@@ -34,7 +34,7 @@ impl Ed25519SigningKey {
 
     /// Sign a message.
     pub fn sign(&self, data: &[u8]) -> Signature {
-        Signature(self.signing_key.sign(data).to_vec())
+        Signature::new(SignatureKind::Ed25519, self.signing_key.sign(data).to_vec())
     }
 
     /// Gets the public key.
@@ -72,7 +72,10 @@ impl Ed25519PublicKey {
 
     /// Verify a signature.
     pub fn verify(&self, signature: &Signature, data: &[u8]) -> Result<(), InvalidSignature> {
-        let signature = ed25519_dalek::Signature::from_slice(&signature.0).map_err(|_| InvalidSignature)?;
+        if signature.kind != SignatureKind::Ed25519 {
+            return Err(InvalidSignature);
+        }
+        let signature = ed25519_dalek::Signature::from_slice(&signature.bytes).map_err(|_| InvalidSignature)?;
         self.0.verify(data, &signature).map_err(|_| InvalidSignature)?;
         Ok(())
     }
@@ -118,7 +121,7 @@ mod tests {
     #[test]
     fn verify_different_signature_fails() {
         let (payload, key, mut signature) = make_signature();
-        signature.0[0] = signature.0[0].wrapping_add(1);
+        signature.bytes[0] = signature.bytes[0].wrapping_add(1);
         key.public_key().verify(&signature, payload).expect_err("verification didn't fail");
     }
 
@@ -134,7 +137,7 @@ mod tests {
         let key = Ed25519SigningKey::from_seed("test");
         let signature = key.sign(&[1]);
         assert_eq!(
-            signature.0,
+            signature.bytes,
             &[
                 12, 101, 115, 14, 187, 31, 66, 50, 107, 78, 139, 17, 70, 106, 146, 136, 233, 33, 233, 200, 141, 121,
                 185, 35, 165, 112, 59, 178, 41, 234, 216, 253, 215, 94, 101, 234, 151, 121, 25, 68, 96, 125, 94, 37,
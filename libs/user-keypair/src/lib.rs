@@ -21,20 +21,37 @@ use secp256k1::{Secp256k1PublicKey, Secp256k1SigningKey};
 pub mod ed25519;
 pub mod secp256k1;
 
-/// A signature.
+/// A signature, along with the kind of key that produced it.
 #[derive(Clone, PartialEq)]
-pub struct Signature(Vec<u8>);
+pub struct Signature {
+    /// The kind of key this signature was produced with.
+    pub kind: SignatureKind,
+
+    /// The raw signature bytes.
+    pub bytes: Vec<u8>,
+}
+
+impl Signature {
+    /// Constructs a signature of the given kind from its raw bytes.
+    pub fn new(kind: SignatureKind, bytes: Vec<u8>) -> Self {
+        Self { kind, bytes }
+    }
+}
 
 impl From<Signature> for Vec<u8> {
     fn from(signature: Signature) -> Self {
-        signature.0
+        signature.bytes
     }
 }
 
-impl From<Vec<u8>> for Signature {
-    fn from(signature: Vec<u8>) -> Self {
-        Self(signature)
-    }
+/// The kind of key a signature was produced with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureKind {
+    /// An ed25519 signature.
+    Ed25519,
+
+    /// A secp256k1 ECDSA signature.
+    Secp256k1,
 }
 
 /// A signature was invalid.
@@ -84,6 +101,14 @@ impl SigningKey {
         }
     }
 
+    /// Gets the kind of signatures this key produces.
+    pub fn kind(&self) -> SignatureKind {
+        match self {
+            Self::Ed25519(_) => SignatureKind::Ed25519,
+            Self::Secp256k1(_) => SignatureKind::Secp256k1,
+        }
+    }
+
     /// Gets the signing key's secret bytes.
     ///
     /// This exposes the secret key, use with care.
@@ -119,6 +144,8 @@ pub enum PublicKey {
 
 impl PublicKey {
     /// Verify a signature.
+    ///
+    /// Signatures of a different kind than this key's are rejected without being parsed.
     pub fn verify(&self, signature: &Signature, data: &[u8]) -> Result<(), InvalidSignature> {
         match self {
             Self::Ed25519(key) => key.verify(signature, data),
@@ -126,6 +153,14 @@ impl PublicKey {
         }
     }
 
+    /// Gets the kind of signatures this key verifies.
+    pub fn kind(&self) -> SignatureKind {
+        match self {
+            Self::Ed25519(_) => SignatureKind::Ed25519,
+            Self::Secp256k1(_) => SignatureKind::Secp256k1,
+        }
+    }
+
     /// Get the raw bytes in the underlying key.
     pub fn as_bytes(&self) -> Vec<u8> {
         match self {
@@ -146,3 +181,38 @@ impl From<Secp256k1PublicKey> for PublicKey {
         Self::Secp256k1(key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_other_kind_fails() {
+        let data = b"hi mom";
+        let secp256k1_key = SigningKey::generate_secp256k1();
+        let ed25519_key = SigningKey::generate_ed25519();
+        let signature = secp256k1_key.sign(data);
+        assert_eq!(signature.kind, SignatureKind::Secp256k1);
+
+        ed25519_key.public_key().verify(&signature, data).expect_err("verification didn't fail");
+        secp256k1_key.public_key().verify(&signature, data).expect("verification failed");
+    }
+
+    #[test]
+    fn verify_mislabeled_kind_fails() {
+        let data = b"hi mom";
+        let key = SigningKey::generate_ed25519();
+        // A valid ed25519 signature claiming to be a secp256k1 one.
+        let signature = Signature::new(SignatureKind::Secp256k1, key.sign(data).into());
+        key.public_key().verify(&signature, data).expect_err("verification didn't fail");
+    }
+
+    #[test]
+    fn verify_wrong_length_fails() {
+        let data = b"hi mom";
+        let key = SigningKey::generate_ed25519();
+        let mut signature = key.sign(data);
+        signature.bytes.push(0);
+        key.public_key().verify(&signature, data).expect_err("verification didn't fail");
+    }
+}
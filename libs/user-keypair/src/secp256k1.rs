@@ -1,6 +1,6 @@
 //! secp256k1 keys.
 
-use crate::{InvalidKey, InvalidSignature, Signature};
+use crate::{InvalidKey, InvalidSignature, Signature, SignatureKind};
 use ed25519_dalek::Verifier;
 use k256::ecdsa::{signature::Signer, SigningKey, VerifyingKey};
 use sha2::{Digest, Sha256};
@@ -36,7 +36,7 @@ impl Secp256k1SigningKey {
     /// Sign a message.
     pub fn sign(&self, data: &[u8]) -> Signature {
         let signature: k256::ecdsa::Signature = self.signing_key.sign(data);
-        Signature(signature.to_vec())
+        Signature::new(SignatureKind::Secp256k1, signature.to_vec())
     }
 
     /// Gets the public key.
@@ -74,7 +74,10 @@ impl Secp256k1PublicKey {
 
     /// Verify a signature.
     pub fn verify(&self, signature: &Signature, data: &[u8]) -> Result<(), InvalidSignature> {
-        let signature = k256::ecdsa::Signature::from_slice(&signature.0).map_err(|_| InvalidSignature)?;
+        if signature.kind != SignatureKind::Secp256k1 {
+            return Err(InvalidSignature);
+        }
+        let signature = k256::ecdsa::Signature::from_slice(&signature.bytes).map_err(|_| InvalidSignature)?;
         self.0.verify(data, &signature).map_err(|_| InvalidSignature)?;
         Ok(())
     }
@@ -120,7 +123,7 @@ mod tests {
     #[test]
     fn verify_different_signature_fails() {
         let (payload, key, mut signature) = make_signature();
-        signature.0[0] = signature.0[0].wrapping_add(1);
+        signature.bytes[0] = signature.bytes[0].wrapping_add(1);
         key.public_key().verify(&signature, payload).expect_err("verification didn't fail");
     }
 
@@ -144,7 +147,7 @@ mod tests {
         let key = Secp256k1SigningKey::try_from_seed("test").unwrap();
         let signature = key.sign(&[1]);
         assert_eq!(
-            signature.0,
+            signature.bytes,
             &[
                 22, 156, 180, 160, 31, 25, 179, 99, 9, 52, 138, 203, 25, 173, 189, 253, 2, 163, 23, 137, 45, 20, 202,
                 173, 171, 82, 198, 145, 245, 209, 138, 14, 52, 77, 242, 21, 52, 57, 9, 196, 178, 206, 66, 105, 97, 116,
@@ -18,7 +18,7 @@ const TIMEOUT: u64 = 5000;
 
 /// Compile a program test case
 fn compile_test_case(test_case: TestCase, tmp_dir: PathBuf) -> Result<TestCase, Error> {
-    let options = CompilerOptions { persist: PersistOptions { mir_bin: true, mir_json: false } };
+    let options = CompilerOptions { persist: PersistOptions { mir_bin: true, mir_json: false }, ..Default::default() };
     let mut test_case = test_case;
     let compiler = Compiler::with_options(tmp_dir, options);
     debug!("Compiling {} : {}", test_case.name, test_case.program_path.to_string_lossy());
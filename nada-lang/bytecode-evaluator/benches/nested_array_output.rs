@@ -0,0 +1,50 @@
+//! Measures how long it takes to evaluate programs that output arrays with an increasing number of dimensions.
+//!
+//! Assembling the outputs of these programs used to deep-clone every nested array once per nesting level. To compare
+//! against that, run `cargo bench --bench nested_array_output -- --save-baseline <name>` on the older revision and
+//! then `-- --baseline <name>` on this one.
+
+use bytecode_evaluator::Evaluator;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use jit_compiler::{mir2bytecode::MIR2Bytecode, models::bytecode::ProgramBytecode};
+use math_lib::modular::U64SafePrime;
+use nada_value::{clear::Clear, NadaValue};
+use std::{collections::HashMap, time::Duration};
+use test_programs::PROGRAMS;
+
+const SIZE: i64 = 5;
+
+fn nested_array(depth: usize, next: &mut i64) -> NadaValue<Clear> {
+    if depth == 0 {
+        *next += 1;
+        return NadaValue::new_secret_integer(*next);
+    }
+    let values = (0..SIZE).map(|_| nested_array(depth - 1, next)).collect();
+    NadaValue::new_array_non_empty(values).expect("invalid array")
+}
+
+fn run_nested_array_output_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nested array output");
+    let programs = [
+        ("input_array", "my_integer_array", 1),
+        ("input_2_dimensional_array", "a", 2),
+        ("input_n_dimensional_array", "a", 3),
+    ];
+    for (program, input, depth) in programs {
+        let program_mir = PROGRAMS.mir(program).expect("program not found");
+        let bytecode: ProgramBytecode = MIR2Bytecode::transform(&program_mir).expect("transformation failed");
+        let inputs = HashMap::from([(input.to_string(), nested_array(depth, &mut 0))]);
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &inputs, |b, inputs| {
+            b.iter(|| Evaluator::<U64SafePrime>::run(black_box(&bytecode), inputs.clone()).expect("evaluation failed"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    name = nested_array_output_bench;
+    config = Criterion::default().sample_size(20).measurement_time(Duration::from_secs(2));
+    targets = run_nested_array_output_bench
+);
+
+criterion_main!(nested_array_output_bench);
@@ -18,7 +18,7 @@ use math_lib::{
     conversions::boolean_from_bigint,
     errors::DivByZero,
     impl_boxed_from_encoded_safe_prime,
-    modular::{Modular, ModularNumber, Overflow, Prime, SafePrime},
+    modular::{AsBits, Modular, ModularNumber, Overflow, Prime, SafePrime},
 };
 use nada_compiler_backend::{
     literal_value::LiteralValue,
@@ -73,6 +73,23 @@ pub enum EvaluationError {
     /// Operand is not allowed
     #[error("operand is not allowed: {0}")]
     NotAllowedOperand(&'static str),
+
+    /// The result of an arithmetic operation doesn't fit in the prime, so it wrapped around.
+    #[error("{operation} overflowed: {left} {symbol} {right} = {result} doesn't fit in a {bits} bit prime")]
+    ArithmeticOverflow {
+        /// The operation's name.
+        operation: &'static str,
+        /// The operation's symbol.
+        symbol: &'static str,
+        /// The left operand.
+        left: BigInt,
+        /// The right operand.
+        right: BigInt,
+        /// The result of the operation over the integers.
+        result: BigInt,
+        /// The size of the prime in bits.
+        bits: usize,
+    },
 }
 
 pub(crate) enum BytecodeMemoryElement<T: SafePrime> {
@@ -134,13 +151,70 @@ impl<T: SafePrime> HeapMemory<T> {
     pub(crate) fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// The number of addresses allocated in the heap after the given one.
+    pub(crate) fn allocated_after(&self, address: BytecodeAddress) -> Result<usize, Error> {
+        if address.1 != AddressType::Heap {
+            return Err(anyhow!("address {address:?} is not in the heap"));
+        }
+        self.0
+            .len()
+            .checked_sub(address.0)
+            .and_then(|allocated| allocated.checked_sub(1))
+            .ok_or(anyhow!("address {address} not found in the heap"))
+    }
+}
+
+/// The target of the structured events emitted for every evaluated operation.
+///
+/// Each event contains the operation name, the types of its operands, its output type and the heap index its result
+/// is stored at.
+#[cfg(feature = "tracing")]
+pub const OPERATION_EVENT_TARGET: &str = "bytecode_evaluator::operation";
+
+/// The default maximum number of primitive elements a single input can contain.
+pub const DEFAULT_MAX_INPUT_ELEMENTS: usize = 10_000_000;
+
+/// The default maximum number of primitive elements a single random operation can generate.
+pub const DEFAULT_MAX_RANDOM_ELEMENTS: usize = 10_000_000;
+
+/// The limits enforced while evaluating a program.
+#[derive(Clone, Copy, Debug)]
+pub struct EvaluatorLimits {
+    /// The maximum number of primitive elements a single input can contain.
+    pub max_input_elements: usize,
+    /// The maximum number of primitive elements a single random operation can generate.
+    pub max_random_elements: usize,
+}
+
+impl Default for EvaluatorLimits {
+    fn default() -> Self {
+        Self { max_input_elements: DEFAULT_MAX_INPUT_ELEMENTS, max_random_elements: DEFAULT_MAX_RANDOM_ELEMENTS }
+    }
+}
+
+/// The options used when evaluating a program.
+#[derive(Clone, Debug, Default)]
+pub struct EvaluatorOptions {
+    /// The limits enforced while evaluating the program.
+    pub limits: EvaluatorLimits,
+    /// The values that replace some of the program's literals, keyed by literal name.
+    ///
+    /// Every override must be named after one of the program's literals and have the same type as it.
+    pub literal_overrides: HashMap<String, NadaValue<Clear>>,
+    /// Fail with [EvaluationError::ArithmeticOverflow] when the result of an addition, subtraction or
+    /// multiplication doesn't fit in the prime, instead of letting it wrap around.
+    pub detect_overflow: bool,
 }
 
 pub struct Evaluator<T: SafePrime> {
     inputs: Vec<NadaValue<ClearModular<T>>>,
+    max_input_elements: usize,
+    max_random_elements: usize,
+    detect_overflow: bool,
     literals: HashMap<String, NadaValue<ClearModular<T>>>,
     heap: HeapMemory<T>,
-    outputs: Vec<NadaValue<ClearModular<T>>>,
+    outputs: Vec<BytecodeMemoryElement<T>>,
     _unused: PhantomData<T>,
 }
 
@@ -148,6 +222,9 @@ impl<T: SafePrime> Default for Evaluator<T> {
     fn default() -> Self {
         Self {
             inputs: Vec::new(),
+            max_input_elements: DEFAULT_MAX_INPUT_ELEMENTS,
+            max_random_elements: DEFAULT_MAX_RANDOM_ELEMENTS,
+            detect_overflow: false,
             literals: HashMap::new(),
             heap: HeapMemory::new(),
             outputs: Vec::new(),
@@ -160,12 +237,55 @@ impl<T: SafePrime> Evaluator<T> {
     pub fn run(
         bytecode: &ProgramBytecode,
         inputs: HashMap<String, NadaValue<Clear>>,
+    ) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
+        Self::run_with(bytecode, inputs, EvaluatorOptions::default())
+    }
+
+    /// Runs a program rejecting any input that contains more than `max_input_elements` primitive elements.
+    pub fn run_with_max_input_elements(
+        bytecode: &ProgramBytecode,
+        inputs: HashMap<String, NadaValue<Clear>>,
+        max_input_elements: usize,
+    ) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
+        let limits = EvaluatorLimits { max_input_elements, ..Default::default() };
+        Self::run_with(bytecode, inputs, EvaluatorOptions { limits, ..Default::default() })
+    }
+
+    /// Runs a program enforcing the given limits.
+    pub fn run_with_limits(
+        bytecode: &ProgramBytecode,
+        inputs: HashMap<String, NadaValue<Clear>>,
+        limits: EvaluatorLimits,
+    ) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
+        Self::run_with(bytecode, inputs, EvaluatorOptions { limits, ..Default::default() })
+    }
+
+    /// Runs a program replacing the values of some of its literals.
+    ///
+    /// Every override must be named after one of the program's literals and have the same type as it.
+    pub fn run_with_literals(
+        bytecode: &ProgramBytecode,
+        inputs: HashMap<String, NadaValue<Clear>>,
+        literal_overrides: HashMap<String, NadaValue<Clear>>,
+    ) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
+        Self::run_with(bytecode, inputs, EvaluatorOptions { literal_overrides, ..Default::default() })
+    }
+
+    /// Runs a program using the given options.
+    pub fn run_with(
+        bytecode: &ProgramBytecode,
+        inputs: HashMap<String, NadaValue<Clear>>,
+        options: EvaluatorOptions,
     ) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
         info!("{}", bytecode.header_text_repr());
 
-        let mut evaluator: Evaluator<T> = Evaluator::default();
+        let EvaluatorOptions { limits, literal_overrides, detect_overflow } = options;
+        let EvaluatorLimits { max_input_elements, max_random_elements } = limits;
+        let mut evaluator: Evaluator<T> =
+            Evaluator { max_input_elements, max_random_elements, detect_overflow, ..Default::default() };
         info!("\nLoading Literals:");
         evaluator.store_literals(bytecode)?;
+        evaluator.override_literals(bytecode, literal_overrides)?;
         info!("\nLoading Inputs:");
         evaluator.store_inputs(bytecode, inputs)?;
         info!("\nComputing:");
@@ -187,19 +307,20 @@ impl<T: SafePrime> Evaluator<T> {
         } else {
             return Ok(outputs); // The bytecode doesn't expect outputs
         };
-        #[allow(clippy::type_complexity)]
-        let mut compound_elements: Vec<(NadaValue<ClearModular<T>>, Vec<NadaValue<Clear>>)> = vec![];
+        let mut compound_elements: Vec<(NadaType, Vec<NadaValue<Clear>>)> = vec![];
         for element in self.outputs.into_iter() {
-            let ty = element.to_type();
-            if ty.is_array() || ty.is_tuple() {
-                // If the element is a compound type, then we have to add to into compound_elements,
-                // because we have built its inner_elements.
-                compound_elements.push((element, vec![]));
-                continue;
-            }
+            let element = match element {
+                BytecodeMemoryElement::Header(ty) => {
+                    // If the element is a compound type, then we have to add to into compound_elements,
+                    // because we have built its inner_elements.
+                    compound_elements.push((ty, vec![]));
+                    continue;
+                }
+                BytecodeMemoryElement::Value(value) => value,
+            };
 
             // From here, the element doesn't contain any element and it is completed.
-            let mut element = Some(memory_element_into_output(&element, vec![])?);
+            let mut element = Some(primitive_into_output(&element)?);
             // This while checks if an element is an output or an inner element.
             while let Some(inner_element) = element {
                 if let Some((compound_element, mut inner_elements)) = compound_elements.pop() {
@@ -208,16 +329,18 @@ impl<T: SafePrime> Evaluator<T> {
                     // in the top of compound_elements.
                     inner_elements.push(inner_element);
 
-                    match output.ty() {
+                    // The compound element's own header says when it's complete, the output's type only
+                    // describes the outermost element.
+                    match &compound_element {
                         NadaType::Array { size, .. } if *size == inner_elements.len() => {
                             // If compound_element is completed, we have to iterate and check if it is an output
                             // or it's an inner_element.
-                            element = Some(memory_element_into_output(&compound_element, inner_elements)?);
+                            element = Some(compound_into_output(&compound_element, inner_elements)?);
                         }
                         NadaType::Tuple { .. } if inner_elements.len() == 2 => {
                             // If compound_element is completed, we have to iterate and check if it is an output
                             // or it's an inner_element.
-                            element = Some(memory_element_into_output(&compound_element, inner_elements)?);
+                            element = Some(compound_into_output(&compound_element, inner_elements)?);
                         }
                         _ => {
                             // If the compound_element isn't completed, we'll continue getting elements
@@ -241,7 +364,8 @@ impl<T: SafePrime> Evaluator<T> {
                 }
             }
         }
-        Ok(outputs)
+        // The output memory is exhausted but there are outputs that weren't filled.
+        Err(anyhow!("output \"{}\" not found in the output memory", output.name))
     }
 
     fn store_literals(&mut self, bytecode: &ProgramBytecode) -> Result<(), Error> {
@@ -253,6 +377,27 @@ impl<T: SafePrime> Evaluator<T> {
         Ok(())
     }
 
+    fn override_literals(
+        &mut self,
+        bytecode: &ProgramBytecode,
+        overrides: HashMap<String, NadaValue<Clear>>,
+    ) -> Result<(), Error> {
+        for (name, value) in overrides {
+            let literal = bytecode
+                .literals()
+                .find(|literal| literal.name == name)
+                .ok_or_else(|| anyhow!("literal {name} not found in the program"))?;
+            let value_type = value.to_type();
+            if value_type != literal.ty {
+                return Err(anyhow!("literal {name} has type {}, override has type {value_type}", literal.ty));
+            }
+            let memory_element: NadaValue<ClearModular<T>> = value.try_into_modular_named(&name)?;
+            info!("override {name}\n  {memory_element:?}");
+            self.literals.insert(name, memory_element);
+        }
+        Ok(())
+    }
+
     fn store_inputs(
         &mut self,
         bytecode: &ProgramBytecode,
@@ -263,8 +408,16 @@ impl<T: SafePrime> Evaluator<T> {
             let input_name = bytecode_input.name();
             // Read inputs
             let input = inputs.remove(input_name).ok_or(anyhow!("program requires an input {input_name} not found"))?;
+            // Reject oversized inputs before they are converted and flattened into the input memory.
+            let elements = input.to_type().primitive_elements_count();
+            if elements > self.max_input_elements {
+                return Err(anyhow!(
+                    "input \"{input_name}\" has {elements} elements, maximum allowed is {}",
+                    self.max_input_elements
+                ));
+            }
             Self::input_typecheck(bytecode_input, &input.to_type())?;
-            let input: NadaValue<ClearModular<T>> = input.try_into()?;
+            let input: NadaValue<ClearModular<T>> = input.try_into_modular_named(input_name)?;
             self.inputs.extend(input.flatten_inner_values());
         }
 
@@ -279,11 +432,10 @@ impl<T: SafePrime> Evaluator<T> {
     fn input_typecheck(bytecode_input: &Input, provided_input_type: &NadaType) -> Result<(), Error> {
         let bytecode_input_type = &bytecode_input.ty;
 
-        if provided_input_type != bytecode_input_type {
-            return Err(anyhow!(
-                "type mismatch for input \"{}\": was {provided_input_type}, expected {bytecode_input_type}",
-                bytecode_input.name
-            ));
+        let mismatches = bytecode_input_type.diff(provided_input_type);
+        if !mismatches.is_empty() {
+            let mismatches: Vec<_> = mismatches.iter().map(ToString::to_string).collect();
+            return Err(anyhow!("type mismatch for input \"{}\": {}", bytecode_input.name, mismatches.join("; ")));
         }
         Ok(())
     }
@@ -292,7 +444,11 @@ impl<T: SafePrime> Evaluator<T> {
     fn allocated_element_value(&self, address: BytecodeAddress) -> Result<&NadaValue<ClearModular<T>>, Error> {
         let allocated_element = match address.1 {
             AddressType::Input => self.inputs.get(address.0),
-            AddressType::Output => self.outputs.get(address.0),
+            AddressType::Output => match self.outputs.get(address.0) {
+                Some(BytecodeMemoryElement::Value(value)) => Some(value),
+                Some(BytecodeMemoryElement::Header(_)) => Err(anyhow!("tried to access a non-value memory element"))?,
+                None => None,
+            },
             AddressType::Heap => Some(self.heap.get_value(address)?),
             AddressType::Literals => Err(anyhow!("support for literals memory address is not implemented"))?,
         };
@@ -313,23 +469,25 @@ impl<T: SafePrime> Evaluator<T> {
             // This list will help us track and build all the compound elements iteratively
             // Since we store new arrays with empty elements we need to void the inner elements
             use NadaType::*;
+            // The inner elements must be within the heap region that's actually been allocated.
+            let bound = self.heap.allocated_after(address)?;
             match ty {
                 Array { inner_type, size } => {
                     let mut values = vec![];
                     for i in 1..=size {
-                        let inner_element_address = address.advance(i)?;
+                        let inner_element_address = address.advance_checked(i, bound)?;
                         values.push(self.read_memory_element(inner_element_address)?);
                     }
                     Ok(NadaValue::new_array(*inner_type, values)?)
                 }
                 Tuple { .. } => Ok(NadaValue::new_tuple(
-                    self.read_memory_element(address.advance(1)?)?,
-                    self.read_memory_element(address.advance(2)?)?,
+                    self.read_memory_element(address.advance_checked(1, bound)?)?,
+                    self.read_memory_element(address.advance_checked(2, bound)?)?,
                 )?),
                 NTuple { types } => {
                     let mut values = vec![];
                     for i in 1..=types.len() {
-                        let inner_element_address = address.advance(i)?;
+                        let inner_element_address = address.advance_checked(i, bound)?;
                         values.push(self.read_memory_element(inner_element_address)?);
                     }
                     Ok(NadaValue::new_n_tuple(values)?)
@@ -337,7 +495,7 @@ impl<T: SafePrime> Evaluator<T> {
                 Object { types } => {
                     let mut values = vec![];
                     for i in 1..=types.len() {
-                        let inner_element_address = address.advance(i)?;
+                        let inner_element_address = address.advance_checked(i, bound)?;
                         values.push(self.read_memory_element(inner_element_address)?);
                     }
                     Ok(NadaValue::new_object(types.keys().cloned().zip(values.into_iter()).collect())?)
@@ -444,21 +602,31 @@ impl<T: SafePrime> Evaluator<T> {
                 Operation::IfElse(IfElse { first, second, third, .. }) => {
                     self.run_ternary_operation(*first, *second, *third, operation_text_repr, IfElseOperation)?;
                 }
-                Operation::Random(Random { ty, address, .. }) => match ty {
-                    NadaType::SecretInteger | NadaType::SecretUnsignedInteger => {
-                        let value = ModularNumber::gen_random();
-                        let result = NadaValue::from_iter(Some(value), ty.clone())?;
-                        debug!("[Heap {}] new random [Input {}]", self.heap.len() + 1, address.0);
-                        self.heap.push_value(result)?;
+                Operation::Random(Random { ty, address, .. }) => {
+                    // Reject oversized random values before generating any of their elements.
+                    let elements = ty.primitive_elements_count();
+                    if elements > self.max_random_elements {
+                        return Err(anyhow!(
+                            "random value has {elements} elements, maximum allowed is {}",
+                            self.max_random_elements
+                        ));
                     }
-                    NadaType::SecretBoolean => {
-                        let value = (ModularNumber::gen_random() % &ModularNumber::two())?;
-                        let result = NadaValue::from_iter(Some(value), ty.clone())?;
-                        debug!("[Heap {}] new random [Input {}]", self.heap.len() + 1, address.0);
-                        self.heap.push_value(result)?;
+                    match ty {
+                        NadaType::SecretInteger | NadaType::SecretUnsignedInteger => {
+                            let value = ModularNumber::gen_random();
+                            let result = NadaValue::from_iter(Some(value), ty.clone())?;
+                            debug!("[Heap {}] new random [Input {}]", self.heap.len() + 1, address.0);
+                            self.heap.push_value(result)?;
+                        }
+                        NadaType::SecretBoolean => {
+                            let value = (ModularNumber::gen_random() % &ModularNumber::two())?;
+                            let result = NadaValue::from_iter(Some(value), ty.clone())?;
+                            debug!("[Heap {}] new random [Input {}]", self.heap.len() + 1, address.0);
+                            self.heap.push_value(result)?;
+                        }
+                        _ => Err(anyhow!("unsupported type for random operation: {:?}", ty))?,
                     }
-                    _ => Err(anyhow!("unsupported type for random operation: {:?}", ty))?,
-                },
+                }
                 Operation::Reveal(Reveal { operand, .. }) => {
                     self.run_unary_operation(*operand, operation_text_repr, RevealOperation)?;
                 }
@@ -477,21 +645,23 @@ impl<T: SafePrime> Evaluator<T> {
             }
         }
 
-        // We load the memory elements from the heap to the program's output memory
+        // We load the memory elements from the heap to the program's output memory. Compound elements are
+        // copied as headers: their inner elements follow them in memory, so only primitive values are cloned.
         for output in bytecode.outputs() {
-            let output_result = self.read_memory_element(output.inner)?.clone();
-            self.outputs.push(output_result);
-            // If the output is an array, we have to move the array content to the output memory
-            for memory_offset in 1..address_count(&output.ty)? {
-                // We have to ignore the first memory position because is the new that we have just move above.
-                // We add the array offset (the address of the 'new' operation) to get the address
-                // of the array's element.
+            for memory_offset in 0..address_count(&output.ty)? {
+                // We add the output offset (the address of the 'new' operation for compound outputs) to get the
+                // address of each of the output's elements.
                 let memory_offset = BytecodeAddress::new(
                     memory_offset + output.inner.0,
                     AddressType::Heap, // The outputs are always load from the heap.
                 );
-                let allocated_element = self.allocated_element_value(memory_offset)?.clone();
-                self.outputs.push(allocated_element);
+                let ty = self.heap.get_type(memory_offset)?;
+                let element = if ty.is_primitive() {
+                    BytecodeMemoryElement::Value(clone_output_value(self.heap.get_value(memory_offset)?))
+                } else {
+                    BytecodeMemoryElement::Header(ty)
+                };
+                self.outputs.push(element);
             }
         }
         Ok(())
@@ -510,6 +680,16 @@ impl<T: SafePrime> Evaluator<T> {
         let second_hs = self.heap.get_value(second)?;
         let third_hs = self.heap.get_value(third)?;
         let operation_type = operation.output_type(first_address, second_hs, third_hs)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: OPERATION_EVENT_TARGET,
+            operation = operation.display_info().name,
+            first_type = %first_address.to_type(),
+            second_type = %second_hs.to_type(),
+            third_type = %third_hs.to_type(),
+            output_type = %operation_type,
+            heap_index = self.heap.len(),
+        );
         let value = operation.execute(first_address.clone(), second_hs.clone(), third_hs.clone())?;
         let result = NadaValue::from_iter(Some(value), operation_type)?;
         info!("{operation_text_repr}\n  {result:?} = {symbol} {first:?} {second:?} {third_hs:?}");
@@ -535,7 +715,19 @@ impl<T: SafePrime> Evaluator<T> {
             rhs.to_type(),
             operation_type
         );
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: OPERATION_EVENT_TARGET,
+            operation = operation.display_info().name,
+            left_type = %lhs.to_type(),
+            right_type = %rhs.to_type(),
+            output_type = %operation_type,
+            heap_index = self.heap.len(),
+        );
         let value = operation.execute(lhs.clone(), rhs.clone())?;
+        if self.detect_overflow {
+            check_overflow(&operation, &lhs, &rhs, &value, &operation_type)?;
+        }
         let result = NadaValue::from_iter(Some(value), operation_type)?;
         info!("{operation_text_repr}\n  {result:?} = {lhs:?} {symbol} {rhs:?}");
         self.heap.push_value(result)?;
@@ -551,6 +743,14 @@ impl<T: SafePrime> Evaluator<T> {
         let symbol = operation.display_info().symbol;
         let operand = self.allocated_element_value(operand_address)?;
         let operation_type = operation.output_type(operand)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: OPERATION_EVENT_TARGET,
+            operation = operation.display_info().name,
+            operand_type = %operand.to_type(),
+            output_type = %operation_type,
+            heap_index = self.heap.len(),
+        );
         let value = operation.execute(operand.clone())?;
         let result = NadaValue::from_iter(Some(value), operation_type)?;
         info!("{operation_text_repr}\n  {result:?} = {operand:?} {symbol}");
@@ -574,10 +774,14 @@ pub(crate) fn memory_element_from_literal<T: Modular>(
     }
 }
 
-fn memory_element_into_output<T: Prime>(
-    memory_element: &NadaValue<ClearModular<T>>,
-    content: Vec<NadaValue<Clear>>,
-) -> Result<NadaValue<Clear>, Error> {
+/// Clones a primitive heap value into the output memory.
+fn clone_output_value<T: Prime>(value: &NadaValue<ClearModular<T>>) -> NadaValue<ClearModular<T>> {
+    #[cfg(test)]
+    tests::CLONED_OUTPUT_VALUES.with(|count| count.set(count.get().saturating_add(1)));
+    value.clone()
+}
+
+fn primitive_into_output<T: Prime>(memory_element: &NadaValue<ClearModular<T>>) -> Result<NadaValue<Clear>, Error> {
     match memory_element {
         NadaValue::SecretInteger(value) => Ok(NadaValue::new_secret_integer(value)),
         NadaValue::SecretUnsignedInteger(value) => Ok(NadaValue::new_secret_unsigned_integer(value)),
@@ -591,19 +795,51 @@ fn memory_element_into_output<T: Prime>(
             let value = BigInt::from(value);
             Ok(NadaValue::new_boolean(boolean_from_bigint(value)?))
         }
-        NadaValue::Array { inner_type, .. } => {
-            let metadata: NadaTypeMetadata = inner_type.into();
+        memory_element => Err(anyhow!("type is not supported: {}", memory_element.to_type())),
+    }
+}
+
+/// Checks that the result of a binary operation matches the one it'd have over the integers.
+fn check_overflow<T: Prime>(
+    operation: &impl BinaryOperation,
+    lhs: &NadaValue<ClearModular<T>>,
+    rhs: &NadaValue<ClearModular<T>>,
+    value: &ModularNumber<T>,
+    ty: &NadaType,
+) -> Result<(), EvaluationError> {
+    let to_integer: fn(&ModularNumber<T>) -> BigInt = match ty {
+        NadaType::Integer | NadaType::SecretInteger => BigInt::from,
+        NadaType::UnsignedInteger | NadaType::SecretUnsignedInteger => |value| BigUint::from(value).into(),
+        _ => return Ok(()),
+    };
+    let left = to_integer(&ModularNumber::try_from(lhs.clone())?);
+    let right = to_integer(&ModularNumber::try_from(rhs.clone())?);
+    let Some(result) = operation.exact(&left, &right) else {
+        return Ok(());
+    };
+    if result == to_integer(value) {
+        return Ok(());
+    }
+    let OperationDisplay { name, symbol } = operation.display_info();
+    Err(EvaluationError::ArithmeticOverflow { operation: name, symbol, left, right, result, bits: T::MODULO.bits() })
+}
+
+/// Builds a compound output out of its header type and its already built inner elements.
+fn compound_into_output(ty: &NadaType, content: Vec<NadaValue<Clear>>) -> Result<NadaValue<Clear>, Error> {
+    match ty {
+        NadaType::Array { inner_type, .. } => {
+            let metadata: NadaTypeMetadata = inner_type.as_ref().into();
             let metadata = metadata.with_shape(Shape::Secret);
             let inner_type: NadaType = (&metadata).try_into()?;
             Ok(NadaValue::new_array(inner_type, content)?)
         }
-        NadaValue::Tuple { .. } => {
-            if content.len() != 2 {
-                return Err(anyhow!("expected two elements in content, got {}", content.len()));
-            }
-            Ok(NadaValue::new_tuple(content[0].clone(), content[1].clone())?)
+        NadaType::Tuple { .. } => {
+            let Ok([left, right]) = <[NadaValue<Clear>; 2]>::try_from(content) else {
+                return Err(anyhow!("expected two elements in tuple content"));
+            };
+            Ok(NadaValue::new_tuple(left, right)?)
         }
-        memory_element => Err(anyhow!("type is not supported: {}", memory_element.to_type())),
+        ty => Err(anyhow!("type is not supported: {ty}")),
     }
 }
 
@@ -616,6 +852,9 @@ pub trait EvaluatorRunner {
         bytecode: &ProgramBytecode,
         values: HashMap<String, NadaValue<Clear>>,
     ) -> Result<HashMap<String, NadaValue<Clear>>, Error>;
+
+    /// The number of bits in the prime this runner uses.
+    fn prime_bits(&self) -> u32;
 }
 
 impl<T: SafePrime> EvaluatorRunner for PrimeRunner<T> {
@@ -626,6 +865,10 @@ impl<T: SafePrime> EvaluatorRunner for PrimeRunner<T> {
     ) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
         Evaluator::<T>::run(bytecode, values)
     }
+
+    fn prime_bits(&self) -> u32 {
+        T::MODULO.bits() as u32
+    }
 }
 
 impl_boxed_from_encoded_safe_prime!(PrimeRunner, EvaluatorRunner);
@@ -210,6 +210,13 @@ pub(crate) trait BinaryOperation {
         lhs: NadaValue<ClearModular<T>>,
         rhs: NadaValue<ClearModular<T>>,
     ) -> Result<ModularNumber<T>, EvaluationError>;
+
+    /// The result of this operation over the integers, used to detect results that wrap around the prime.
+    ///
+    /// Operations whose results can't overflow don't need to implement this.
+    fn exact(&self, _lhs: &BigInt, _rhs: &BigInt) -> Option<BigInt> {
+        None
+    }
 }
 
 pub(crate) struct AddOperation;
@@ -236,6 +243,10 @@ impl BinaryOperation for AddOperation {
         let right: ModularNumber<T> = rhs.try_into()?;
         Ok(left + &right)
     }
+
+    fn exact(&self, lhs: &BigInt, rhs: &BigInt) -> Option<BigInt> {
+        Some(lhs + rhs)
+    }
 }
 
 pub(crate) struct SubOperation;
@@ -262,6 +273,10 @@ impl BinaryOperation for SubOperation {
         let right: ModularNumber<T> = rhs.try_into()?;
         Ok(left - &right)
     }
+
+    fn exact(&self, lhs: &BigInt, rhs: &BigInt) -> Option<BigInt> {
+        Some(lhs - rhs)
+    }
 }
 
 pub(crate) struct MulOperation;
@@ -288,6 +303,10 @@ impl BinaryOperation for MulOperation {
         let right: ModularNumber<T> = rhs.try_into()?;
         Ok(left * &right)
     }
+
+    fn exact(&self, lhs: &BigInt, rhs: &BigInt) -> Option<BigInt> {
+        Some(lhs * rhs)
+    }
 }
 
 pub(crate) struct ModuloOperation;
@@ -1,19 +1,24 @@
 //! The bytecode evaluator tests
-use crate::Evaluator;
+use crate::{EvaluationError, Evaluator, EvaluatorLimits, EvaluatorOptions, EvaluatorRunner};
 use anyhow::{Error, Result};
 use jit_compiler::{
     mir2bytecode::MIR2Bytecode,
     models::bytecode::{memory::BytecodeAddress, ProgramBytecode},
 };
-use math_lib::modular::{ModularNumber, U64SafePrime};
+use math_lib::modular::{EncodedModulo, ModularNumber, U64SafePrime};
 use nada_value::{clear::Clear, NadaType, NadaValue};
 use rstest::rstest;
 use serde_files_utils::json::read_json;
-use std::{collections::HashMap, env::current_dir};
+use std::{cell::Cell, collections::HashMap, env::current_dir};
 use test_programs::PROGRAMS;
 
 type Prime = U64SafePrime;
 
+thread_local! {
+    /// The number of heap values cloned into the output memory by the current thread.
+    pub(crate) static CLONED_OUTPUT_VALUES: Cell<usize> = const { Cell::new(0) };
+}
+
 fn run_evaluator_pred(
     test_id: &str,
     variables_file_id: &str,
@@ -309,6 +314,138 @@ fn test_invalid_input_types() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_oversized_input_rejected() -> Result<(), Error> {
+    let program_mir = &PROGRAMS.mir("input_2_dimensional_array").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inner_type = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: 5 };
+    let rows = (0..5)
+        .map(|_| NadaValue::new_array(NadaType::SecretInteger, (0..5).map(NadaValue::new_secret_integer).collect()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let inputs = HashMap::from([("a".to_string(), NadaValue::new_array(inner_type, rows)?)]);
+
+    let mut evaluator = Evaluator::<Prime> { max_input_elements: 24, ..Default::default() };
+    let err = evaluator.store_inputs(&bytecode, inputs).expect_err("oversized input accepted");
+    assert_eq!(err.to_string(), "input \"a\" has 25 elements, maximum allowed is 24");
+    assert!(evaluator.inputs.is_empty());
+    Ok(())
+}
+
+fn random_bytecode(ty: NadaType) -> ProgramBytecode {
+    let mut bytecode = ProgramBytecode::default();
+    let party_id = bytecode.create_new_party("Party1".to_string());
+    let random = bytecode.create_new_random(ty.clone());
+    bytecode.create_new_output("my_output".to_string(), random, ty, party_id).unwrap();
+    bytecode
+}
+
+#[test]
+fn test_oversized_random_rejected() {
+    let ty = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: usize::MAX };
+    let bytecode = random_bytecode(ty);
+    let mut evaluator = Evaluator::<Prime> { max_random_elements: 1_000, ..Default::default() };
+    let err = evaluator.simulate(&bytecode).expect_err("oversized random accepted");
+    assert_eq!(err.to_string(), format!("random value has {} elements, maximum allowed is 1000", usize::MAX));
+}
+
+#[test]
+fn test_random_within_limit() {
+    let bytecode = random_bytecode(NadaType::SecretInteger);
+    let limits = EvaluatorLimits { max_random_elements: 1, ..Default::default() };
+    let outputs = Evaluator::<Prime>::run_with_limits(&bytecode, HashMap::new(), limits).unwrap();
+    assert_eq!(outputs.len(), 1);
+}
+
+fn shift_left_literal_name(bytecode: &ProgramBytecode) -> String {
+    let literal = bytecode.literals().next().expect("no literals");
+    assert_eq!(literal.ty, NadaType::UnsignedInteger);
+    literal.name.clone()
+}
+
+#[test]
+fn test_literal_override() -> Result<(), Error> {
+    let program_mir = &PROGRAMS.mir("shift_left_literal").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inputs = HashMap::from([("my_int1".to_string(), NadaValue::new_secret_integer(32))]);
+    let overrides = HashMap::from([(shift_left_literal_name(&bytecode), NadaValue::new_unsigned_integer(3u32))]);
+
+    // 32 << 3 instead of 32 << 2
+    let outputs = Evaluator::<Prime>::run_with_literals(&bytecode, inputs, overrides)?;
+    assert_eq!(outputs, HashMap::from([("my_output".to_string(), NadaValue::new_secret_integer(256))]));
+    Ok(())
+}
+
+#[test]
+fn test_literal_override_type_mismatch() {
+    let program_mir = &PROGRAMS.mir("shift_left_literal").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inputs = HashMap::from([("my_int1".to_string(), NadaValue::new_secret_integer(32))]);
+    let name = shift_left_literal_name(&bytecode);
+    let overrides = HashMap::from([(name.clone(), NadaValue::new_integer(3))]);
+
+    let options = EvaluatorOptions { literal_overrides: overrides, ..Default::default() };
+    let err = Evaluator::<Prime>::run_with(&bytecode, inputs, options).expect_err("override accepted");
+    assert_eq!(err.to_string(), format!("literal {name} has type UnsignedInteger, override has type Integer"));
+}
+
+#[test]
+fn test_multiplication_overflow() {
+    let program_mir = &PROGRAMS.mir("multiplication_simple").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inputs = HashMap::from([
+        ("my_int1".to_string(), NadaValue::new_secret_integer(1i64 << 40)),
+        ("my_int2".to_string(), NadaValue::new_secret_integer(1i64 << 40)),
+    ]);
+
+    // The product silently wraps around the prime unless overflow detection is enabled.
+    Evaluator::<Prime>::run(&bytecode, inputs.clone()).expect("run failed");
+
+    let options = EvaluatorOptions { detect_overflow: true, ..Default::default() };
+    let err = Evaluator::<Prime>::run_with(&bytecode, inputs, options).expect_err("overflow not detected");
+    let err = err.downcast_ref::<EvaluationError>().expect("not an evaluation error");
+    assert!(matches!(err, EvaluationError::ArithmeticOverflow { operation: "multiplication", .. }));
+    assert_eq!(
+        err.to_string(),
+        "multiplication overflowed: 1099511627776 * 1099511627776 = 1208925819614629174706176 doesn't fit in a 64 bit prime"
+    );
+}
+
+#[test]
+fn test_missing_output_data() -> Result<(), Error> {
+    let program_mir = &PROGRAMS.mir("addition_simple").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inputs = HashMap::from([
+        ("my_int1".to_string(), NadaValue::new_secret_integer(32)),
+        ("my_int2".to_string(), NadaValue::new_secret_integer(81)),
+    ]);
+    let mut evaluator = Evaluator::<Prime>::default();
+    evaluator.store_literals(&bytecode)?;
+    evaluator.store_inputs(&bytecode, inputs)?;
+    evaluator.simulate(&bytecode)?;
+
+    // Drop the output's data to simulate a program that doesn't produce it.
+    evaluator.outputs.clear();
+    let err = evaluator.load_outputs(&bytecode).expect_err("missing output accepted");
+    assert_eq!(err.to_string(), "output \"my_output\" not found in the output memory");
+    Ok(())
+}
+
+#[test]
+fn test_run_with_max_input_elements() -> Result<(), Error> {
+    let program_mir = &PROGRAMS.mir("input_2_dimensional_array").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inner_type = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: 5 };
+    let rows = (0..5)
+        .map(|_| NadaValue::new_array(NadaType::SecretInteger, (0..5).map(NadaValue::new_secret_integer).collect()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let inputs = HashMap::from([("a".to_string(), NadaValue::new_array(inner_type, rows)?)]);
+
+    let err =
+        Evaluator::<Prime>::run_with_max_input_elements(&bytecode, inputs, 24).expect_err("oversized input accepted");
+    assert_eq!(err.to_string(), "input \"a\" has 25 elements, maximum allowed is 24");
+    Ok(())
+}
+
 #[test]
 fn test_read_memory_element_array() -> Result<(), Error> {
     let mut base_dir = current_dir()?;
@@ -343,3 +480,170 @@ fn test_read_memory_element_array() -> Result<(), Error> {
     );
     Ok(())
 }
+
+#[test]
+fn test_read_memory_element_out_of_bounds() -> Result<(), Error> {
+    let mut evaluator: Evaluator<Prime> = Evaluator::<Prime>::default();
+    // An array of 3 elements that only has 2 of them allocated.
+    evaluator.heap.push_header(NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: 3 })?;
+    evaluator.heap.push_value(NadaValue::new_secret_integer(ModularNumber::from_u32(1)))?;
+    evaluator.heap.push_value(NadaValue::new_secret_integer(ModularNumber::from_u32(2)))?;
+
+    let err = evaluator
+        .read_memory_element(BytecodeAddress::new(0, jit_compiler::models::memory::AddressType::Heap))
+        .expect_err("out of bounds read accepted");
+    assert_eq!(err.to_string(), "offset 3 from address addr(0) is out of bounds: region ends at offset 2");
+    Ok(())
+}
+
+#[rstest]
+#[case::u64(EncodedModulo::U64SafePrime, 64)]
+#[case::u128(EncodedModulo::U128SafePrime, 128)]
+#[case::u256(EncodedModulo::U256SafePrime, 256)]
+fn runner_prime_bits(#[case] modulo: EncodedModulo, #[case] bits: u32) {
+    let runner = Box::<dyn EvaluatorRunner>::try_from(&modulo).expect("runner creation failed");
+    assert_eq!(runner.prime_bits(), bits);
+}
+
+#[test]
+fn test_nested_array_output() -> Result<(), Error> {
+    let program_mir = &PROGRAMS.mir("input_2_dimensional_array").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inner_type = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: 5 };
+    let rows = (0..5)
+        .map(|row| {
+            let values = (0..5).map(|column| NadaValue::new_secret_integer(row * 5 + column)).collect();
+            NadaValue::new_array(NadaType::SecretInteger, values)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let input = NadaValue::new_array(inner_type, rows)?;
+    let inputs = HashMap::from([("a".to_string(), input.clone())]);
+
+    let outputs = Evaluator::<Prime>::run(&bytecode, inputs)?;
+    assert_eq!(outputs, HashMap::from([("my_output".to_string(), input)]));
+    Ok(())
+}
+
+#[test]
+fn test_non_square_nested_array_output() -> Result<(), Error> {
+    let program_mir = &PROGRAMS.mir("input_non_square_array").expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inner_type = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size: 3 };
+    let rows = (0..2)
+        .map(|row| {
+            let values = (0..3).map(|column| NadaValue::new_secret_integer(row * 3 + column)).collect();
+            NadaValue::new_array(NadaType::SecretInteger, values)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let input = NadaValue::new_array(inner_type, rows)?;
+    let inputs = HashMap::from([("a".to_string(), input.clone())]);
+
+    let outputs = Evaluator::<Prime>::run(&bytecode, inputs)?;
+    assert_eq!(outputs, HashMap::from([("my_output".to_string(), input)]));
+    Ok(())
+}
+
+#[rstest]
+#[case::one_dimension("input_array", "my_integer_array", 1)]
+#[case::two_dimensions("input_2_dimensional_array", "a", 2)]
+#[case::three_dimensions("input_n_dimensional_array", "a", 3)]
+fn nested_array_output_clones_each_element_once(
+    #[case] program: &str,
+    #[case] input: &str,
+    #[case] depth: u32,
+) -> Result<(), Error> {
+    fn nested_array(depth: u32) -> Result<NadaValue<Clear>, Error> {
+        if depth == 0 {
+            return Ok(NadaValue::new_secret_integer(42));
+        }
+        let values = (0..5).map(|_| nested_array(depth - 1)).collect::<Result<Vec<_>, _>>()?;
+        Ok(NadaValue::new_array_non_empty(values)?)
+    }
+
+    let program_mir = &PROGRAMS.mir(program).expect("program not found");
+    let bytecode: ProgramBytecode = MIR2Bytecode::transform(program_mir).expect("transformation failed");
+    let inputs = HashMap::from([(input.to_string(), nested_array(depth)?)]);
+
+    CLONED_OUTPUT_VALUES.with(|count| count.set(0));
+    Evaluator::<Prime>::run(&bytecode, inputs)?;
+    // Only the primitive elements are cloned, regardless of how deeply they're nested.
+    assert_eq!(CLONED_OUTPUT_VALUES.with(Cell::get), 5_usize.pow(depth));
+    Ok(())
+}
+
+#[test]
+fn test_array_of_tuples_output() -> Result<(), Error> {
+    let tuples = [(1, 2), (2, 3), (3, 4)]
+        .into_iter()
+        .map(|(left, right)| {
+            NadaValue::new_tuple(NadaValue::new_secret_integer(left), NadaValue::new_secret_integer(right))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let expected = NadaValue::new_array_non_empty(tuples)?;
+    run_evaluator("zip_simple", "default", HashMap::from([("my_output".to_string(), expected)]))
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_events {
+    use super::*;
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        Event, Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+    type RecordedEvents = Arc<Mutex<Vec<HashMap<String, String>>>>;
+
+    #[derive(Default)]
+    struct FieldsVisitor(HashMap<String, String>);
+
+    impl Visit for FieldsVisitor {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    struct OperationEventsRecorder(RecordedEvents);
+
+    impl<S: Subscriber> Layer<S> for OperationEventsRecorder {
+        fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+            if event.metadata().target() == crate::OPERATION_EVENT_TARGET {
+                let mut visitor = FieldsVisitor::default();
+                event.record(&mut visitor);
+                self.0.lock().unwrap().push(visitor.0);
+            }
+        }
+    }
+
+    #[test]
+    fn addition_event_fields() {
+        let program_mir = PROGRAMS.mir("addition_simple").expect("program not found");
+        let bytecode = MIR2Bytecode::transform(&program_mir).expect("transformation failed");
+        let inputs = HashMap::from([
+            ("my_int1".to_string(), NadaValue::new_secret_integer(2)),
+            ("my_int2".to_string(), NadaValue::new_secret_integer(3)),
+        ]);
+
+        let events = RecordedEvents::default();
+        let subscriber = tracing_subscriber::registry().with(OperationEventsRecorder(events.clone()));
+        tracing::subscriber::with_default(subscriber, || Evaluator::<Prime>::run(&bytecode, inputs)).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = events.first().unwrap();
+        let field = |name: &str| event.get(name).map(String::as_str);
+        assert_eq!(field("operation"), Some("addition"));
+        assert_eq!(field("left_type"), Some("SecretInteger"));
+        assert_eq!(field("right_type"), Some("SecretInteger"));
+        assert_eq!(field("output_type"), Some("SecretInteger"));
+        assert_eq!(field("heap_index"), Some("2"));
+    }
+}
@@ -1,6 +1,10 @@
 //! This crate implements the Circuit Contract model
 
-use mir_model::{Input as MIRInput, OutputElement, Party, ProgramMIR};
+use indexmap::IndexMap;
+use mir_model::{
+    proto::{ConvertProto, ProtoError},
+    Input as MIRInput, OutputElement, Party, ProgramMIR,
+};
 use nada_value::NadaType;
 use std::collections::{HashMap, HashSet};
 
@@ -65,6 +69,14 @@ impl ProgramContract {
         })
     }
 
+    /// Create a CircuitContract from a protobuf encoded MIR program
+    ///
+    /// This only decodes the program and extracts its inputs, outputs and parties, it doesn't compile it.
+    pub fn from_raw_mir(bytes: &[u8]) -> Result<ProgramContract, ProgramContractError> {
+        let program = ProgramMIR::try_decode(bytes)?;
+        Self::from_program_mir(&program)
+    }
+
     fn build_inputs(program: &ProgramMIR, parties: &[Party]) -> Result<Vec<Input>, ProgramContractError> {
         let mut inputs = Vec::new();
         let inputs_readings = program.count_inputs_readings();
@@ -132,6 +144,27 @@ impl ProgramContract {
         self.group_by_party_name(&self.outputs, |o| o.party)
     }
 
+    /// Returns the names of the program's parties in declaration order.
+    pub fn parties(&self) -> Vec<&str> {
+        self.parties.iter().map(|party| party.name.as_str()).collect()
+    }
+
+    /// Groups the program's input names and types by the name of the party that provides them.
+    ///
+    /// Parties are listed in declaration order and only if they provide at least one input. Inputs keep their
+    /// declaration order within each party.
+    pub fn inputs_by_party(&self) -> IndexMap<String, Vec<(String, NadaType)>> {
+        let mut grouped: IndexMap<String, Vec<(String, NadaType)>> =
+            self.parties.iter().map(|party| (party.name.clone(), Vec::new())).collect();
+        for input in &self.inputs {
+            if let Some((_, inputs)) = grouped.get_index_mut(input.party) {
+                inputs.push((input.name.clone(), input.ty.clone()));
+            }
+        }
+        grouped.retain(|_, inputs| !inputs.is_empty());
+        grouped
+    }
+
     fn collect_parties<'a, T>(
         &'a self,
         items: &'a Vec<T>,
@@ -158,6 +191,30 @@ impl ProgramContract {
         self.collect_parties(&self.outputs, |o| o.party)
     }
 
+    /// Checks that the provided input types match the inputs declared by the program.
+    ///
+    /// Every declared input must be provided with exactly its declared type, and no other inputs can be provided. All
+    /// mismatches are returned at once.
+    pub fn validate_inputs(&self, inputs: &HashMap<String, NadaType>) -> Result<(), Vec<InputTypeError>> {
+        let mut errors = Vec::new();
+        for input in &self.inputs {
+            match inputs.get(&input.name) {
+                None => errors.push(InputTypeError::Missing(input.name.clone())),
+                Some(ty) if ty != &input.ty => errors.push(InputTypeError::TypeMismatch {
+                    name: input.name.clone(),
+                    expected: input.ty.clone(),
+                    actual: ty.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        let declared: HashSet<_> = self.inputs.iter().map(|input| input.name.as_str()).collect();
+        let mut unexpected: Vec<_> = inputs.keys().filter(|name| !declared.contains(name.as_str())).collect();
+        unexpected.sort();
+        errors.extend(unexpected.into_iter().map(|name| InputTypeError::Unexpected(name.clone())));
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
     /// Get the input types
     pub fn input_types(&self) -> HashMap<String, NadaType> {
         self.inputs.iter().map(|input| (input.name.clone(), input.ty.clone())).collect()
@@ -187,4 +244,163 @@ pub enum ProgramContractError {
     /// Literal value error
     #[error("failed parsing a literal value: {0}")]
     LiteralValueParsingFailed(#[from] LiteralValueError),
+    /// The MIR program could not be decoded
+    #[error("failed decoding program: {0}")]
+    Decode(#[from] ProtoError),
+}
+
+/// A mismatch between the provided inputs and the ones declared by a program.
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum InputTypeError {
+    /// A declared input was not provided.
+    #[error("input {0} is missing")]
+    Missing(String),
+    /// An input that is not declared by the program was provided.
+    #[error("input {0} is not declared by the program")]
+    Unexpected(String),
+    /// An input was provided with a different type than the declared one.
+    #[error("input {name} has type {actual}, expected {expected}")]
+    TypeMismatch {
+        /// The input name.
+        name: String,
+        /// The declared type.
+        expected: NadaType,
+        /// The provided type.
+        actual: NadaType,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mir_model::{proto::Message, InputReference, Operation, OperationId, Output as MIROutput, SourceRefIndex};
+
+    fn program_bytes() -> Vec<u8> {
+        let party = |name: &str| Party { name: name.to_string(), source_ref_index: SourceRefIndex::default() };
+        let input_ref_id = OperationId::with_id(0);
+        let mut program = ProgramMIR {
+            parties: vec![party("Dealer"), party("Result")],
+            inputs: vec![MIRInput {
+                ty: NadaType::SecretInteger,
+                party: "Dealer".to_string(),
+                name: "my_int".to_string(),
+                doc: String::new(),
+                source_ref_index: SourceRefIndex::default(),
+            }],
+            outputs: vec![MIROutput {
+                name: "my_output".to_string(),
+                operation_id: input_ref_id,
+                party: "Result".to_string(),
+                ty: NadaType::SecretInteger,
+                source_ref_index: SourceRefIndex::default(),
+            }],
+            ..Default::default()
+        };
+        let input_ref = InputReference {
+            id: input_ref_id,
+            refers_to: "my_int".to_string(),
+            ty: NadaType::SecretInteger,
+            source_ref_index: SourceRefIndex::default(),
+        };
+        program.operations.insert(input_ref_id, Operation::InputReference(input_ref));
+        program.into_proto().encode_to_vec()
+    }
+
+    #[test]
+    fn contract_from_raw_mir() {
+        let contract = ProgramContract::from_raw_mir(&program_bytes()).unwrap();
+        assert_eq!(
+            contract.inputs,
+            vec![Input { name: "my_int".to_string(), party: 0, ty: NadaType::SecretInteger, readings: 1 }]
+        );
+        assert_eq!(
+            contract.outputs,
+            vec![Output { name: "my_output".to_string(), party: 1, ty: NadaType::SecretInteger }]
+        );
+    }
+
+    #[test]
+    fn contract_from_invalid_raw_mir() {
+        let result = ProgramContract::from_raw_mir(&[0xff, 0xff, 0xff]);
+        assert!(matches!(result, Err(ProgramContractError::Decode(_))));
+    }
+
+    fn contract() -> ProgramContract {
+        let input = |name: &str, ty| Input { name: name.to_string(), party: 0, ty, readings: 1 };
+        ProgramContract {
+            inputs: vec![input("a", NadaType::SecretInteger), input("b", NadaType::Boolean)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn inputs_by_party() {
+        let party = |name: &str| Party { name: name.to_string(), source_ref_index: SourceRefIndex::default() };
+        let input = |name: &str, party, ty| Input { name: name.to_string(), party, ty, readings: 1 };
+        let contract = ProgramContract {
+            parties: vec![party("Alice"), party("Bob"), party("Result")],
+            inputs: vec![
+                input("b1", 1, NadaType::SecretInteger),
+                input("a1", 0, NadaType::Integer),
+                input("b0", 1, NadaType::SecretBoolean),
+                input("a0", 0, NadaType::SecretInteger),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(contract.parties(), vec!["Alice", "Bob", "Result"]);
+
+        let grouped = contract.inputs_by_party();
+        let expected = IndexMap::from([
+            (
+                "Alice".to_string(),
+                vec![("a1".to_string(), NadaType::Integer), ("a0".to_string(), NadaType::SecretInteger)],
+            ),
+            (
+                "Bob".to_string(),
+                vec![("b1".to_string(), NadaType::SecretInteger), ("b0".to_string(), NadaType::SecretBoolean)],
+            ),
+        ]);
+        assert_eq!(grouped, expected);
+        assert!(grouped.keys().eq(["Alice", "Bob"]));
+    }
+
+    #[test]
+    fn validate_inputs_ok() {
+        let inputs = HashMap::from([("a".to_string(), NadaType::SecretInteger), ("b".to_string(), NadaType::Boolean)]);
+        assert_eq!(contract().validate_inputs(&inputs), Ok(()));
+    }
+
+    #[test]
+    fn validate_inputs_missing() {
+        let inputs = HashMap::from([("a".to_string(), NadaType::SecretInteger)]);
+        assert_eq!(contract().validate_inputs(&inputs), Err(vec![InputTypeError::Missing("b".to_string())]));
+    }
+
+    #[test]
+    fn validate_inputs_unexpected() {
+        let inputs = HashMap::from([
+            ("a".to_string(), NadaType::SecretInteger),
+            ("b".to_string(), NadaType::Boolean),
+            ("c".to_string(), NadaType::Integer),
+        ]);
+        assert_eq!(contract().validate_inputs(&inputs), Err(vec![InputTypeError::Unexpected("c".to_string())]));
+    }
+
+    #[test]
+    fn validate_inputs_type_mismatch() {
+        let inputs = HashMap::from([("a".to_string(), NadaType::Integer), ("c".to_string(), NadaType::Integer)]);
+        let errors = contract().validate_inputs(&inputs).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                InputTypeError::TypeMismatch {
+                    name: "a".to_string(),
+                    expected: NadaType::SecretInteger,
+                    actual: NadaType::Integer
+                },
+                InputTypeError::Missing("b".to_string()),
+                InputTypeError::Unexpected("c".to_string()),
+            ]
+        );
+    }
 }
@@ -12,7 +12,12 @@ pub mod proto;
 pub use model::*;
 use nada_type::NadaType;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt::Display};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    ops::{Deref, DerefMut},
+};
 pub use utils::MIRProgramMalformed;
 
 /// Binary file extension for MIR model
@@ -268,9 +273,202 @@ pub struct SourceRef {
     pub length: u32,
 }
 
+impl SourceRef {
+    /// Returns the snippet this reference points to in the given source.
+    ///
+    /// The offset and length are byte positions, so `None` is returned if the range is out of bounds or doesn't
+    /// fall on character boundaries.
+    pub fn snippet<'a>(&self, source: &'a str) -> Option<&'a str> {
+        let start = usize::try_from(self.offset).ok()?;
+        let end = start.checked_add(usize::try_from(self.length).ok()?)?;
+        source.get(start..end)
+    }
+
+    /// Returns the snippet this reference points to, looking up its file in the given source files.
+    pub fn snippet_in<'a>(&self, source_files: &'a SourceFiles) -> Option<&'a str> {
+        self.snippet(source_files.get(&self.file)?)
+    }
+}
+
 /// Sources Files contains all used files and the content of them
-pub type SourceFiles = BTreeMap<String, String>;
+///
+/// When serialized, files with identical contents are stored once, see [SourceFiles::intern].
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, Hash, PartialEq, Default)]
+#[serde(into = "InternedSourceFiles", try_from = "InternedSourceFiles")]
+pub struct SourceFiles(BTreeMap<String, String>);
+
+impl SourceFiles {
+    /// Returns an empty set of source files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the content-addressed representation of these files, where each unique file body is stored once and
+    /// referenced by its hash.
+    pub fn intern(&self) -> InternedSourceFiles {
+        let mut interned = InternedSourceFiles::default();
+        for (file, contents) in &self.0 {
+            let hash = Sha256::digest(contents.as_bytes()).iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+            interned.contents.entry(hash.clone()).or_insert_with(|| contents.clone());
+            interned.files.insert(file.clone(), hash);
+        }
+        interned
+    }
+}
+
+impl Deref for SourceFiles {
+    type Target = BTreeMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SourceFiles {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<BTreeMap<String, String>> for SourceFiles {
+    fn from(files: BTreeMap<String, String>) -> Self {
+        Self(files)
+    }
+}
+
+impl<const N: usize> From<[(String, String); N]> for SourceFiles {
+    fn from(files: [(String, String); N]) -> Self {
+        Self(BTreeMap::from(files))
+    }
+}
+
+impl FromIterator<(String, String)> for SourceFiles {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self(BTreeMap::from_iter(iter))
+    }
+}
+
+impl IntoIterator for SourceFiles {
+    type Item = (String, String);
+    type IntoIter = std::collections::btree_map::IntoIter<String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Source files where each unique file body is stored once and referenced by the hex encoded SHA-256 hash of its
+/// contents.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Default)]
+pub struct InternedSourceFiles {
+    /// The hash of the contents of each file, by file name.
+    pub files: BTreeMap<String, String>,
+    /// The unique file bodies, by hash.
+    pub contents: BTreeMap<String, String>,
+}
+
+impl From<SourceFiles> for InternedSourceFiles {
+    fn from(source_files: SourceFiles) -> Self {
+        source_files.intern()
+    }
+}
+
+impl TryFrom<InternedSourceFiles> for SourceFiles {
+    type Error = MissingSourceContents;
+
+    fn try_from(interned: InternedSourceFiles) -> Result<Self, Self::Error> {
+        let InternedSourceFiles { files, contents } = interned;
+        let mut source_files = BTreeMap::new();
+        for (file, hash) in files {
+            let Some(body) = contents.get(&hash) else {
+                return Err(MissingSourceContents { file, hash });
+            };
+            source_files.insert(file, body.clone());
+        }
+        Ok(SourceFiles(source_files))
+    }
+}
+
+/// A file in an [InternedSourceFiles] references contents that aren't present.
+#[derive(Debug, thiserror::Error)]
+#[error("contents for source file {file} with hash {hash} not found")]
+pub struct MissingSourceContents {
+    /// The file name.
+    pub file: String,
+    /// The hash of the missing contents.
+    pub hash: String,
+}
 
 /// Index to a source ref
 #[derive(Deserialize, Debug, Clone, Copy, Serialize, Eq, Hash, PartialEq, Default)]
 pub struct SourceRefIndex(pub u64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{SourceFiles, SourceRef};
+
+    fn source_ref(offset: u32, length: u32) -> SourceRef {
+        SourceRef { file: "main.py".to_string(), lineno: 1, offset, length }
+    }
+
+    #[test]
+    fn snippet_multibyte() {
+        // 'é' takes two bytes, so "naïve" starts at byte 8 and takes 6 bytes.
+        let source = "# café naïve = 1";
+        assert_eq!(source_ref(8, 6).snippet(source), Some("naïve"));
+        assert_eq!(source_ref(0, 0).snippet(source), Some(""));
+
+        let files = SourceFiles::from([("main.py".to_string(), source.to_string())]);
+        assert_eq!(source_ref(8, 6).snippet_in(&files), Some("naïve"));
+    }
+
+    #[test]
+    fn snippet_invalid_range() {
+        let source = "# café naïve = 1";
+        // Ends in the middle of 'é'.
+        assert_eq!(source_ref(2, 4).snippet(source), None);
+        // Out of bounds.
+        assert_eq!(source_ref(10, 100).snippet(source), None);
+        assert_eq!(source_ref(u32::MAX, u32::MAX).snippet(source), None);
+        // Unknown file.
+        assert_eq!(source_ref(0, 1).snippet_in(&SourceFiles::new()), None);
+    }
+
+    #[test]
+    fn intern_deduplicates_contents() {
+        let library = "def helper():\n    return 1\n".to_string();
+        let source_files = SourceFiles::from([
+            ("lib_a.py".to_string(), library.clone()),
+            ("lib_b.py".to_string(), library.clone()),
+            ("main.py".to_string(), "from lib_a import helper\n".to_string()),
+        ]);
+
+        let interned = source_files.intern();
+        assert_eq!(interned.files.len(), 3);
+        assert_eq!(interned.contents.len(), 2);
+        assert_eq!(interned.files.get("lib_a.py"), interned.files.get("lib_b.py"));
+        assert_eq!(interned.contents.values().filter(|contents| **contents == library).count(), 1);
+
+        let restored = SourceFiles::try_from(interned).unwrap();
+        assert_eq!(restored, source_files);
+    }
+
+    #[test]
+    fn interned_serialization() {
+        let library = "x = 1\n".repeat(100);
+        let source_files =
+            SourceFiles::from([("lib_a.py".to_string(), library.clone()), ("lib_b.py".to_string(), library)]);
+
+        let serialized = serde_json::to_string(&source_files).unwrap();
+        let plain = serde_json::to_string(&*source_files).unwrap();
+        assert!(serialized.len() < plain.len());
+        assert_eq!(serde_json::from_str::<SourceFiles>(&serialized).unwrap(), source_files);
+    }
+
+    #[test]
+    fn missing_contents() {
+        let mut interned = SourceFiles::from([("main.py".to_string(), "x".to_string())]).intern();
+        interned.contents.clear();
+        assert!(SourceFiles::try_from(interned).is_err());
+    }
+}
@@ -9,7 +9,7 @@ use nada_type::NadaType;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Display,
 };
 use substring::Substring;
@@ -99,6 +99,111 @@ impl ProgramMIR {
         }
         counters
     }
+
+    /// Returns the names of the inputs that don't contribute to any output, in declaration order.
+    ///
+    /// The operations are walked from the outputs through their operands, including the bodies of the functions they
+    /// call. An input that is only read by operations whose result never reaches an output is reported as unused too.
+    pub fn unused_inputs(&self) -> Vec<String> {
+        let mut used = HashSet::new();
+        let mut visited = HashSet::new();
+        // Operations are scoped by the function they belong to, if any.
+        let mut pending: Vec<(Option<OperationId>, OperationId)> =
+            self.outputs.iter().map(|output| (None, output.operation_id)).collect();
+        while let Some((scope, id)) = pending.pop() {
+            if !visited.insert((scope, id)) {
+                continue;
+            }
+            let operations = match scope {
+                None => &self.operations,
+                Some(function_id) => match self.function(function_id) {
+                    Some(function) => &function.operations,
+                    None => continue,
+                },
+            };
+            let Some(operation) = operations.get(&id) else {
+                continue;
+            };
+            if let Operation::InputReference(input_ref) = operation {
+                used.insert(input_ref.refers_to.as_str());
+            }
+            let (operands, called_function) = Self::dependency_edges(operation);
+            pending.extend(operands.into_iter().map(|operand| (scope, operand)));
+            if let Some(function) = called_function.and_then(|function_id| self.function(function_id)) {
+                pending.push((Some(function.id), function.return_operation_id));
+            }
+        }
+        self.inputs.iter().filter(|input| !used.contains(input.name.as_str())).map(|input| input.name.clone()).collect()
+    }
+
+    /// Checks that every output can be computed.
+    ///
+    /// An output can be computed if its operation exists and so does every operation it transitively depends on,
+    /// including the functions it calls and the inputs it reads. The names of the outputs that can't be computed are
+    /// returned otherwise, in declaration order.
+    pub fn validate_outputs_reachable(&self) -> std::result::Result<(), Vec<String>> {
+        let unreachable: Vec<_> = self
+            .outputs
+            .iter()
+            .filter(|output| !self.is_operation_reachable(output.operation_id))
+            .map(|output| output.name.clone())
+            .collect();
+        if unreachable.is_empty() { Ok(()) } else { Err(unreachable) }
+    }
+
+    fn is_operation_reachable(&self, id: OperationId) -> bool {
+        let mut visited = HashSet::new();
+        // Operations are scoped by the function they belong to, if any.
+        let mut pending: Vec<(Option<OperationId>, OperationId)> = vec![(None, id)];
+        while let Some((scope, id)) = pending.pop() {
+            if !visited.insert((scope, id)) {
+                continue;
+            }
+            let operations = match scope {
+                None => &self.operations,
+                Some(function_id) => match self.function(function_id) {
+                    Some(function) => &function.operations,
+                    None => return false,
+                },
+            };
+            let Some(operation) = operations.get(&id) else {
+                return false;
+            };
+            if let Operation::InputReference(input_ref) = operation {
+                if !self.inputs.iter().any(|input| input.name == input_ref.refers_to) {
+                    return false;
+                }
+            }
+            let (operands, called_function) = Self::dependency_edges(operation);
+            pending.extend(operands.into_iter().map(|operand| (scope, operand)));
+            if let Some(function_id) = called_function {
+                let Some(function) = self.function(function_id) else {
+                    return false;
+                };
+                pending.push((Some(function.id), function.return_operation_id));
+            }
+        }
+        true
+    }
+
+    /// Returns the operations an operation reads from within its own scope, along with the function it calls, if any.
+    fn dependency_edges(operation: &Operation) -> (Vec<OperationId>, Option<OperationId>) {
+        let mut operands = operation.incoming_operations();
+        let called_function = match operation {
+            Operation::NadaFunctionCall(call) => {
+                operands.extend(call.args.iter().copied());
+                Some(call.function_id)
+            }
+            Operation::Map(map) => Some(map.function_id),
+            Operation::Reduce(reduce) => {
+                operands.push(reduce.initial);
+                Some(reduce.function_id)
+            }
+            _ => None,
+        };
+        (operands, called_function)
+    }
+
     pub fn source_info(&self, source_ref_index: SourceRefIndex) -> String {
         let Ok(src_ref) = self.source_ref(source_ref_index) else {
             return "".to_string();
@@ -745,3 +850,67 @@ impl NamedElement for Operation {
         delegate_to_inner!(self, name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str) -> Input {
+        Input {
+            ty: NadaType::SecretInteger,
+            party: "Party1".to_string(),
+            name: name.to_string(),
+            doc: String::new(),
+            source_ref_index: SourceRefIndex::default(),
+        }
+    }
+
+    fn input_reference(id: i64, name: &str) -> Operation {
+        Operation::InputReference(InputReference {
+            id: OperationId::with_id(id),
+            refers_to: name.to_string(),
+            ty: NadaType::SecretInteger,
+            source_ref_index: SourceRefIndex::default(),
+        })
+    }
+
+    fn output(name: &str, operation_id: i64) -> Output {
+        Output {
+            name: name.to_string(),
+            operation_id: OperationId::with_id(operation_id),
+            party: "Party1".to_string(),
+            ty: NadaType::SecretInteger,
+            source_ref_index: SourceRefIndex::default(),
+        }
+    }
+
+    #[test]
+    fn unused_inputs() {
+        let output = output("my_output", 0);
+        let mut program = ProgramMIR {
+            inputs: vec![input("used"), input("discarded"), input("unread")],
+            outputs: vec![output],
+            ..Default::default()
+        };
+        // "discarded" is read, but the result of that read never reaches an output.
+        for (id, name) in [(0, "used"), (1, "discarded")] {
+            program.operations.insert(OperationId::with_id(id), input_reference(id, name));
+        }
+        assert_eq!(program.unused_inputs(), vec!["discarded".to_string(), "unread".to_string()]);
+    }
+
+    #[test]
+    fn outputs_reachable() {
+        let mut program = ProgramMIR {
+            inputs: vec![input("my_int")],
+            outputs: vec![output("live", 0), output("dead", 5), output("undeclared_input", 1)],
+            ..Default::default()
+        };
+        program.operations.insert(OperationId::with_id(0), input_reference(0, "my_int"));
+        program.operations.insert(OperationId::with_id(1), input_reference(1, "missing"));
+        assert_eq!(program.validate_outputs_reachable(), Err(vec!["dead".to_string(), "undeclared_input".to_string()]));
+
+        program.outputs.truncate(1);
+        assert_eq!(program.validate_outputs_reachable(), Ok(()));
+    }
+}
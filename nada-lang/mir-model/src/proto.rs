@@ -1,10 +1,12 @@
 use crate::{
     Input, Literal, NadaFunction, NadaFunctionArg, Operation, OperationId, OperationMap, Output, Party, ProgramMIR,
-    SourceRef, SourceRefIndex, TupleIndex,
+    SourceFiles, SourceRef, SourceRefIndex, TupleIndex,
 };
 use mir_proto::nillion::nada::{mir::v1 as proto_mir, operations::v1 as proto_op, types::v1 as proto_ty};
-use nada_type::{HashableIndexMap, IndexMap, NadaType};
+use nada_type::{IndexMap, NadaType, TypeError};
+use prost::encoding::{decode_key, decode_varint, skip_field, DecodeContext, WireType};
 pub use prost::Message;
+use sha2::{Digest, Sha256};
 use std::{
     collections::{BTreeMap, HashMap},
     hash::Hash,
@@ -16,6 +18,24 @@ pub use mir_proto::nillion::nada::mir::v1::ProgramMir as ProtoProgramMIR;
 #[error("protobuf parsing error: {0}")]
 pub struct ProtoError(pub &'static str);
 
+/// An error during a bounded MIR decoding. See [`ProgramMIR::try_decode_limited`].
+#[derive(Debug, thiserror::Error)]
+pub enum LimitedDecodeError {
+    /// The program could not be decoded.
+    #[error(transparent)]
+    Proto(#[from] ProtoError),
+
+    /// The program has more operations than allowed.
+    #[error("program has {count} operations, maximum allowed is {max}")]
+    TooManyOperations {
+        /// The number of operations in the program, including the ones in its functions.
+        count: usize,
+
+        /// The maximum number of operations allowed.
+        max: usize,
+    },
+}
+
 /// A trait that allows converting a trait from/into protobuf.
 pub trait ConvertProto: Sized {
     /// The protobuf type that represents this type.
@@ -115,6 +135,19 @@ where
     }
 }
 
+/// The protobuf model maps each file name to its full contents, so files aren't interned here.
+impl ConvertProto for SourceFiles {
+    type ProtoType = HashMap<String, String>;
+
+    fn into_proto(self) -> Self::ProtoType {
+        self.into_iter().collect()
+    }
+
+    fn try_from_proto(model: Self::ProtoType) -> Result<Self, ProtoError> {
+        Ok(model.into_iter().collect())
+    }
+}
+
 fn operation_map_to_proto(map: OperationMap) -> Vec<proto_mir::OperationMapEntry> {
     map.into_iter()
         .map(|(k, v)| proto_mir::OperationMapEntry { id: k.into_proto(), operation: Some(v.into_proto()) })
@@ -165,6 +198,89 @@ impl ConvertProto for ProgramMIR {
     }
 }
 
+impl ProgramMIR {
+    /// Decodes a program from its protobuf representation, rejecting it if it has more than `max_operations`
+    /// operations, counting the ones in its functions.
+    ///
+    /// The operations are counted by walking the encoded fields, before anything is decoded, so oversized
+    /// programs are rejected without materializing them.
+    pub fn try_decode_limited(bytes: &[u8], max_operations: usize) -> Result<Self, LimitedDecodeError> {
+        let count = count_encoded_operations(bytes)?;
+        if count > max_operations {
+            return Err(LimitedDecodeError::TooManyOperations { count, max: max_operations });
+        }
+        Ok(Self::try_decode(bytes)?)
+    }
+
+    /// Returns a SHA-256 hash of this program's canonical protobuf encoding, to be used as its identity when caching.
+    ///
+    /// Equal programs always produce equal hashes. The source files are encoded as a protobuf map, whose entries
+    /// have no defined order, so they are hashed separately, sorted by file name, after the rest of the program.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut program = self.clone();
+        let source_files = std::mem::take(&mut program.source_files);
+        let mut hasher = Sha256::new();
+        hasher.update(program.into_proto().encode_to_vec());
+        // `SourceFiles` is backed by a `BTreeMap` so this iterates in file name order.
+        for (name, contents) in source_files {
+            for field in [name, contents] {
+                hasher.update((field.len() as u64).to_be_bytes());
+                hasher.update(field);
+            }
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// The field number of `ProgramMIR.operations` in `mir.proto`.
+const PROGRAM_OPERATIONS_FIELD: u32 = 6;
+
+/// The field number of `ProgramMIR.functions` in `mir.proto`.
+const PROGRAM_FUNCTIONS_FIELD: u32 = 1;
+
+/// The field number of `NadaFunction.operations` in `mir.proto`.
+const FUNCTION_OPERATIONS_FIELD: u32 = 3;
+
+/// Counts the operations in an encoded program, including the ones in its functions, without decoding it.
+fn count_encoded_operations(bytes: &[u8]) -> Result<usize, ProtoError> {
+    let mut count = 0usize;
+    visit_length_delimited_fields(bytes, |tag, contents| {
+        if tag == PROGRAM_OPERATIONS_FIELD {
+            count = count.saturating_add(1);
+        } else if tag == PROGRAM_FUNCTIONS_FIELD {
+            visit_length_delimited_fields(contents, |tag, _| {
+                if tag == FUNCTION_OPERATIONS_FIELD {
+                    count = count.saturating_add(1);
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+/// Calls `visit` with the tag and contents of every length delimited field in an encoded message, skipping the rest.
+fn visit_length_delimited_fields(
+    mut bytes: &[u8],
+    mut visit: impl FnMut(u32, &[u8]) -> Result<(), ProtoError>,
+) -> Result<(), ProtoError> {
+    while !bytes.is_empty() {
+        let (tag, wire_type) = decode_key(&mut bytes).map_err(|_| ProtoError("protobuf decoding failed"))?;
+        if wire_type != WireType::LengthDelimited {
+            skip_field(wire_type, tag, &mut bytes, DecodeContext::default())
+                .map_err(|_| ProtoError("protobuf decoding failed"))?;
+            continue;
+        }
+        let length = decode_varint(&mut bytes).map_err(|_| ProtoError("protobuf decoding failed"))?;
+        let length = usize::try_from(length).map_err(|_| ProtoError("protobuf field is too large"))?;
+        let (contents, rest) = bytes.split_at_checked(length).ok_or(ProtoError("protobuf field is truncated"))?;
+        visit(tag, contents)?;
+        bytes = rest;
+    }
+    Ok(())
+}
+
 impl ConvertProto for NadaFunction {
     type ProtoType = proto_mir::NadaFunction;
 
@@ -1459,21 +1575,32 @@ impl ConvertProto for NadaType {
             ProtoNadaType::EcdsaSignature(_) => NadaType::EcdsaSignature,
             ProtoNadaType::EddsaSignature(_) => NadaType::EddsaSignature,
             ProtoNadaType::EddsaMessage(_) => NadaType::EddsaMessage,
-            ProtoNadaType::Array(array) => NadaType::Array {
-                inner_type: array.contained_type.ok_or(ProtoError("contained type not set"))?.try_into_rust()?,
-                size: array.size as usize,
-            },
-            ProtoNadaType::Tuple(tuple) => NadaType::Tuple {
-                left_type: tuple.left.ok_or(ProtoError("left type not set"))?.try_into_rust()?,
-                right_type: tuple.right.ok_or(ProtoError("right type not set"))?.try_into_rust()?,
-            },
-            ProtoNadaType::Ntuple(ntuple) => NadaType::NTuple {
-                types: ntuple
-                    .fields
-                    .into_iter()
-                    .map(|ty| ty.try_into_rust())
-                    .collect::<Result<Vec<_>, ProtoError>>()?,
-            },
+            ProtoNadaType::Array(array) => {
+                let inner_type = array.contained_type.ok_or(ProtoError("contained type not set"))?.try_into_rust()?;
+                let size = usize::try_from(array.size).map_err(|_| ProtoError("array size is too large"))?;
+                NadaType::new_array(inner_type, size).map_err(|e| match e {
+                    TypeError::MaxArityExceeded => ProtoError("array has too many elements"),
+                    TypeError::MaxRecursionDepthExceeded => ProtoError("array nested depth is too large"),
+                    _ => ProtoError("invalid array type"),
+                })?
+            }
+            ProtoNadaType::Tuple(tuple) => NadaType::new_tuple(
+                tuple.left.ok_or(ProtoError("left type not set"))?.try_into_rust()?,
+                tuple.right.ok_or(ProtoError("right type not set"))?.try_into_rust()?,
+            )
+            .map_err(|e| match e {
+                TypeError::MaxRecursionDepthExceeded => ProtoError("tuple nested depth is too large"),
+                _ => ProtoError("invalid tuple type"),
+            })?,
+            ProtoNadaType::Ntuple(ntuple) => {
+                let types =
+                    ntuple.fields.into_iter().map(|ty| ty.try_into_rust()).collect::<Result<Vec<_>, ProtoError>>()?;
+                NadaType::new_n_tuple(types).map_err(|e| match e {
+                    TypeError::MaxArityExceeded => ProtoError("ntuple has too many elements"),
+                    TypeError::MaxRecursionDepthExceeded => ProtoError("ntuple nested depth is too large"),
+                    _ => ProtoError("invalid ntuple type"),
+                })?
+            }
             ProtoNadaType::Object(object) => {
                 let types = object
                     .fields
@@ -1482,9 +1609,132 @@ impl ConvertProto for NadaType {
                         Ok((entry.name.clone(), entry.r#type.ok_or(ProtoError("type not set"))?.try_into_rust()?))
                     })
                     .collect::<Result<IndexMap<String, NadaType>, ProtoError>>()?;
-                NadaType::Object { types: HashableIndexMap(types) }
+                NadaType::new_object(types).map_err(|e| match e {
+                    TypeError::ObjectKeyTooLong { .. } => ProtoError("object key is too long"),
+                    TypeError::MaxArityExceeded => ProtoError("object has too many fields"),
+                    TypeError::MaxRecursionDepthExceeded => ProtoError("object nested depth is too large"),
+                    _ => ProtoError("invalid object type"),
+                })?
             }
         };
         Ok(nada_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputReference, OperationIdGenerator};
+
+    fn program_with_operations(count: usize) -> Vec<u8> {
+        let mut ids = OperationIdGenerator::default();
+        let mut program = ProgramMIR::default();
+        for _ in 0..count {
+            let id = ids.next_id();
+            let operation = Operation::InputReference(InputReference {
+                id,
+                refers_to: "my_int".to_string(),
+                ty: NadaType::SecretInteger,
+                source_ref_index: SourceRefIndex::default(),
+            });
+            program.operations.insert(id, operation);
+        }
+        program.into_proto().encode_to_vec()
+    }
+
+    fn program_with_sources(operations: usize, sources: &[(&str, &str)]) -> ProgramMIR {
+        let mut program = ProgramMIR::try_decode(&program_with_operations(operations)).expect("decoding failed");
+        for (name, contents) in sources {
+            program.source_files.insert(name.to_string(), contents.to_string());
+        }
+        program
+    }
+
+    #[test]
+    fn content_hash_equal_programs() {
+        let sources = [("a.py", "a = 1"), ("b.py", "b = 2"), ("c.py", "c = 3")];
+        let program = program_with_sources(10, &sources);
+        let other = program_with_sources(10, &sources);
+        assert_eq!(program.content_hash(), other.content_hash());
+    }
+
+    #[test]
+    fn content_hash_changed_program() {
+        let program = program_with_sources(10, &[("a.py", "a = 1")]);
+        assert_ne!(program.content_hash(), program_with_sources(11, &[("a.py", "a = 1")]).content_hash());
+        assert_ne!(program.content_hash(), program_with_sources(10, &[("a.py", "a = 2")]).content_hash());
+    }
+
+    #[test]
+    fn decode_limited_rejects_oversized_program() {
+        let bytes = program_with_operations(100);
+        let result = ProgramMIR::try_decode_limited(&bytes, 10);
+        assert!(matches!(result, Err(LimitedDecodeError::TooManyOperations { count: 100, max: 10 })));
+    }
+
+    #[test]
+    fn object_key_too_long() {
+        let field = |name: String| proto_ty::ObjectEntry { name, r#type: Some(NadaType::Integer.into_proto()) };
+        let object = |name: String| proto_ty::NadaType {
+            nada_type: Some(proto_ty::nada_type::NadaType::Object(proto_ty::Object { fields: vec![field(name)] })),
+        };
+        assert!(NadaType::try_from_proto(object("a".repeat(nada_type::MAX_OBJECT_KEY_LEN))).is_ok());
+        let err = NadaType::try_from_proto(object("a".repeat(nada_type::MAX_OBJECT_KEY_LEN + 1))).unwrap_err();
+        assert_eq!(err.to_string(), "protobuf parsing error: object key is too long");
+    }
+
+    #[test]
+    fn compound_type_too_many_elements() {
+        let array =
+            NadaType::Array { inner_type: Box::new(NadaType::Integer), size: nada_type::MAX_COMPOUND_ARITY + 1 };
+        let err = NadaType::try_from_proto(array.into_proto()).unwrap_err();
+        assert_eq!(err.to_string(), "protobuf parsing error: array has too many elements");
+
+        let ntuple = NadaType::NTuple { types: vec![NadaType::Integer; nada_type::MAX_COMPOUND_ARITY + 1] };
+        let err = NadaType::try_from_proto(ntuple.into_proto()).unwrap_err();
+        assert_eq!(err.to_string(), "protobuf parsing error: ntuple has too many elements");
+    }
+
+    #[test]
+    fn decode_limited_counts_function_operations() {
+        let function = proto_mir::NadaFunction { operations: vec![Default::default(); 20], ..Default::default() };
+        let model = ProtoProgramMIR { functions: vec![function], ..Default::default() };
+        let result = ProgramMIR::try_decode_limited(&model.encode_to_vec(), 10);
+        assert!(matches!(result, Err(LimitedDecodeError::TooManyOperations { count: 20, max: 10 })));
+    }
+
+    #[test]
+    fn decode_limited_rejects_before_decoding() {
+        // Empty operations fail to decode, so the count has to be checked before decoding them.
+        let model = ProtoProgramMIR { operations: vec![Default::default(); 100], ..Default::default() };
+        let bytes = model.encode_to_vec();
+        assert!(ProgramMIR::try_decode(&bytes).is_err());
+        let result = ProgramMIR::try_decode_limited(&bytes, 10);
+        assert!(matches!(result, Err(LimitedDecodeError::TooManyOperations { count: 100, max: 10 })));
+    }
+
+    #[test]
+    fn decode_limited_accepts_program_within_limit() {
+        let bytes = program_with_operations(10);
+        let program = ProgramMIR::try_decode_limited(&bytes, 10).unwrap();
+        assert_eq!(program, ProgramMIR::try_decode(&bytes).unwrap());
+        assert_eq!(program.operations.len(), 10);
+    }
+
+    /// Returns the tag of the first field in an encoded message.
+    fn probe_tag(message: impl Message) -> u32 {
+        let (tag, _) = decode_key(&mut message.encode_to_vec().as_slice()).unwrap();
+        tag
+    }
+
+    #[test]
+    fn operation_field_numbers_match_schema() {
+        let program_operations = ProtoProgramMIR { operations: vec![Default::default()], ..Default::default() };
+        assert_eq!(probe_tag(program_operations), PROGRAM_OPERATIONS_FIELD);
+        let program_functions = ProtoProgramMIR { functions: vec![Default::default()], ..Default::default() };
+        assert_eq!(probe_tag(program_functions), PROGRAM_FUNCTIONS_FIELD);
+        let function_operations =
+            proto_mir::NadaFunction { operations: vec![Default::default()], ..Default::default() };
+        assert_eq!(probe_tag(function_operations), FUNCTION_OPERATIONS_FIELD);
+    }
+}
@@ -13,6 +13,7 @@ use std::{
     hash::{Hash, Hasher},
     ops::{Deref, DerefMut},
 };
+use strum::IntoEnumIterator;
 use strum_macros::{EnumDiscriminants, EnumIter, IntoStaticStr};
 use thiserror::Error;
 use types_proc_macros::{EnumIsPrimitive, EnumNewFunctions, EnumPrimitiveToTrait, EnumToNadaTypeKind};
@@ -21,14 +22,31 @@ use types_proc_macros::{EnumIsPrimitive, EnumNewFunctions, EnumPrimitiveToTrait,
 /// This is set to reduce the risk of hitting a stack overflow.
 pub const MAX_RECURSION_DEPTH: usize = 100;
 
+/// Maximum number of elements in an array or ntuple, or fields in an object.
+/// This is set to prevent types that are cheap to declare but expensive to hold in memory.
+pub const MAX_COMPOUND_ARITY: usize = 100_000;
+
+/// Maximum length in bytes of an object's key.
+/// This is set to prevent keys that waste memory and bloat serialized programs.
+pub const MAX_OBJECT_KEY_LEN: usize = 256;
+
+// The number of characters of a key that are kept in errors when it's too long.
+const OBJECT_KEY_PREFIX_LEN: usize = 16;
+
 /// A hashable version of IndexMap.
+///
+/// Equality ignores the insertion order, as `IndexMap`'s does, so entries are hashed sorted by key to keep `Hash`
+/// consistent with `Eq`.
 #[derive(Clone, Eq, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HashableIndexMap<K: Hash + Eq, V: Hash>(pub IndexMap<K, V>);
 
-impl<K: Hash + Eq, V: Hash> Hash for HashableIndexMap<K, V> {
+impl<K: Hash + Eq + Ord, V: Hash> Hash for HashableIndexMap<K, V> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        for (key, value) in self.iter() {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+        state.write_usize(entries.len());
+        for (key, value) in entries {
             key.hash(state);
             value.hash(state);
         }
@@ -404,6 +422,7 @@ pub enum NadaType {
         inner_type: Box<Self>,
 
         /// Array length.
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "checked_serde::deserialize_size"))]
         size: usize,
     },
 
@@ -425,6 +444,7 @@ pub enum NadaType {
     #[skip_new_function]
     NTuple {
         /// NTuple types.
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "checked_serde::deserialize_types"))]
         types: Vec<Self>,
     },
 
@@ -436,6 +456,7 @@ pub enum NadaType {
     #[skip_new_function]
     Object {
         /// Key-value types.
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "checked_serde::deserialize_fields"))]
         types: HashableIndexMap<String, Self>,
     },
 
@@ -482,7 +503,12 @@ impl NadaType {
     }
 
     /// Returns a new array.
+    ///
+    /// Empty arrays are allowed. Use [`NadaType::new_array_nonempty`] when an empty array must be rejected.
     pub fn new_array(inner_type: Self, size: usize) -> Result<Self, TypeError> {
+        if size > MAX_COMPOUND_ARITY {
+            return Err(TypeError::MaxArityExceeded);
+        }
         let value = NadaType::Array { inner_type: Box::new(inner_type), size };
 
         if value.recursion_depth() > MAX_RECURSION_DEPTH {
@@ -492,6 +518,32 @@ impl NadaType {
         Ok(value)
     }
 
+    /// Returns a new array that has at least one element.
+    ///
+    /// This is the same as [`NadaType::new_array`] but fails with [`TypeError::ZeroValue`] if the size is zero.
+    pub fn new_array_nonempty(inner_type: Self, size: usize) -> Result<Self, TypeError> {
+        if size == 0 {
+            return Err(TypeError::ZeroValue);
+        }
+        Self::new_array(inner_type, size)
+    }
+
+    /// Returns a new array from the types of its elements.
+    ///
+    /// All elements must have the same type, otherwise this fails with [`TypeError::HeterogeneousArray`] pointing
+    /// at the first element that differs from the first one.
+    pub fn new_array_from_elements(elements: Vec<Self>) -> Result<Self, TypeError> {
+        let size = elements.len();
+        let mut elements = elements.into_iter().enumerate();
+        let Some((_, expected)) = elements.next() else {
+            return Err(TypeError::NonEmptyVecOnly);
+        };
+        if let Some((index, found)) = elements.find(|(_, element)| element != &expected) {
+            return Err(TypeError::HeterogeneousArray { index, expected, found });
+        }
+        Self::new_array(expected, size)
+    }
+
     /// Returns a new tuple.
     pub fn new_tuple(left: Self, right: Self) -> Result<Self, TypeError> {
         let value = NadaType::Tuple { left_type: Box::new(left), right_type: Box::new(right) };
@@ -505,6 +557,9 @@ impl NadaType {
 
     /// Returns a new ntuple.
     pub fn new_n_tuple(types: Vec<Self>) -> Result<Self, TypeError> {
+        if types.len() > MAX_COMPOUND_ARITY {
+            return Err(TypeError::MaxArityExceeded);
+        }
         let value = NadaType::NTuple { types };
 
         if value.recursion_depth() > MAX_RECURSION_DEPTH {
@@ -516,6 +571,10 @@ impl NadaType {
 
     /// Returns a new object.
     pub fn new_object(types: IndexMap<String, Self>) -> Result<Self, TypeError> {
+        if types.len() > MAX_COMPOUND_ARITY {
+            return Err(TypeError::MaxArityExceeded);
+        }
+        check_object_keys(types.keys())?;
         let value = NadaType::Object { types: types.into() };
 
         if value.recursion_depth() > MAX_RECURSION_DEPTH {
@@ -706,6 +765,8 @@ impl NadaType {
     }
 
     /// Returns the number of primitive types that are required to represent this [`NadaType`]
+    ///
+    /// The count saturates at `usize::MAX` rather than overflowing.
     pub fn primitive_elements_count(&self) -> usize {
         let mut count = 0usize;
         let mut inner_types = vec![(self, 1)];
@@ -730,9 +791,9 @@ impl NadaType {
                 | EddsaPrivateKey
                 | EddsaPublicKey
                 | EddsaSignature
-                | EddsaMessage => count = count.wrapping_add(multiplier),
+                | EddsaMessage => count = count.saturating_add(multiplier),
                 Array { size, inner_type } => {
-                    inner_types.push((inner_type, multiplier.wrapping_mul(*size)));
+                    inner_types.push((inner_type, multiplier.saturating_mul(*size)));
                 }
                 Tuple { left_type, right_type } => {
                     inner_types.push((left_type, multiplier));
@@ -789,7 +850,7 @@ impl NadaType {
                 }
 
                 Array { inner_type, size } => {
-                    inner_types.push((inner_type, multiplier.wrapping_mul(*size)));
+                    inner_types.push((inner_type, multiplier.saturating_mul(*size)));
                 }
                 Tuple { left_type, right_type } => {
                     inner_types.push((left_type, multiplier));
@@ -811,6 +872,29 @@ impl NadaType {
         Ok(count)
     }
 
+    /// Returns true if this [`NadaType`] is or contains a [`NadaType::SecretBlob`].
+    ///
+    /// Types containing blobs can't be counted, this allows checking for them before calling
+    /// [`NadaType::elements_count`].
+    pub fn contains_blob(&self) -> bool {
+        use NadaType::*;
+        let mut inner_types = vec![self];
+        while let Some(ty) = inner_types.pop() {
+            match ty {
+                SecretBlob => return true,
+                Array { inner_type, .. } => inner_types.push(inner_type),
+                Tuple { left_type, right_type } => {
+                    inner_types.push(left_type);
+                    inner_types.push(right_type);
+                }
+                NTuple { types } => inner_types.extend(types),
+                Object { types } => inner_types.extend(types.values()),
+                _ => {}
+            }
+        }
+        false
+    }
+
     /// Returns true if this [`NadaType`] and the other [`NadaType`] contain the same underlying type.
     /// For instance, SecretInteger and Integer have the same underlying type: Integer.
     pub fn has_same_underlying_type(&self, other: &Self) -> bool {
@@ -819,6 +903,78 @@ impl NadaType {
         self_metadata.nada_primitive_type() == other_metadata.nada_primitive_type()
     }
 
+    /// Returns every place where this type, the expected one, diverges from the actual type.
+    ///
+    /// Compound types are compared recursively so each mismatch points to the innermost type that differs. Since
+    /// arrays are homogeneous, a mismatch in the inner type of an array is reported under the `[*]` path segment.
+    /// An empty list means both types are equal.
+    pub fn diff(&self, actual: &NadaType) -> Vec<TypeMismatch> {
+        let mut mismatches = vec![];
+        self.diff_at(actual, String::new(), &mut mismatches);
+        mismatches
+    }
+
+    fn diff_at(&self, actual: &NadaType, path: String, mismatches: &mut Vec<TypeMismatch>) {
+        use NadaType::*;
+        match (self, actual) {
+            (Array { inner_type: expected, size: expected_size }, Array { inner_type: actual, size: actual_size })
+                if expected_size == actual_size =>
+            {
+                expected.diff_at(actual, format!("{path}[*]"), mismatches);
+            }
+            (Tuple { left_type: expected_left, right_type: expected_right }, Tuple { left_type, right_type }) => {
+                expected_left.diff_at(left_type, TypeMismatch::child_path(&path, "left"), mismatches);
+                expected_right.diff_at(right_type, TypeMismatch::child_path(&path, "right"), mismatches);
+            }
+            (NTuple { types: expected }, NTuple { types: actual }) if expected.len() == actual.len() => {
+                for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                    expected.diff_at(actual, format!("{path}[{index}]"), mismatches);
+                }
+            }
+            (Object { types: expected }, Object { types: actual })
+                if expected.len() == actual.len() && expected.keys().all(|key| actual.contains_key(key)) =>
+            {
+                for (key, expected) in expected.iter() {
+                    if let Some(actual) = actual.get(key) {
+                        expected.diff_at(
+                            actual,
+                            TypeMismatch::child_path(&path, &format!("field \"{key}\"")),
+                            mismatches,
+                        );
+                    }
+                }
+            }
+            _ => {
+                if self != actual {
+                    mismatches.push(TypeMismatch { path, expected: self.clone(), actual: actual.clone() });
+                }
+            }
+        }
+    }
+
+    /// Returns this type with the keys of every object, at any depth, sorted lexicographically.
+    ///
+    /// Objects keep their keys in insertion order, so the same object type built in a different key order displays
+    /// and serializes differently even though it compares and hashes equally. Canonicalized types are identical
+    /// regardless of the original key order. Arrays, tuples and ntuples are positional and keep their order.
+    pub fn canonicalize(self) -> Self {
+        use NadaType::*;
+        match self {
+            Array { inner_type, size } => Array { inner_type: Box::new(inner_type.canonicalize()), size },
+            Tuple { left_type, right_type } => {
+                Tuple { left_type: Box::new(left_type.canonicalize()), right_type: Box::new(right_type.canonicalize()) }
+            }
+            NTuple { types } => NTuple { types: types.into_iter().map(NadaType::canonicalize).collect() },
+            Object { types } => {
+                let mut types: IndexMap<String, NadaType> =
+                    IndexMap::from(types).into_iter().map(|(key, ty)| (key, ty.canonicalize())).collect();
+                types.sort_keys();
+                Object { types: types.into() }
+            }
+            ty => ty,
+        }
+    }
+
     /// Returns the recursion depth.
     fn recursion_depth(&self) -> usize {
         let mut stack = vec![(self, 1)];
@@ -943,6 +1099,50 @@ pub struct ElementsCount {
     pub ecdsa_signature_shares: usize,
 }
 
+impl ElementsCount {
+    /// Returns the total number of elements, public and secret.
+    pub fn total(&self) -> usize {
+        self.public.saturating_add(self.secret_total())
+    }
+
+    /// Returns the number of secret elements, which includes shares and ecdsa/eddsa shares.
+    pub fn secret_total(&self) -> usize {
+        // Destructure so adding a new kind of element forces updating this.
+        let Self { public: _, share, ecdsa_private_key_shares, eddsa_private_key_shares, ecdsa_signature_shares } =
+            self;
+        share
+            .saturating_add(*ecdsa_private_key_shares)
+            .saturating_add(*eddsa_private_key_shares)
+            .saturating_add(*ecdsa_signature_shares)
+    }
+}
+
+/// A place where two types diverge. See [`NadaType::diff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeMismatch {
+    /// The path to the diverging type, e.g. `field "scores"[*]`. Empty if the types diverge at the root.
+    pub path: String,
+    /// The expected type.
+    pub expected: NadaType,
+    /// The actual type.
+    pub actual: NadaType,
+}
+
+impl TypeMismatch {
+    fn child_path(path: &str, segment: &str) -> String {
+        if path.is_empty() { segment.to_string() } else { format!("{path}.{segment}") }
+    }
+}
+
+impl Display for TypeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "at {}: ", self.path)?;
+        }
+        write!(f, "was {}, expected {}", self.actual, self.expected)
+    }
+}
+
 /// Error when trying to count either secret blob or ecdsa private key shares.
 #[derive(Error, Debug)]
 pub enum CantCountError {
@@ -972,6 +1172,72 @@ impl Display for NadaTypeKind {
     }
 }
 
+impl NadaTypeKind {
+    /// Returns the primitive type kinds that users can choose when declaring a value.
+    ///
+    /// Shamir share kinds are only used internally and compound kinds are built out of other types, so neither is
+    /// included.
+    pub fn user_selectable_primitives() -> Vec<NadaTypeKind> {
+        NadaTypeKind::iter().filter(NadaTypeKind::is_user_selectable_primitive).collect()
+    }
+
+    /// Returns true if this is a primitive type kind that users can choose when declaring a value.
+    fn is_user_selectable_primitive(&self) -> bool {
+        use NadaTypeKind::*;
+        match self {
+            Integer
+            | UnsignedInteger
+            | Boolean
+            | SecretInteger
+            | SecretUnsignedInteger
+            | SecretBoolean
+            | SecretBlob
+            | EcdsaPrivateKey
+            | EcdsaDigestMessage
+            | EcdsaSignature
+            | EcdsaPublicKey
+            | StoreId
+            | EddsaPrivateKey
+            | EddsaPublicKey
+            | EddsaSignature
+            | EddsaMessage => true,
+            ShamirShareInteger | ShamirShareUnsignedInteger | ShamirShareBoolean | Array | Tuple | NTuple | Object => {
+                false
+            }
+        }
+    }
+
+    /// Returns a human readable name for this type kind.
+    pub fn display_name(&self) -> &'static str {
+        use NadaTypeKind::*;
+        match self {
+            Integer => "Integer",
+            UnsignedInteger => "Unsigned integer",
+            Boolean => "Boolean",
+            SecretInteger => "Secret integer",
+            SecretUnsignedInteger => "Secret unsigned integer",
+            SecretBoolean => "Secret boolean",
+            SecretBlob => "Secret blob",
+            ShamirShareInteger => "Shamir share integer",
+            ShamirShareUnsignedInteger => "Shamir share unsigned integer",
+            ShamirShareBoolean => "Shamir share boolean",
+            Array => "Array",
+            Tuple => "Tuple",
+            EcdsaPrivateKey => "ECDSA private key",
+            NTuple => "N-tuple",
+            EcdsaDigestMessage => "ECDSA digest message",
+            Object => "Object",
+            EcdsaSignature => "ECDSA signature",
+            EcdsaPublicKey => "ECDSA public key",
+            StoreId => "Store id",
+            EddsaPrivateKey => "EdDSA private key",
+            EddsaPublicKey => "EdDSA public key",
+            EddsaSignature => "EdDSA signature",
+            EddsaMessage => "EdDSA message",
+        }
+    }
+}
+
 impl TryFrom<&NadaTypeMetadata> for NadaType {
     type Error = TypeError;
 
@@ -1173,22 +1439,19 @@ impl TryFrom<&NadaTypeMetadata> for NadaType {
                 ..
             } => return Err(TypeError::unimplemented("shamir share eddsa message")),
 
-            NadaTypeMetadata::Array { size, inner } => {
-                NadaType::Array { size: *size, inner_type: Box::new(inner.as_ref().try_into()?) }
+            NadaTypeMetadata::Array { size, inner } => NadaType::new_array(inner.as_ref().try_into()?, *size)?,
+            NadaTypeMetadata::Tuple { left, right } => {
+                NadaType::new_tuple(left.as_ref().try_into()?, right.as_ref().try_into()?)?
             }
-            NadaTypeMetadata::Tuple { left, right } => NadaType::Tuple {
-                left_type: Box::new(left.as_ref().try_into()?),
-                right_type: Box::new(right.as_ref().try_into()?),
-            },
-            NadaTypeMetadata::NTuple { types } => NadaType::NTuple {
-                types: types.iter().map(|inner_type| inner_type.try_into()).collect::<Result<Vec<_>, Self::Error>>()?,
-            },
+            NadaTypeMetadata::NTuple { types } => NadaType::new_n_tuple(
+                types.iter().map(|inner_type| inner_type.try_into()).collect::<Result<Vec<_>, Self::Error>>()?,
+            )?,
             NadaTypeMetadata::Object { types } => {
                 let mut new_types = IndexMap::with_capacity(types.len());
                 for (name, inner_type) in types {
                     new_types.insert(name.clone(), inner_type.try_into()?);
                 }
-                NadaType::Object { types: new_types.into() }
+                NadaType::new_object(new_types)?
             }
         })
     }
@@ -1206,17 +1469,72 @@ pub enum TypeError {
     #[error("only a vector with homogeneous types (same type variant) is allowed")]
     HomogeneousVecOnly,
 
+    /// An array element doesn't have the same type as the first one.
+    #[error("array element {index} has type {found}, expected {expected}")]
+    HeterogeneousArray {
+        /// The position of the first element with a different type.
+        index: usize,
+        /// The type of the first element.
+        expected: NadaType,
+        /// The type of the divergent element.
+        found: NadaType,
+    },
+
     /// Maximum recursion depth exceeded.
     #[error("maximum recursion depth of {} exceeded", MAX_RECURSION_DEPTH)]
     MaxRecursionDepthExceeded,
 
+    /// Maximum number of elements or fields exceeded.
+    #[error("maximum number of elements of {} exceeded", MAX_COMPOUND_ARITY)]
+    MaxArityExceeded,
+
+    /// An object key is longer than the maximum allowed.
+    #[error("object key {key_prefix:?}... is {len} bytes long, maximum is {}", MAX_OBJECT_KEY_LEN)]
+    ObjectKeyTooLong {
+        /// The first characters of the key.
+        key_prefix: String,
+        /// The length of the key in bytes.
+        len: usize,
+    },
+
     /// Zero value is not allowed.
     #[error("providing zero is not possible")]
     ZeroValue,
 
+    /// The type has no zero value.
+    #[error("{0} has no zero value")]
+    NoZeroValue(NadaType),
+
     /// Zero value is not allowed.
     #[error("{0} is unimplemented")]
     Unimplemented(String),
+
+    /// The number of values doesn't match the number of primitive elements in a type.
+    #[error("expected {expected} primitive values, got {actual}")]
+    ValueCountMismatch {
+        /// The number of primitive elements in the type.
+        expected: usize,
+        /// The number of values provided.
+        actual: usize,
+    },
+
+    /// More values were provided than there are primitive elements in a type.
+    #[error("expected {expected} primitive values, got more")]
+    TooManyValues {
+        /// The number of primitive elements in the type.
+        expected: usize,
+    },
+
+    /// A value doesn't have the type expected at its position.
+    #[error("value {index} has type {actual}, expected {expected}")]
+    UnexpectedValueType {
+        /// The position of the value.
+        index: usize,
+        /// The type expected at that position.
+        expected: NadaType,
+        /// The type of the value.
+        actual: NadaType,
+    },
 }
 
 impl TypeError {
@@ -1242,6 +1560,51 @@ impl serde::Serialize for NeverPrimitiveType {
     }
 }
 
+// Checks that none of an object's keys is longer than [MAX_OBJECT_KEY_LEN].
+fn check_object_keys<'a>(mut keys: impl Iterator<Item = &'a String>) -> Result<(), TypeError> {
+    match keys.find(|key| key.len() > MAX_OBJECT_KEY_LEN) {
+        Some(key) => Err(TypeError::ObjectKeyTooLong {
+            key_prefix: key.chars().take(OBJECT_KEY_PREFIX_LEN).collect(),
+            len: key.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Deserializers for the fields of compound types that enforce the same limits as the [NadaType] constructors.
+#[cfg(feature = "serde")]
+mod checked_serde {
+    use crate::{check_object_keys, HashableIndexMap, NadaType, TypeError, MAX_COMPOUND_ARITY};
+    use serde::{de::Error, Deserialize, Deserializer};
+
+    pub(crate) fn deserialize_size<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+        let size = usize::deserialize(deserializer)?;
+        if size > MAX_COMPOUND_ARITY {
+            return Err(Error::custom(TypeError::MaxArityExceeded));
+        }
+        Ok(size)
+    }
+
+    pub(crate) fn deserialize_types<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<NadaType>, D::Error> {
+        let types = Vec::<NadaType>::deserialize(deserializer)?;
+        if types.len() > MAX_COMPOUND_ARITY {
+            return Err(Error::custom(TypeError::MaxArityExceeded));
+        }
+        Ok(types)
+    }
+
+    pub(crate) fn deserialize_fields<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashableIndexMap<String, NadaType>, D::Error> {
+        let types = HashableIndexMap::<String, NadaType>::deserialize(deserializer)?;
+        if types.len() > MAX_COMPOUND_ARITY {
+            return Err(Error::custom(TypeError::MaxArityExceeded));
+        }
+        check_object_keys(types.keys()).map_err(Error::custom)?;
+        Ok(types)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for NeverPrimitiveType {
     fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
@@ -1255,7 +1618,20 @@ impl<'de> serde::Deserialize<'de> for NeverPrimitiveType {
 
 #[cfg(test)]
 mod tests {
-    use crate::NadaType;
+    use crate::{
+        IndexMap, NadaType, NadaTypeKind, NadaTypeMetadata, TypeError, TypeMismatch, MAX_COMPOUND_ARITY,
+        MAX_OBJECT_KEY_LEN, OBJECT_KEY_PREFIX_LEN,
+    };
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    fn hash_of(ty: &NadaType) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        ty.hash(&mut hasher);
+        hasher.finish()
+    }
 
     #[test]
     fn test_has_same_underlying_type() {
@@ -1264,4 +1640,261 @@ mod tests {
         assert!(NadaType::SecretInteger.has_same_underlying_type(&NadaType::SecretInteger));
         assert!(!NadaType::Integer.has_same_underlying_type(&NadaType::SecretBoolean));
     }
+
+    #[test]
+    fn test_primitive_elements_count_saturates() {
+        // 2^33 * 2^33 elements overflows a 64 bit usize
+        let size = 1 << 33;
+        let inner = NadaType::Array { inner_type: Box::new(NadaType::SecretInteger), size };
+        let outer = NadaType::Array { inner_type: Box::new(inner), size };
+        let ty = NadaType::Tuple { left_type: Box::new(outer.clone()), right_type: Box::new(outer) };
+        assert_eq!(ty.primitive_elements_count(), usize::MAX);
+        assert_eq!(ty.elements_count().unwrap().share, usize::MAX);
+    }
+
+    #[test]
+    fn test_elements_count_totals() {
+        let ty = NadaType::new_n_tuple(vec![
+            NadaType::new_array(NadaType::SecretInteger, 3).unwrap(),
+            NadaType::Integer,
+            NadaType::EcdsaPrivateKey,
+            NadaType::EcdsaSignature,
+            NadaType::EddsaPrivateKey,
+            NadaType::Boolean,
+        ])
+        .unwrap();
+        let count = ty.elements_count().unwrap();
+        let sum = count.public
+            + count.share
+            + count.ecdsa_private_key_shares
+            + count.eddsa_private_key_shares
+            + count.ecdsa_signature_shares;
+        assert_eq!(count.total(), sum);
+        assert_eq!(count.total(), 8);
+        assert_eq!(count.secret_total(), 6);
+    }
+
+    #[test]
+    fn test_contains_blob() {
+        assert!(!NadaType::Integer.contains_blob());
+        assert!(NadaType::SecretBlob.contains_blob());
+        assert!(NadaType::new_array(NadaType::SecretBlob, 3).unwrap().contains_blob());
+        let ty =
+            NadaType::new_n_tuple(vec![NadaType::SecretInteger, NadaType::new_array(NadaType::Boolean, 2).unwrap()])
+                .unwrap();
+        assert!(!ty.contains_blob());
+    }
+
+    #[test]
+    fn test_diff_array_size() {
+        let expected = NadaType::new_array(NadaType::new_array(NadaType::SecretInteger, 3).unwrap(), 2).unwrap();
+        let actual = NadaType::new_array(NadaType::new_array(NadaType::SecretInteger, 4).unwrap(), 2).unwrap();
+        let mismatches = expected.diff(&actual);
+        assert_eq!(
+            mismatches,
+            vec![TypeMismatch {
+                path: "[*]".to_string(),
+                expected: NadaType::new_array(NadaType::SecretInteger, 3).unwrap(),
+                actual: NadaType::new_array(NadaType::SecretInteger, 4).unwrap(),
+            }]
+        );
+        assert!(expected.diff(&expected).is_empty());
+    }
+
+    #[test]
+    fn test_diff_object_leaf() {
+        let expected = NadaType::new_object(
+            [
+                ("name".to_string(), NadaType::SecretBlob),
+                (
+                    "scores".to_string(),
+                    NadaType::new_n_tuple(vec![NadaType::Integer, NadaType::SecretInteger]).unwrap(),
+                ),
+            ]
+            .into(),
+        )
+        .unwrap();
+        let actual = NadaType::new_object(
+            [
+                ("name".to_string(), NadaType::SecretBlob),
+                ("scores".to_string(), NadaType::new_n_tuple(vec![NadaType::Integer, NadaType::Boolean]).unwrap()),
+            ]
+            .into(),
+        )
+        .unwrap();
+        let mismatches = expected.diff(&actual);
+        assert_eq!(
+            mismatches,
+            vec![TypeMismatch {
+                path: "field \"scores\"[1]".to_string(),
+                expected: NadaType::SecretInteger,
+                actual: NadaType::Boolean,
+            }]
+        );
+        assert_eq!(mismatches[0].to_string(), "at field \"scores\"[1]: was Boolean, expected SecretInteger");
+    }
+
+    #[test]
+    fn test_object_key_length() {
+        let object = |key: String| NadaType::new_object([(key, NadaType::Integer)].into());
+        assert!(object("a".repeat(MAX_OBJECT_KEY_LEN)).is_ok());
+        assert_eq!(
+            object("a".repeat(MAX_OBJECT_KEY_LEN + 1)).unwrap_err(),
+            TypeError::ObjectKeyTooLong { key_prefix: "a".repeat(OBJECT_KEY_PREFIX_LEN), len: MAX_OBJECT_KEY_LEN + 1 }
+        );
+
+        let key = format!("{}{}", "b".repeat(OBJECT_KEY_PREFIX_LEN), "a".repeat(MAX_OBJECT_KEY_LEN));
+        let len = key.len();
+        assert_eq!(
+            object(key).unwrap_err(),
+            TypeError::ObjectKeyTooLong { key_prefix: "b".repeat(OBJECT_KEY_PREFIX_LEN), len }
+        );
+    }
+
+    #[test]
+    fn test_object_key_length_from_metadata() {
+        let inner = NadaTypeMetadata::from(&NadaType::Integer);
+        let metadata = NadaTypeMetadata::Object { types: [("a".repeat(MAX_OBJECT_KEY_LEN + 1), inner)].into() };
+        assert_eq!(
+            NadaType::try_from(&metadata).unwrap_err(),
+            TypeError::ObjectKeyTooLong { key_prefix: "a".repeat(OBJECT_KEY_PREFIX_LEN), len: MAX_OBJECT_KEY_LEN + 1 }
+        );
+    }
+
+    #[test]
+    fn test_max_arity_from_metadata() {
+        let inner = NadaTypeMetadata::from(&NadaType::Integer);
+        let array = NadaTypeMetadata::Array { size: MAX_COMPOUND_ARITY + 1, inner: Box::new(inner.clone()) };
+        assert_eq!(NadaType::try_from(&array).unwrap_err(), TypeError::MaxArityExceeded);
+
+        let ntuple = NadaTypeMetadata::NTuple { types: vec![inner; MAX_COMPOUND_ARITY + 1] };
+        assert_eq!(NadaType::try_from(&ntuple).unwrap_err(), TypeError::MaxArityExceeded);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_max_arity_deserialize() {
+        let array = serde_json::json!({"Array": {"inner_type": "Integer", "size": MAX_COMPOUND_ARITY + 1}});
+        let error = serde_json::from_value::<NadaType>(array).unwrap_err();
+        assert!(error.to_string().contains(&TypeError::MaxArityExceeded.to_string()));
+
+        let ntuple = serde_json::json!({"NTuple": {"types": vec!["Integer"; MAX_COMPOUND_ARITY + 1]}});
+        let error = serde_json::from_value::<NadaType>(ntuple).unwrap_err();
+        assert!(error.to_string().contains(&TypeError::MaxArityExceeded.to_string()));
+
+        let fields: serde_json::Map<_, _> =
+            (0..=MAX_COMPOUND_ARITY).map(|index| (format!("field_{index}"), "Integer".into())).collect();
+        let object = serde_json::json!({"Object": {"types": fields}});
+        let error = serde_json::from_value::<NadaType>(object).unwrap_err();
+        assert!(error.to_string().contains(&TypeError::MaxArityExceeded.to_string()));
+
+        let object = serde_json::json!({"Object": {"types": {"a".repeat(MAX_OBJECT_KEY_LEN + 1): "Integer"}}});
+        assert!(serde_json::from_value::<NadaType>(object).is_err());
+
+        let array = serde_json::json!({"Array": {"inner_type": "Integer", "size": MAX_COMPOUND_ARITY}});
+        assert!(serde_json::from_value::<NadaType>(array).is_ok());
+    }
+
+    #[test]
+    fn test_object_hash_ignores_key_order() {
+        let object = |fields: &[(&str, NadaType)]| {
+            NadaType::new_object(fields.iter().map(|(name, ty)| (name.to_string(), ty.clone())).collect()).unwrap()
+        };
+        let left = object(&[("a", NadaType::Integer), ("b", NadaType::SecretBoolean)]);
+        let right = object(&[("b", NadaType::SecretBoolean), ("a", NadaType::Integer)]);
+        assert_eq!(left, right);
+        assert_eq!(hash_of(&left), hash_of(&right));
+
+        let swapped = object(&[("b", NadaType::Integer), ("a", NadaType::SecretBoolean)]);
+        assert_ne!(left, swapped);
+        assert_ne!(hash_of(&left), hash_of(&swapped));
+
+        let subset = object(&[("a", NadaType::Integer)]);
+        assert_ne!(left, subset);
+        assert_ne!(hash_of(&left), hash_of(&subset));
+    }
+
+    #[test]
+    fn test_canonicalize_object_key_order() {
+        let inner = |reversed: bool| {
+            let mut types = vec![("x".to_string(), NadaType::Integer), ("y".to_string(), NadaType::Boolean)];
+            if reversed {
+                types.reverse();
+            }
+            NadaType::new_object(types.into_iter().collect()).unwrap()
+        };
+        let left = NadaType::new_object(
+            [("b".to_string(), inner(true)), ("a".to_string(), NadaType::new_array(inner(true), 2).unwrap())].into(),
+        )
+        .unwrap();
+        let right = NadaType::new_object(
+            [("a".to_string(), NadaType::new_array(inner(false), 2).unwrap()), ("b".to_string(), inner(false))].into(),
+        )
+        .unwrap();
+        assert_eq!(left, right);
+        assert_eq!(hash_of(&left), hash_of(&right));
+
+        let left = left.canonicalize();
+        let right = right.canonicalize();
+        assert_eq!(left, right);
+        assert_eq!(hash_of(&left), hash_of(&right));
+        assert_eq!(left.to_string(), right.to_string());
+    }
+
+    #[test]
+    fn test_max_arity() {
+        assert!(NadaType::new_array(NadaType::Integer, MAX_COMPOUND_ARITY).is_ok());
+        assert!(matches!(
+            NadaType::new_array(NadaType::Integer, MAX_COMPOUND_ARITY + 1),
+            Err(TypeError::MaxArityExceeded)
+        ));
+
+        assert!(NadaType::new_n_tuple(vec![NadaType::Integer; MAX_COMPOUND_ARITY]).is_ok());
+        assert!(matches!(
+            NadaType::new_n_tuple(vec![NadaType::Integer; MAX_COMPOUND_ARITY + 1]),
+            Err(TypeError::MaxArityExceeded)
+        ));
+
+        let fields = |count: usize| -> IndexMap<String, NadaType> {
+            (0..count).map(|index| (format!("field_{index}"), NadaType::Integer)).collect()
+        };
+        assert!(NadaType::new_object(fields(MAX_COMPOUND_ARITY)).is_ok());
+        assert!(matches!(NadaType::new_object(fields(MAX_COMPOUND_ARITY + 1)), Err(TypeError::MaxArityExceeded)));
+    }
+
+    #[test]
+    fn test_new_array_nonempty() {
+        assert!(matches!(NadaType::new_array_nonempty(NadaType::Integer, 0), Err(TypeError::ZeroValue)));
+        assert!(NadaType::new_array_nonempty(NadaType::Integer, 1).is_ok());
+        assert!(NadaType::new_array(NadaType::Integer, 0).is_ok());
+    }
+
+    #[test]
+    fn test_user_selectable_primitives() {
+        let kinds = NadaTypeKind::user_selectable_primitives();
+        assert!(kinds.contains(&NadaTypeKind::Integer));
+        assert!(kinds.contains(&NadaTypeKind::SecretBoolean));
+        assert!(!kinds.contains(&NadaTypeKind::ShamirShareInteger));
+        assert!(!kinds.contains(&NadaTypeKind::ShamirShareUnsignedInteger));
+        assert!(!kinds.contains(&NadaTypeKind::ShamirShareBoolean));
+        assert!(!kinds.contains(&NadaTypeKind::Array));
+        assert_eq!(NadaTypeKind::SecretUnsignedInteger.display_name(), "Secret unsigned integer");
+    }
+
+    #[test]
+    fn test_new_array_from_elements() {
+        let array = NadaType::new_array_from_elements(vec![NadaType::SecretInteger; 3]).unwrap();
+        assert_eq!(array, NadaType::new_array(NadaType::SecretInteger, 3).unwrap());
+        assert_eq!(NadaType::new_array_from_elements(vec![]), Err(TypeError::NonEmptyVecOnly));
+    }
+
+    #[test]
+    fn test_new_array_from_heterogeneous_elements() {
+        let elements = vec![NadaType::SecretInteger, NadaType::SecretInteger, NadaType::Boolean, NadaType::Integer];
+        let error = NadaType::new_array_from_elements(elements).unwrap_err();
+        assert_eq!(
+            error,
+            TypeError::HeterogeneousArray { index: 2, expected: NadaType::SecretInteger, found: NadaType::Boolean }
+        );
+    }
 }
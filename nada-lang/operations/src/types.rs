@@ -418,6 +418,27 @@ impl Display for InnerReason {
     }
 }
 
+/// Machine-readable category of a reason for a forbidden combination.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, IntoStaticStr, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum ReasonCategory {
+    /// Not yet implemented, will be later.
+    NotYetImplemented,
+
+    /// This combination is mathematically impossible.
+    ImpossibleMath,
+
+    /// Type error: operation not allowed for that type.
+    TypeError,
+}
+
+impl ReasonCategory {
+    /// Returns the snake case name of this category, e.g. `type_error`.
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+}
+
 /// Reason for a forbidden combination.
 #[derive(Debug, Clone)]
 pub struct Reason {
@@ -444,6 +465,15 @@ impl Reason {
         Self { inner: InnerReason::TypeError, description: None }
     }
 
+    /// Returns the machine-readable category of this reason.
+    pub fn category(&self) -> ReasonCategory {
+        match self.inner {
+            InnerReason::NotYetImplemented => ReasonCategory::NotYetImplemented,
+            InnerReason::ImpossibleMath => ReasonCategory::ImpossibleMath,
+            InnerReason::TypeError => ReasonCategory::TypeError,
+        }
+    }
+
     /// Adds a description for this reason.
     pub fn with_description(mut self, description: &str) -> Self {
         self.description = Some(description.to_string());
@@ -1138,6 +1168,7 @@ impl BinaryOperation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::build;
     use nada_value::NadaTypeKind;
     use OperationType::*;
 
@@ -1231,6 +1262,49 @@ mod tests {
         assert!(method_caller.parameter_types[0].1.is_some()); // one return type
     }
 
+    #[test]
+    fn forbidden_addition() {
+        let operations = build();
+        let boolean = DataType::Identifier(Identifier::Boolean);
+        let allowance = operations.is_allowed("Addition", &boolean, Some(&boolean)).unwrap();
+        let Allowance::Forbidden(reason) = allowance else { panic!("unexpected allowance: {allowance:?}") };
+        assert!(matches!(reason.inner, InnerReason::TypeError));
+        assert_eq!(operations.output_type("Addition", &boolean, Some(&boolean)), None);
+    }
+
+    #[test]
+    fn allowed_addition() {
+        let operations = build();
+        let public = DataType::Identifier(Identifier::Integer);
+        let secret = DataType::Identifier(Identifier::SecretInteger);
+        let allowance = operations.is_allowed("Addition", &public, Some(&secret)).unwrap();
+        assert!(matches!(allowance, Allowance::Allowed(output) if output == secret));
+        assert_eq!(operations.output_type("Addition", &public, Some(&public)), Some(public));
+        assert!(operations.is_allowed("Unknown", &public, Some(&public)).is_none());
+    }
+
+    #[test]
+    fn reason_categories() {
+        assert_eq!(Reason::not_yet_implemented().category(), ReasonCategory::NotYetImplemented);
+        assert_eq!(Reason::impossible_math().category(), ReasonCategory::ImpossibleMath);
+        assert_eq!(Reason::type_error().with_description("boolean arithmetic").category(), ReasonCategory::TypeError);
+        assert_eq!(ReasonCategory::NotYetImplemented.as_str(), "not_yet_implemented");
+        assert_eq!(ReasonCategory::ImpossibleMath.as_str(), "impossible_math");
+        assert_eq!(ReasonCategory::TypeError.as_str(), "type_error");
+    }
+
+    #[test]
+    fn forbidden_category() {
+        let operations = build();
+        let boolean = DataType::Identifier(Identifier::Boolean);
+        let integer = DataType::Identifier(Identifier::Integer);
+        assert_eq!(
+            operations.forbidden_category("Addition", &boolean, Some(&boolean)),
+            Some(ReasonCategory::TypeError)
+        );
+        assert_eq!(operations.forbidden_category("Addition", &integer, Some(&integer)), None);
+    }
+
     #[test]
     #[should_panic(expected = "wrong number of arguments")]
     fn illegal_number_of_arguments() {
@@ -1439,3 +1513,65 @@ pub struct BuiltOperations {
     /// List of instance methods by type
     pub instance_methods: LinkedHashMap<DataType, Vec<InstanceMethod>>,
 }
+
+impl BuiltOperations {
+    /// Returns whether an operation is allowed on a combination of types.
+    ///
+    /// Binary operations are looked up when a right type is provided, unary operations otherwise. Returns `None` if
+    /// there is no such operation.
+    pub fn is_allowed(&self, op_name: &str, left: &DataType, right: Option<&DataType>) -> Option<Allowance> {
+        match right {
+            Some(right) => {
+                let operation = self.binary_operations.get(op_name)?;
+                let input = (*left, *right);
+                if let Some(output) = operation.allowed_combinations.get(&input) {
+                    Some(Allowance::Allowed(*output))
+                } else {
+                    operation.forbidden_combinations.get(&input).cloned().map(Allowance::Forbidden)
+                }
+            }
+            None => {
+                let operation =
+                    self.unary_operations.values().flatten().find(|operation| operation.metadata.name == op_name)?;
+                if let Some(output) = operation.allowed_combinations.get(left) {
+                    Some(Allowance::Allowed(*output))
+                } else {
+                    operation.forbidden_combinations.get(left).cloned().map(Allowance::Forbidden)
+                }
+            }
+        }
+    }
+
+    /// Returns the category of the reason an operation is forbidden on a combination of types.
+    ///
+    /// Returns `None` if there is no such operation or if the operation is allowed.
+    pub fn forbidden_category(
+        &self,
+        op_name: &str,
+        left: &DataType,
+        right: Option<&DataType>,
+    ) -> Option<ReasonCategory> {
+        match self.is_allowed(op_name, left, right)? {
+            Allowance::Allowed(_) => None,
+            Allowance::Forbidden(reason) => Some(reason.category()),
+        }
+    }
+
+    /// Returns the output type of an operation on a combination of types, if that operation is allowed.
+    pub fn output_type(&self, op_name: &str, left: &DataType, right: Option<&DataType>) -> Option<DataType> {
+        match self.is_allowed(op_name, left, right)? {
+            Allowance::Allowed(output) => Some(output),
+            Allowance::Forbidden(_) => None,
+        }
+    }
+}
+
+/// Whether an operation is allowed on a combination of types.
+#[derive(Debug, Clone)]
+pub enum Allowance {
+    /// The operation is allowed and outputs a value of this type.
+    Allowed(DataType),
+
+    /// The operation is forbidden.
+    Forbidden(Reason),
+}
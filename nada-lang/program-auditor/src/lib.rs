@@ -14,7 +14,10 @@
     clippy::todo
 )]
 
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
 use mpc_vm::{
     requirements::{MPCProgramRequirements, ProgramRequirements},
@@ -39,11 +42,115 @@ pub struct ProgramAuditorConfig {
     pub max_instructions_per_type: HashMap<String, u64>,
     /// Maximum amount of pre-processing elements that are allowed.
     pub max_preprocessing: MPCProgramRequirements,
+    /// Whether programs that declare inputs not contributing to any output are rejected.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reject_unused_inputs: bool,
+    /// The names of the policies that are skipped during audits.
+    ///
+    /// Unlike [`ProgramAuditorConfig::disable`], every other policy is still run. Loading a configuration that names
+    /// a policy that doesn't exist fails.
+    #[cfg_attr(feature = "serde", serde(default, deserialize_with = "deserialize_disabled_policies"))]
+    pub disabled_policies: HashSet<String>,
     /// Disables the program auditor
     #[cfg_attr(feature = "serde", serde(skip))]
     pub disable: bool,
 }
 
+impl ProgramAuditorConfig {
+    /// Returns a builder for a program auditor configuration.
+    pub fn builder() -> ProgramAuditorConfigBuilder {
+        ProgramAuditorConfigBuilder::default()
+    }
+
+    /// Checks that every disabled policy is one of the policies run during audits.
+    pub fn validate(&self) -> Result<(), UnknownPolicyError> {
+        validate_policy_names(&self.disabled_policies)
+    }
+}
+
+/// A policy name that doesn't match any of the policies run during audits.
+#[derive(Debug, Error, PartialEq)]
+#[error("unknown program auditor policy {0:?}")]
+pub struct UnknownPolicyError(pub String);
+
+fn validate_policy_names(names: &HashSet<String>) -> Result<(), UnknownPolicyError> {
+    let policies = Policy::policies();
+    match names.iter().find(|name| !policies.iter().any(|policy| policy.name() == name.as_str())) {
+        Some(name) => Err(UnknownPolicyError(name.clone())),
+        None => Ok(()),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_disabled_policies<'de, D>(deserializer: D) -> Result<HashSet<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let names: HashSet<String> = serde::Deserialize::deserialize(deserializer)?;
+    validate_policy_names(&names).map_err(serde::de::Error::custom)?;
+    Ok(names)
+}
+
+/// A builder for a [`ProgramAuditorConfig`].
+///
+/// Any setting that isn't set keeps the value in [`ProgramAuditorConfig::default`].
+#[derive(Clone, Debug, Default)]
+pub struct ProgramAuditorConfigBuilder {
+    config: ProgramAuditorConfig,
+}
+
+impl ProgramAuditorConfigBuilder {
+    /// Sets the maximum amount of memory elements that are allowed.
+    pub fn max_memory(mut self, size: u64) -> Self {
+        self.config.max_memory_size = size;
+        self
+    }
+
+    /// Sets the maximum allowed total number of instructions.
+    pub fn max_instructions(mut self, count: u64) -> Self {
+        self.config.max_instructions = count;
+        self
+    }
+
+    /// Sets the maximum allowed number of instructions of a specific type.
+    pub fn max_instruction<S: Into<String>>(mut self, name: S, count: u64) -> Self {
+        self.config.max_instructions_per_type.insert(name.into(), count);
+        self
+    }
+
+    /// Sets the maximum amount of pre-processing elements that are allowed.
+    pub fn max_preprocessing(mut self, requirements: MPCProgramRequirements) -> Self {
+        self.config.max_preprocessing = requirements;
+        self
+    }
+
+    /// Sets whether programs that declare inputs not contributing to any output are rejected.
+    pub fn reject_unused_inputs(mut self, reject: bool) -> Self {
+        self.config.reject_unused_inputs = reject;
+        self
+    }
+
+    /// Disables the policy with the given name, leaving the rest of them enabled.
+    pub fn disable_policy<S: Into<String>>(mut self, name: S) -> Self {
+        self.config.disabled_policies.insert(name.into());
+        self
+    }
+
+    /// Sets whether the program auditor is disabled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.config.disable = disabled;
+        self
+    }
+
+    /// Builds the program auditor configuration.
+    ///
+    /// Fails if any of the disabled policies doesn't exist.
+    pub fn build(self) -> Result<ProgramAuditorConfig, UnknownPolicyError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 /// Program Auditor Request
 ///
 /// Represents a request to the Program Auditor.  
@@ -58,6 +165,9 @@ pub struct ProgramAuditorRequest {
     pub instructions: HashMap<String, u64>,
     /// The program preprocessing requirements
     pub preprocessing_requirements: MPCProgramRequirements,
+    /// The declared inputs that don't contribute to any output
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unused_inputs: Vec<String>,
 }
 
 impl ProgramAuditorRequest {
@@ -79,6 +189,7 @@ impl ProgramAuditorRequest {
             total_instructions: program.body.protocols.len() as u64,
             instructions: Self::calculate_instructions_map(&program)?,
             preprocessing_requirements,
+            unused_inputs: mir.unused_inputs(),
         })
     }
 
@@ -123,12 +234,15 @@ pub enum Policy {
     MaxInstructions(MaxInstructionsPolicy),
     /// Maximum amount of preprocessing elements policy
     MaxPreprocessing(MaxPreprocessingPolicy),
+    /// No unused inputs policy
+    NoUnusedInputs(NoUnusedInputsPolicy),
 }
 
 named_element!(
     (MaxMemoryPolicy, "max_memory"),
     (MaxInstructionsPolicy, "max_instructions"),
-    (MaxPreprocessingPolicy, "max_preprocessing_elements")
+    (MaxPreprocessingPolicy, "max_preprocessing_elements"),
+    (NoUnusedInputsPolicy, "no_unused_inputs")
 );
 
 impl Policy {
@@ -141,6 +255,7 @@ impl Policy {
             MaxMemory(MaxMemoryPolicy {}),
             MaxInstructions(MaxInstructionsPolicy {}),
             MaxPreprocessing(MaxPreprocessingPolicy {}),
+            NoUnusedInputs(NoUnusedInputsPolicy {}),
         ]
     }
 }
@@ -177,7 +292,8 @@ impl ProgramAuditor {
 
     /// Audits a [`ProgramMIR`].
     ///
-    /// The audit runs all the policies specified in the [`Policy`] enum. Whenever if finds a failure,
+    /// The audit runs all the policies specified in the [`Policy`] enum, except for the ones listed in
+    /// [`ProgramAuditorConfig::disabled_policies`]. Whenever if finds a failure,
     /// it stops and returns the violation. The reason for this behaviour is to prevent unnecessary execution
     /// when the program has been detected as invalid. This unnecessary execution of policies has
     /// computing costs and also it could potentially be introducing additional risks.  
@@ -195,6 +311,9 @@ impl ProgramAuditor {
         let context = ProgramAuditorContext { config: &self.config, request };
         // Lets run the policies. We will return at the first failure.
         for policy in Policy::policies() {
+            if self.config.disabled_policies.contains(policy.name()) {
+                continue;
+            }
             policy.run(&context)?;
         }
         Ok(())
@@ -288,6 +407,25 @@ impl PolicyRunner for MaxPreprocessingPolicy {
     }
 }
 
+/// Implementation of No Unused Inputs Policy
+///
+/// Unused inputs leak their presence and waste preprocessing elements. This policy only applies if
+/// [`ProgramAuditorConfig::reject_unused_inputs`] is set.
+#[derive(PartialEq, Debug)]
+pub struct NoUnusedInputsPolicy;
+
+impl PolicyRunner for NoUnusedInputsPolicy {
+    fn run(&self, context: &ProgramAuditorContext) -> Result<(), ProgramAuditorError> {
+        if context.config.reject_unused_inputs && !context.request.unused_inputs.is_empty() {
+            return Err(ProgramAuditorError::InvalidProgram(PolicyViolation {
+                policy: self.name().to_string(),
+                message: format!("program declares unused inputs: {}", context.request.unused_inputs.join(", ")),
+            }));
+        }
+        Ok(())
+    }
+}
+
 impl PolicyRunner for Policy {
     fn run(&self, context: &ProgramAuditorContext) -> Result<(), ProgramAuditorError> {
         use Policy::*;
@@ -295,6 +433,7 @@ impl PolicyRunner for Policy {
             MaxInstructions(o) => o.run(context),
             MaxMemory(o) => o.run(context),
             MaxPreprocessing(o) => o.run(context),
+            NoUnusedInputs(o) => o.run(context),
         }
     }
 }
@@ -306,6 +445,7 @@ impl NamedElement for Policy {
             MaxInstructions(o) => o.name(),
             MaxMemory(o) => o.name(),
             MaxPreprocessing(o) => o.name(),
+            NoUnusedInputs(o) => o.name(),
         }
     }
 }
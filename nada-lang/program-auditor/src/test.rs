@@ -1,9 +1,15 @@
 //! Tests for the program auditor
 
-use crate::{MaxPreprocessingPolicy, ProgramAuditorError, ProgramAuditorRequest};
+use crate::{
+    MaxInstructionsPolicy, MaxMemoryPolicy, MaxPreprocessingPolicy, NoUnusedInputsPolicy, ProgramAuditorError,
+    ProgramAuditorRequest, UnknownPolicyError,
+};
 use anyhow::Error;
 use mpc_vm::requirements::MPCProgramRequirements;
-use nada_compiler_backend::mir::NamedElement;
+use nada_compiler_backend::mir::{
+    proto::{ConvertProto, Message},
+    NamedElement,
+};
 use rstest::rstest;
 use test_programs::PROGRAMS;
 
@@ -91,8 +97,107 @@ fn test_program_auditor(
     run_test_program_auditor(program, config, success, policy_failure)
 }
 
+#[rstest]
+#[case::array_product("array_product")]
+#[case::invalid_program("invalid_program")]
+fn test_requirements_from_raw_mir(#[case] program: &str) -> Result<(), Error> {
+    let mir = PROGRAMS.mir(program)?.into_proto().encode_to_vec();
+    let auditor_request = ProgramAuditorRequest::from_raw_mir(&mir)?;
+    let requirements = MPCProgramRequirements::from_raw_mir(&mir)?;
+    assert_eq!(auditor_request.preprocessing_requirements, requirements);
+    Ok(())
+}
+
 #[test]
 fn test_default_config_enabled() {
     let config = ProgramAuditorConfig::default();
     assert!(!config.disable);
 }
+
+#[test]
+fn test_config_builder() {
+    let preprocessing = MPCProgramRequirements::default().with_compare_elements(10);
+    let config = ProgramAuditorConfig::builder()
+        .max_memory(100)
+        .max_instructions(50)
+        .max_instruction("Addition", 20)
+        .max_instruction("MultiplicationShares", 30)
+        .max_preprocessing(preprocessing.clone())
+        .disable_policy("max_memory")
+        .disabled(true)
+        .build()
+        .unwrap();
+    assert_eq!(config.max_memory_size, 100);
+    assert_eq!(config.max_instructions, 50);
+    assert_eq!(
+        config.max_instructions_per_type,
+        [("Addition".to_string(), 20), ("MultiplicationShares".to_string(), 30)].into_iter().collect()
+    );
+    assert_eq!(config.max_preprocessing, preprocessing);
+    assert!(!config.reject_unused_inputs);
+    assert_eq!(config.disabled_policies, ["max_memory".to_string()].into_iter().collect());
+    assert!(config.disable);
+}
+
+#[rstest]
+#[case::allowed(false, true)]
+#[case::rejected(true, false)]
+fn test_unused_inputs(#[case] reject_unused_inputs: bool, #[case] success: bool) {
+    let config = ProgramAuditorConfig { reject_unused_inputs, ..good_config() };
+    let request = ProgramAuditorRequest {
+        memory_size: 1,
+        total_instructions: 1,
+        instructions: Default::default(),
+        preprocessing_requirements: MPCProgramRequirements::default(),
+        unused_inputs: vec!["my_int".to_string()],
+    };
+    match ProgramAuditor::new(config).audit(&request) {
+        Ok(()) => assert!(success),
+        Err(ProgramAuditorError::InvalidProgram(violation)) => {
+            assert!(!success);
+            assert_eq!(violation.policy, NoUnusedInputsPolicy.name());
+            assert!(violation.message.contains("my_int"));
+        }
+        Err(e) => panic!("unexpected error: {e}"),
+    }
+}
+
+#[test]
+fn test_unknown_disabled_policy() {
+    let result = ProgramAuditorConfig::builder().disable_policy("max_memory").disable_policy("max_memry").build();
+    assert_eq!(result.unwrap_err(), UnknownPolicyError("max_memry".to_string()));
+
+    let mut config = good_config();
+    config.disabled_policies.insert("unknown".to_string());
+    assert_eq!(config.validate().unwrap_err().to_string(), "unknown program auditor policy \"unknown\"");
+}
+
+#[test]
+fn test_disabled_policies() {
+    let config = ProgramAuditorConfig::builder()
+        .max_memory(10)
+        .max_instructions(10)
+        .disable_policy(MaxMemoryPolicy.name())
+        .build()
+        .unwrap();
+    let auditor = ProgramAuditor::new(config);
+    let request = ProgramAuditorRequest {
+        memory_size: 1000,
+        total_instructions: 1,
+        instructions: Default::default(),
+        preprocessing_requirements: MPCProgramRequirements::default(),
+        unused_inputs: vec![],
+    };
+
+    // The memory limit is exceeded but the policy that enforces it is disabled.
+    assert!(auditor.audit(&request).is_ok());
+
+    // Other policies still run.
+    let request = ProgramAuditorRequest { total_instructions: 1000, ..request };
+    match auditor.audit(&request) {
+        Err(ProgramAuditorError::InvalidProgram(violation)) => {
+            assert_eq!(violation.policy, MaxInstructionsPolicy.name())
+        }
+        result => panic!("unexpected audit result: {result:?}"),
+    }
+}
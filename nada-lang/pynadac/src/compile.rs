@@ -17,6 +17,9 @@ use nada_compiler_backend::{
     validators::{report::ValidationContext, Validator},
 };
 
+#[cfg(feature = "audit")]
+use program_auditor::{ProgramAuditor, ProgramAuditorRequest};
+use program_auditor::{ProgramAuditorConfig, ProgramAuditorError};
 use serde_files_utils::json::write_json;
 
 /// The output persistence options.
@@ -34,6 +37,11 @@ pub struct PersistOptions {
 pub struct CompilerOptions {
     /// Options related to program persistence.
     pub persist: PersistOptions,
+
+    /// If set, the compiled program is audited using this configuration.
+    ///
+    /// Programs are only audited when the `audit` feature, enabled by default, is on.
+    pub audit_with: Option<ProgramAuditorConfig>,
 }
 
 /// A nada compiler for python programs.
@@ -75,12 +83,14 @@ impl Compiler {
 
         let mir_json_file = self.persist_mir_json(program_name, &mir)?;
         let mir_bin_file = self.persist_mir_bin(program_name, &mir)?;
+        let audit_result = self.audit(&mir);
         let output = CompileOutput {
             mir,
             program_name: program_name.to_string(),
             mir_bin_file,
             mir_json_file,
             validation_result,
+            audit_result,
         };
         Ok(output)
     }
@@ -104,6 +114,7 @@ impl Compiler {
             mir_bin_file: None,
             mir_json_file: None,
             validation_result,
+            audit_result: None,
         };
         Ok(output)
     }
@@ -134,6 +145,19 @@ impl Compiler {
         }
     }
 
+    #[cfg(feature = "audit")]
+    fn audit(&self, mir: &ProgramMIR) -> Option<Result<(), ProgramAuditorError>> {
+        let config = self.options.audit_with.as_ref()?;
+        let result = ProgramAuditorRequest::from_mir(mir)
+            .and_then(|request| ProgramAuditor::new(config.clone()).audit(&request));
+        Some(result)
+    }
+
+    #[cfg(not(feature = "audit"))]
+    fn audit(&self, _mir: &ProgramMIR) -> Option<Result<(), ProgramAuditorError>> {
+        None
+    }
+
     fn build_file_path(&self, file_name: &str, extension: &str) -> PathBuf {
         let file_name = format!("{file_name}{extension}");
         self.target_dir.join(file_name)
@@ -156,6 +180,9 @@ pub struct CompileOutput {
 
     /// The MIR validation result
     pub validation_result: ValidationContext,
+
+    /// The program audit result, if auditing was requested in the compiler options.
+    pub audit_result: Option<Result<(), ProgramAuditorError>>,
 }
 
 fn parse_program_name(path: &str) -> Result<String> {
@@ -214,4 +241,35 @@ def nada_main():
 
         Compiler::compile_str(program_str, "test_program").unwrap();
     }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn test_compile_audit_max_memory() {
+        use crate::compile::CompilerOptions;
+        use program_auditor::{ProgramAuditorConfig, ProgramAuditorError};
+
+        let program_str = r#"
+from nada_dsl import *
+
+def nada_main():
+    party1 = Party(name="Party1")
+    my_int1 = SecretInteger(Input(name="my_int1", party=party1))
+    my_int2 = SecretInteger(Input(name="my_int2", party=party1))
+
+    return [Output(my_int1 + my_int2, "my_output", party1)]
+    "#;
+        let target_dir = tempfile::tempdir().unwrap();
+        let program_path = target_dir.path().join("pynadac_audit_max_memory.py");
+        std::fs::write(&program_path, program_str).unwrap();
+
+        let config = ProgramAuditorConfig { max_memory_size: 1, max_instructions: 1000, ..Default::default() };
+        let options = CompilerOptions { audit_with: Some(config), ..Default::default() };
+        let compiler = Compiler::with_options(target_dir.path(), options);
+        let output = compiler.compile(program_path.to_str().unwrap()).unwrap();
+
+        match output.audit_result {
+            Some(Err(ProgramAuditorError::InvalidProgram(violation))) => assert_eq!(violation.policy, "max_memory"),
+            other => panic!("expected max memory violation, got {other:?}"),
+        }
+    }
 }
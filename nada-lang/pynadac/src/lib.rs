@@ -57,13 +57,38 @@ pub enum CheckVersionError {
     #[error("missing nada-dsl version")]
     MissingVersion,
 
-    #[error("The installed nada-dsl version {0} is incompatible. This release supports nada-dsl versions {1}.*")]
+    #[error(
+        "The installed nada-dsl version {0} is incompatible. This release supports nada-dsl versions {1}.*, install one \
+         with: {}",
+        install_command(.1)
+    )]
     IncompatibleVersion(String, String),
 
     #[error("pip show command returned invalid output")]
     InvalidPipShowOutput,
 }
 
+impl CheckVersionError {
+    /// Returns the suggested remediation for this error.
+    pub fn hint(&self) -> String {
+        match self {
+            Self::MissingVersion | Self::InvalidPipShowOutput => {
+                format!("install a supported nada-dsl version with: {}", install_command(NADA_DSL_VERSION))
+            }
+            Self::IncompatibleVersion(_, supported) => {
+                format!("install a supported nada-dsl version with: {}", install_command(supported))
+            }
+        }
+    }
+}
+
+/// Returns the `pip` command that installs the latest nada-dsl release for the given minor version.
+///
+/// The compatible release specifier `~=X.Y.0` allows any `X.Y.*` release but no later minor versions.
+pub fn install_command(version: &str) -> String {
+    format!("{PIP} install 'nada_dsl~={version}.0'")
+}
+
 /// Checks that pynadac version matches the DSL version
 ///
 /// It uses `pip show` which is a reasonable expectation that
@@ -91,9 +116,19 @@ pub fn check_version_matches() -> Result<(), CheckVersionError> {
     Ok(())
 }
 
+/// Checks that pynadac version matches the DSL version, see [check_version_matches].
+///
+/// On failure the error is returned along with the suggested remediation.
+pub fn check_version_matches_with_hint() -> Result<(), (CheckVersionError, String)> {
+    check_version_matches().map_err(|e| {
+        let hint = e.hint();
+        (e, hint)
+    })
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{check_version_matches, parse_dsl_version, CheckVersionError, NADA_DSL_VERSION};
+    use crate::{check_version_matches, install_command, parse_dsl_version, CheckVersionError, NADA_DSL_VERSION};
 
     fn pip_show_output() -> String {
         format!(
@@ -115,6 +150,15 @@ Required-by:
         assert_eq!(format!("{NADA_DSL_VERSION}.3"), parse_dsl_version(&pip_show_output()))
     }
 
+    #[test]
+    fn test_incompatible_version_hint() {
+        let error = CheckVersionError::IncompatibleVersion("0.1.0".into(), NADA_DSL_VERSION.into());
+        let command = format!("pip install 'nada_dsl~={NADA_DSL_VERSION}.0'");
+        assert_eq!(install_command(NADA_DSL_VERSION), command);
+        assert!(error.hint().contains(&command), "{}", error.hint());
+        assert!(error.to_string().contains(&command), "{error}");
+    }
+
     #[test]
     fn test_compatible_nada_dsl_version() -> Result<(), CheckVersionError> {
         check_version_matches()
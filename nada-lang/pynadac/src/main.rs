@@ -28,6 +28,7 @@ fn run(args: Args) -> Result<(), Error> {
             mir_bin: true,
             mir_json: args.generate_mir_json,
         },
+        ..Default::default()
     };
     create_dir_all(&args.target_dir)
         .with_context(|| format!("failed to create target directory: {}", args.target_dir))?;
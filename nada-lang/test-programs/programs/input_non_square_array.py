@@ -0,0 +1,7 @@
+from nada_dsl import *
+
+
+def nada_main():
+    party1 = Party(name="Party1")
+    a = Array(Array(SecretInteger(Input(name="a", party=party1)), size=3), size=2)
+    return [Output(a, "my_output", party1)]
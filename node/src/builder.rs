@@ -2,7 +2,7 @@
 
 use crate::{
     channels::{ClusterChannels, DefaultClusterChannels},
-    config::ObjectStorageConfig,
+    config::{ObjectStorageConfig, StorageConfig},
     controllers::{
         compute::{ComputeApi, ComputeApiHandles, ComputeApiServices},
         leader_queries::{LeaderQueriesApi, LeaderQueriesApiServices},
@@ -17,6 +17,7 @@ use crate::{
         interceptors::{InternalServiceInterceptor, RateLimitInterceptor},
         metrics::MetricsMiddleware,
     },
+    health::{HealthChecker, NodeHealth, PreprocessingPool},
     observability::{process::ProcessMetricsCollector, PrometheusExporter},
     services::{
         auxiliary_material::{
@@ -35,7 +36,8 @@ use crate::{
         scheduling::{DefaultPreprocessingSchedulingService, PreprocessingSchedulingService},
         time::{DefaultTimeService, TimeService},
         token_dollar_conversion::{
-            HardcodedTokenDollarConversionService, TokenDollarConversionCoinGeckoService, TokenDollarConversionService,
+            HardcodedTokenDollarConversionService, PriceCache, TokenDollarConversionCoinGeckoService,
+            TokenDollarConversionService,
         },
         user_values::{DefaultUserValuesService, UserValuesService},
         uuid::DefaultUuidService,
@@ -56,6 +58,7 @@ use crate::{
             balances::{AccountBalanceRepository, SqliteAccountBalanceRepository},
             blob::{
                 BinarySerde, BlobRepository, FilesystemBlobRepository, MemoryBlobRepository, ObjectStoreRepository,
+                ReadAfterWriteRetryBlobRepository, TimeoutBlobRepository,
             },
             blob_expirations::SqliteBlobExpirationsRepository,
             nonces::{SqliteUsedNoncesRepository, UsedNoncesRepository},
@@ -90,7 +93,7 @@ use node_api::{
 };
 use node_config::{
     AuxiliaryMaterialConfig, KeyKind, MetricsConfig, PaymentsConfig, PrefundedAccount, PreprocessingConfig,
-    PrivateKeyConfig, RateLimitBucket,
+    PrivateKeyConfig, RateLimitBucket, ReadAfterWriteRetryConfig,
 };
 use object_store::{
     aws::{resolve_bucket_region, AmazonS3, AmazonS3Builder, AmazonS3ConfigKey, S3ConditionalPut},
@@ -117,7 +120,10 @@ use serde::{de::DeserializeOwned, Serialize};
 use shamir_sharing::secret_sharer::ShamirSecretSharer;
 use std::{collections::HashMap, fs, path::PathBuf, sync::Arc, time::Duration};
 use strum::IntoEnumIterator;
-use tokio::{sync::oneshot, task::JoinHandle, time::timeout};
+use tokio::{
+    task::JoinHandle,
+    time::{sleep, timeout},
+};
 use tokio_util::sync::CancellationToken;
 use tonic::{
     codegen::InterceptedService,
@@ -135,9 +141,9 @@ use user_keypair::{
     SigningKey,
 };
 
-const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(300);
+const SHUTDOWN_CANCELLATION_TIMEOUT: Duration = Duration::from_secs(30);
+const IN_FLIGHT_ACTIONS_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
-const S3_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
 const S3_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
 struct LeaderDependencies {
@@ -173,6 +179,7 @@ struct Dependencies {
     leader: Option<LeaderDependencies>,
     sqlite: SqliteDb,
     sqlite_repositories: Vec<MetricsExporterRepository>,
+    object_storage: Arc<dyn BlobRepository<u32>>,
     compute_api_handles: ComputeApiHandles,
     channels: Arc<dyn ClusterChannels>,
     cluster: Cluster,
@@ -238,21 +245,35 @@ impl NodeBuilder {
         }
         // Export metrics periodically on these repos.
         StorageMetricsExporter::spawn(dependencies.sqlite_repositories.clone());
+        let preprocessing_pool = match &dependencies.leader {
+            Some(leader) => PreprocessingPool::Offsets {
+                offsets: leader.offsets.clone(),
+                config: leader.preprocessing_config.clone(),
+            },
+            None if is_leader => PreprocessingPool::Unconfigured,
+            None => PreprocessingPool::NotLeader,
+        };
+        let health = Arc::new(HealthChecker::new(
+            dependencies.sqlite.clone(),
+            dependencies.object_storage.clone(),
+            preprocessing_pool,
+        ));
 
         info!("Using identity {user_id}");
-        let handle = Self::launch_grpc_service(config, party_id, dependencies, preprocessing_mode)?;
+        let handle = Self::launch_grpc_service(config, party_id, dependencies, health, preprocessing_mode)?;
         Ok(handle)
     }
 
-    fn build_blob_repository_backend(config: ObjectStorageConfig) -> anyhow::Result<BlobRepositoryBackend> {
-        let backend = match config {
+    fn build_blob_repository_backend(config: StorageConfig) -> anyhow::Result<BlobRepositoryBackend> {
+        let StorageConfig { object_storage, request_timeout, read_after_write_retry, .. } = config;
+        let storage = match object_storage {
             ObjectStorageConfig::AwsS3 { bucket_name, region, endpoint_url, allow_http } => {
+                let mut client_options = ClientOptions::new().with_connect_timeout(S3_CONNECT_TIMEOUT);
+                if let Some(request_timeout) = request_timeout {
+                    client_options = client_options.with_timeout(request_timeout);
+                }
                 let mut builder = AmazonS3Builder::from_env()
-                    .with_client_options(
-                        ClientOptions::new()
-                            .with_timeout(S3_OPERATION_TIMEOUT)
-                            .with_connect_timeout(S3_CONNECT_TIMEOUT),
-                    )
+                    .with_client_options(client_options)
                     .with_bucket_name(bucket_name.clone())
                     .with_conditional_put(S3ConditionalPut::ETagMatch);
 
@@ -275,24 +296,25 @@ impl NodeBuilder {
                 let repo: Box<dyn BlobRepository<u32>> = Box::new(ObjectStoreRepository::new(object_store.clone()));
                 block_on(async { repo.check_permissions().await.context("s3 permissions validation") })?;
 
-                BlobRepositoryBackend::S3 { object_store }
+                ObjectStorageBackend::S3 { object_store, read_after_write_retry }
             }
 
-            ObjectStorageConfig::InMemory => BlobRepositoryBackend::Memory,
-            ObjectStorageConfig::Filesystem { path } => BlobRepositoryBackend::Filesystem(path),
+            ObjectStorageConfig::InMemory => ObjectStorageBackend::Memory,
+            ObjectStorageConfig::Filesystem { path } => ObjectStorageBackend::Filesystem(path),
         };
-        Ok(backend)
+        Ok(BlobRepositoryBackend { storage, request_timeout })
     }
 
     fn build_dependencies(config: node_config::Config, signing_key: &SigningKey) -> anyhow::Result<Dependencies> {
-        let repo_backend = Self::build_blob_repository_backend(config.storage.object_storage)?;
+        let db_url = config.storage.db_url.clone();
+        let repo_backend = Self::build_blob_repository_backend(config.storage)?;
         let program_auditor = ProgramAuditor::new(config.program_auditor.clone());
         let leader_public_key =
             match Self::build_cluster_member(config.cluster.leader.clone())?.public_keys.authentication {
                 PublicKey::Ed25519(key) => Ed25519PublicKey::from_bytes(&key)?.into(),
                 PublicKey::Secp256k1(key) => Secp256k1PublicKey::from_bytes(&key)?.into(),
             };
-        let sqlite = block_on(async { SqliteDb::new(&config.storage.db_url).await })?;
+        let sqlite = block_on(async { SqliteDb::new(&db_url).await })?;
         let nonces_repository = Arc::new(SqliteUsedNoncesRepository::new(sqlite.clone()));
         let blob_expirations_repository = Arc::new(SqliteBlobExpirationsRepository::new(sqlite.clone()));
         let nonces = Arc::new(DefaultNonceService::new(nonces_repository.clone()));
@@ -300,25 +322,31 @@ impl NodeBuilder {
             vec![nonces_repository.clone(), blob_expirations_repository.clone()];
         let time_service = Arc::new(DefaultTimeService);
         let cluster = Self::build_cluster(config.cluster.clone())?;
-        let mut ca_cert = None;
-        if let Some(tls) = &config.runtime.grpc.tls {
-            if let Some(ca_cert_path) = &tls.ca_cert {
-                ca_cert = Some(fs::read(ca_cert_path).context("reading TLS CA certificate file")?);
-            }
-        }
+        let fixed_token_dollar_price = Decimal::from_f64(config.payments.dollar_token_conversion_fixed)
+            .ok_or(anyhow!("Invalid fixed token dollar conversion rate: Decimal cannot be from that value"))?;
         let token_dollar_conversion: Arc<dyn TokenDollarConversionService> =
             if let Some(dollar_token_conversion) = config.payments.dollar_token_conversion {
                 Arc::new(TokenDollarConversionCoinGeckoService::new(
                     dollar_token_conversion.coingecko_api_key,
                     dollar_token_conversion.coin_id,
+                    PriceCache::new(
+                        dollar_token_conversion.refresh_interval,
+                        dollar_token_conversion.max_staleness,
+                        fixed_token_dollar_price,
+                    ),
                 ))
             } else {
-                let fixed = Decimal::from_f64(config.payments.dollar_token_conversion_fixed)
-                    .ok_or(anyhow!("Invalid fixed token dollar conversion rate: Decimal cannot be from that value"))?;
-                warn!("Using fixed token dollar price ({}) because no coingecko configuration was provided", fixed);
-                Arc::new(HardcodedTokenDollarConversionService::new(fixed))
+                warn!(
+                    "Using fixed token dollar price ({}) because no coingecko configuration was provided",
+                    fixed_token_dollar_price
+                );
+                Arc::new(HardcodedTokenDollarConversionService::new(fixed_token_dollar_price))
             };
-        let channels = Arc::new(DefaultClusterChannels::new(signing_key, &cluster, ca_cert)?);
+        let channels = Arc::new(DefaultClusterChannels::new(
+            signing_key,
+            &config.cluster.members,
+            config.runtime.grpc.tls.as_ref(),
+        )?);
         let dependencies = Dependencies {
             prep_compare: repo_backend.create_preprocessing_service("prep/compare"),
             prep_division_integer_secret: repo_backend.create_preprocessing_service("prep/division_integer_secret"),
@@ -344,13 +372,20 @@ impl NodeBuilder {
                 program_auditor.clone(),
             )),
             tx_retriever: Arc::new(DefaultPaymentTransactionRetriever::new(&config.payments.rpc_endpoint)?),
-            receipts: Arc::new(DefaultReceiptsService::new(leader_public_key, time_service.clone(), nonces.clone())),
+            receipts: Arc::new(DefaultReceiptsService::new(
+                leader_public_key,
+                config.payments.receipt_ttl,
+                config.payments.clock_skew_tolerance,
+                time_service.clone(),
+                nonces.clone(),
+            )),
             token_dollar_conversion_service: token_dollar_conversion,
             nonces,
             nonces_repository,
             sqlite,
             leader: None,
             sqlite_repositories,
+            object_storage: repo_backend.create_repository().into(),
             compute_api_handles: ComputeApiHandles::default(),
             channels,
             cluster,
@@ -363,6 +398,7 @@ impl NodeBuilder {
         config: node_config::Config,
         party_id: PartyId,
         dependencies: Dependencies,
+        health: Arc<HealthChecker>,
         preprocessing_mode: PreprocessingMode,
     ) -> anyhow::Result<NodeHandle> {
         let mut server_builder = tonic::transport::Server::builder();
@@ -391,7 +427,7 @@ impl NodeBuilder {
             })
             .collect();
         let internal_interceptor = InternalServiceInterceptor::new(users.clone());
-        let rate_limit_layer = config.runtime.grpc.rate_limit.as_ref().map(|config| {
+        let rate_limit_layer = config.runtime.grpc.rate_limit.as_ref().filter(|config| config.enabled).map(|config| {
             let quota = match config.bucket {
                 RateLimitBucket::Second => Quota::per_second(config.max_per_bucket),
                 RateLimitBucket::Minute => Quota::per_minute(config.max_per_bucket),
@@ -417,6 +453,7 @@ impl NodeBuilder {
         ));
 
         let max_payload_size = config.network.max_payload_size as usize;
+        let max_program_operations = config.network.max_program_operations as usize;
 
         let preprocessing_api = PreprocessingApi::new(
             party_id.clone(),
@@ -486,10 +523,13 @@ impl NodeBuilder {
                 .max_encoding_message_size(max_payload_size),
             )
             .add_service(
-                ProgramsServer::new(ProgramsApi::new(ProgramsApiServices {
-                    programs: dependencies.programs.clone(),
-                    receipts: dependencies.receipts.clone(),
-                }))
+                ProgramsServer::new(ProgramsApi::new(
+                    ProgramsApiServices {
+                        programs: dependencies.programs.clone(),
+                        receipts: dependencies.receipts.clone(),
+                    },
+                    max_program_operations,
+                ))
                 .max_decoding_message_size(max_payload_size)
                 .max_encoding_message_size(max_payload_size),
             )
@@ -530,8 +570,9 @@ impl NodeBuilder {
             server = server
                 .add_service(
                     PaymentsServer::new(PaymentsApi::new(
-                        dependencies.compute_api_handles.general_compute.clone(),
+                        dependencies.compute_api_handles.clone(),
                         config.runtime.max_concurrent_actions,
+                        config.runtime.max_concurrent_per_operation.clone(),
                         PaymentsApiServices { payments: leader_dependencies.payments.clone() },
                         Days::new(config.payments.account_balance_expiration_days as u64),
                     ))
@@ -567,14 +608,23 @@ impl NodeBuilder {
         ExpiredValuesCleanup::spawn(dependencies.user_values.clone());
         ExpiredComputeResultsCleanup::spawn(dependencies.results.clone());
 
-        let (sender, receiver) = oneshot::channel();
+        let shutdown = ShutdownHandle::default();
+        let shutdown_token = shutdown.token.clone();
         let cancel_token = dependencies.cancel_token.clone();
+        let compute_api_handles = dependencies.compute_api_handles.clone();
+        let grace_period = config.runtime.shutdown_grace_period;
         let signal = async move {
-            if receiver.await.is_err() {
-                error!("Signal channel sender dropped");
-            }
-            info!("Cancelling operations and shutting down");
-            cancel_token.cancel();
+            shutdown_token.cancelled().await;
+            // Resolving this future stops the server from accepting new requests. The actions that are already running
+            // get some time to finish before they're cancelled.
+            info!("Waiting up to {grace_period:?} for in-flight actions to finish");
+            tokio::spawn(async move {
+                if timeout(grace_period, Self::wait_for_in_flight_actions(compute_api_handles)).await.is_err() {
+                    warn!("Timed out waiting for in-flight actions to finish");
+                }
+                info!("Cancelling operations and shutting down");
+                cancel_token.cancel();
+            });
         };
         let fut = server.serve_with_shutdown(config.runtime.grpc.bind_endpoint, signal);
         let handle = tokio::spawn(async move {
@@ -583,7 +633,20 @@ impl NodeBuilder {
             };
         });
         info!("gRPC server started");
-        Ok(NodeHandle { handle, signal: sender })
+        Ok(NodeHandle { handle, shutdown, grace_period, health })
+    }
+
+    async fn wait_for_in_flight_actions(handles: ComputeApiHandles) {
+        loop {
+            let running = handles.general_compute.lock().await.len()
+                + handles.ecdsa_dkg.lock().await.len()
+                + handles.eddsa_dkg.lock().await.len();
+            if running == 0 {
+                return;
+            }
+            info!("Waiting for {running} in-flight actions to finish");
+            sleep(IN_FLIGHT_ACTIONS_POLL_INTERVAL).await;
+        }
     }
 
     async fn prefund_keys(
@@ -704,13 +767,15 @@ impl NodeBuilder {
         let Some(preprocessing_config) = config.network.preprocessing.clone() else {
             return Ok(None);
         };
-        let PaymentsConfig { pricing, quote_ttl, receipt_ttl, minimum_add_funds_payment, .. } = config.payments.clone();
+        let PaymentsConfig { pricing, quote_ttl, receipt_ttl, clock_skew_tolerance, minimum_add_funds_payment, .. } =
+            config.payments.clone();
         let payments_service_config = PaymentsServiceConfig {
             max_payload_size: config.network.max_payload_size,
             pricing,
             preprocessing: preprocessing_config.clone(),
             quote_ttl,
             receipt_ttl,
+            clock_skew_tolerance,
             minimum_add_funds_credits: minimum_add_funds_payment.into(),
         };
         let balances_repository = Arc::new(SqliteAccountBalanceRepository::new(dependencies.sqlite.clone()));
@@ -780,10 +845,15 @@ impl NodeBuilder {
     }
 }
 
-enum BlobRepositoryBackend {
+enum ObjectStorageBackend {
     Memory,
     Filesystem(PathBuf),
-    S3 { object_store: Box<AmazonS3> },
+    S3 { object_store: Box<AmazonS3>, read_after_write_retry: Option<ReadAfterWriteRetryConfig> },
+}
+
+struct BlobRepositoryBackend {
+    storage: ObjectStorageBackend,
+    request_timeout: Option<Duration>,
 }
 
 impl BlobRepositoryBackend {
@@ -791,11 +861,23 @@ impl BlobRepositoryBackend {
     where
         T: BinarySerde + Clone,
     {
-        use BlobRepositoryBackend::*;
-        match &self {
-            Memory => Box::new(MemoryBlobRepository::default()),
-            Filesystem(path) => Box::new(FilesystemBlobRepository::new(path.clone())),
-            S3 { object_store: client } => Box::new(ObjectStoreRepository::new(client.clone())),
+        use ObjectStorageBackend::*;
+        let (repo, read_after_write_retry): (Box<dyn BlobRepository<T>>, _) = match &self.storage {
+            Memory => (Box::new(MemoryBlobRepository::default()), None),
+            Filesystem(path) => (Box::new(FilesystemBlobRepository::new(path.clone())), None),
+            S3 { object_store: client, read_after_write_retry } => {
+                (Box::new(ObjectStoreRepository::new(client.clone())), read_after_write_retry.as_ref())
+            }
+        };
+        let repo = match self.request_timeout {
+            Some(timeout) => Box::new(TimeoutBlobRepository::new(repo, timeout)),
+            None => repo,
+        };
+        match read_after_write_retry {
+            Some(ReadAfterWriteRetryConfig { attempts, delay, window }) => {
+                Box::new(ReadAfterWriteRetryBlobRepository::new(repo, *attempts, *delay, *window))
+            }
+            None => repo,
         }
     }
 
@@ -819,21 +901,140 @@ impl BlobRepositoryBackend {
 /// A handle a running instance of a node.
 pub struct NodeHandle {
     handle: JoinHandle<()>,
-    signal: oneshot::Sender<()>,
+    shutdown: ShutdownHandle,
+    grace_period: Duration,
+    health: Arc<HealthChecker>,
 }
 
 impl NodeHandle {
+    /// Get this node's readiness.
+    ///
+    /// This checks that the database and object storage are reachable and, if this node is the leader, that the
+    /// preprocessing material pool is above its generation threshold.
+    pub async fn readiness(&self) -> NodeHealth {
+        self.health.readiness().await
+    }
+
+    /// Get a handle that can be used to trigger this node's shutdown.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
     /// Shutdown this node gracefully.
+    ///
+    /// This stops accepting new requests, waits up to the configured grace period for in-flight actions to finish,
+    /// and then cancels any background tasks.
     pub async fn shutdown(self) {
         info!("Sending the shutdown signal");
-        if self.signal.send(()).is_err() {
-            error!("Shutdown signal receiver dropped");
-            return;
-        }
-        match timeout(GRACEFUL_SHUTDOWN_TIMEOUT, self.handle).await {
+        self.shutdown.trigger();
+        self.wait().await;
+    }
+
+    /// Wait for this node to stop running.
+    ///
+    /// This resolves once a shutdown has been triggered, via [`ShutdownHandle::trigger`], and the node has finished
+    /// shutting down.
+    pub async fn wait(self) {
+        match timeout(self.grace_period + SHUTDOWN_CANCELLATION_TIMEOUT, self.handle).await {
             Ok(Ok(_)) => info!("Node has shutdown"),
             Ok(Err(_)) => info!("Node has failed to shutdown"),
             Err(_) => info!("Timed out waiting for node to shutdown"),
         }
     }
 }
+
+/// A handle that allows triggering a node's shutdown.
+#[derive(Clone, Default)]
+pub struct ShutdownHandle {
+    token: CancellationToken,
+}
+
+impl ShutdownHandle {
+    /// Trigger the shutdown of the node.
+    pub fn trigger(&self) {
+        self.token.cancel();
+    }
+
+    /// Whether the shutdown has been triggered.
+    pub fn is_triggered(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::PreprocessingHealth;
+    use node_config::{
+        default_storage_read_after_write_retry, default_storage_request_timeout, Config, GrpcConfig, IdentityConfig,
+        NetworkConfig, PaymentsConfig, PreprocessingProtocolConfig, PublicKeys, RuntimeConfig, StorageConfig,
+    };
+    use program_auditor::ProgramAuditorConfig;
+
+    fn make_config(grace_period: Duration) -> Config {
+        let seed = "shutdown-test".to_string();
+        let signing_key: SigningKey = Ed25519SigningKey::from_seed(&seed).into();
+        let member = node_config::ClusterMember {
+            public_keys: PublicKeys { authentication: signing_key.public_key().as_bytes(), kind: KeyKind::Ed25519 },
+            grpc_endpoint: "http://127.0.0.1:0".to_string(),
+        };
+        Config {
+            runtime: RuntimeConfig {
+                max_concurrent_actions: 10,
+                max_concurrent_per_operation: Default::default(),
+                grpc: GrpcConfig { bind_endpoint: "127.0.0.1:0".parse().unwrap(), tls: None, rate_limit: None },
+                shutdown_grace_period: grace_period,
+            },
+            storage: StorageConfig {
+                object_storage: ObjectStorageConfig::InMemory,
+                db_url: "sqlite::memory:".into(),
+                request_timeout: default_storage_request_timeout(),
+                read_after_write_retry: default_storage_read_after_write_retry(),
+            },
+            identity: IdentityConfig { private_key: PrivateKeyConfig::Seed { seed, kind: KeyKind::Ed25519 } },
+            metrics: None,
+            tracing: None,
+            network: NetworkConfig::default(),
+            cluster: node_config::Cluster {
+                members: vec![member.clone()],
+                leader: member,
+                prime: node_config::Prime::Safe64Bits,
+                polynomial_degree: 0,
+                kappa: 0,
+            },
+            program_auditor: ProgramAuditorConfig::default(),
+            payments: PaymentsConfig { rpc_endpoint: "http://127.0.0.1:26657".into(), ..Default::default() },
+            execution_engine: Default::default(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shutdown_resolves_serve_future() {
+        let handle = NodeBuilder::new(make_config(Duration::from_secs(1))).launch().expect("launch failed");
+        let shutdown = handle.shutdown_handle();
+        assert!(!shutdown.is_triggered());
+
+        shutdown.trigger();
+        assert!(shutdown.is_triggered());
+        timeout(Duration::from_secs(10), handle.wait()).await.expect("serve future did not resolve");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fresh_node_not_ready_for_compute() {
+        let mut config = make_config(Duration::from_secs(1));
+        config.network.preprocessing = Some(PreprocessingConfig::new(PreprocessingProtocolConfig {
+            batch_size: 10,
+            generation_threshold: 100,
+            target_offset_jump: 100,
+        }));
+        let handle = NodeBuilder::new(config).launch().expect("launch failed");
+
+        let health = handle.readiness().await;
+        assert!(health.database_reachable);
+        assert!(health.object_storage_reachable);
+        assert!(matches!(health.preprocessing, PreprocessingHealth::BelowThreshold(_)));
+        assert!(health.is_ready());
+        assert!(!health.is_ready_for_compute());
+        handle.shutdown().await;
+    }
+}
@@ -6,9 +6,9 @@ use basic_types::PartyId;
 use futures::StreamExt;
 use grpc_channel::{token::TokenAuthenticator, AuthenticatedGrpcChannel, GrpcChannelConfig, TransportChannel};
 use node_api::{
-    auth::rust::{PublicKey, UserId},
+    auth::rust::UserId,
     compute::{proto::compute_client::ComputeClient, rust::ComputeStreamMessage},
-    membership::rust::Cluster,
+    membership::rust::NodeId,
     preprocessing::{
         proto::{
             preprocessing_client::PreprocessingClient,
@@ -21,6 +21,7 @@ use node_api::{
     },
     ConvertProto, TryIntoRust,
 };
+use node_config::{ClusterMember, GrpcTlsConfig};
 use std::{collections::HashMap, future::Future, iter, time::Duration};
 use tokio::{
     sync::mpsc::{channel, Receiver, Sender},
@@ -101,44 +102,36 @@ pub(crate) struct DefaultClusterChannels {
 }
 
 impl DefaultClusterChannels {
-    pub(crate) fn new(key: &SigningKey, cluster: &Cluster, ca_cert: Option<Vec<u8>>) -> anyhow::Result<Self> {
+    pub(crate) fn new(
+        key: &SigningKey,
+        members: &[ClusterMember],
+        tls: Option<&GrpcTlsConfig>,
+    ) -> anyhow::Result<Self> {
         let mut channels = HashMap::new();
         let mut parties = Vec::new();
-        for member in &cluster.members {
-            let party = match member.public_keys.authentication {
-                PublicKey::Ed25519(bytes) => Self::party_from_ed25519(&bytes)?,
-                PublicKey::Secp256k1(bytes) => Self::party_from_secp256k1(&bytes)?,
-            };
+        for member in members {
+            let party = Self::party_from_public_key(&member.public_keys.authentication);
             info!(
                 "Setting up channel to member {} (user {}) @ {}",
                 party.party_id, party.user_id, member.grpc_endpoint
             );
-            let authenticator = TokenAuthenticator::new(key.clone(), member.identity.clone(), TOKEN_EXPIRY);
-            let mut builder = GrpcChannelConfig::new(member.grpc_endpoint.clone())
+            let identity = NodeId::from(party.user_id.as_ref().to_vec());
+            let authenticator = TokenAuthenticator::new(key.clone(), identity, TOKEN_EXPIRY);
+            let channel = GrpcChannelConfig::from_cluster_member(member, tls)?
                 .authentication(authenticator)
-                .timeout(CHANNEL_TIMEOUT);
-            if let Some(cert) = ca_cert.as_ref() {
-                // This should be made configurable
-                builder = builder.ca_certificate(cert).domain("nillion.local");
-            }
-            let channel = builder.build()?;
+                .timeout(CHANNEL_TIMEOUT)
+                .build()?;
             channels.insert(party.party_id.clone(), channel);
             parties.push(party);
         }
-        let our_party = Self::party_from_ed25519(&key.public_key().as_bytes())?;
+        let our_party = Self::party_from_public_key(&key.public_key().as_bytes());
         Ok(Self { channels, parties, our_party })
     }
 
-    fn party_from_ed25519(public_key: &[u8]) -> anyhow::Result<Party> {
-        let user_id = UserId::from_bytes(public_key);
-        let party_id = PartyId::from(user_id.as_ref());
-        Ok(Party { party_id, user_id })
-    }
-
-    fn party_from_secp256k1(public_key: &[u8]) -> anyhow::Result<Party> {
+    fn party_from_public_key(public_key: &[u8]) -> Party {
         let user_id = UserId::from_bytes(public_key);
         let party_id = PartyId::from(user_id.as_ref());
-        Ok(Party { party_id, user_id })
+        Party { party_id, user_id }
     }
 
     fn party_channel(&self, party: &PartyId) -> tonic::Result<&AuthenticatedGrpcChannel> {
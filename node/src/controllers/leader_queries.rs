@@ -48,8 +48,10 @@ impl LeaderQueriesApi {
         let mut output = BTreeMap::new();
         let mut preprocessing_active = false;
         for (element, offsets) in offsets {
-            let element_config = preprocessing_config.element_config(&element);
-            let threshold = element_config.generation_threshold;
+            let threshold = preprocessing_config
+                .element_config(&element)
+                .map(|config| config.generation_threshold)
+                .unwrap_or_default();
             let available_count = offsets.available().count() as u64;
             if available_count < threshold {
                 preprocessing_active = true;
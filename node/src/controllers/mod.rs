@@ -49,6 +49,10 @@ impl From<BlobRepositoryError> for Status {
         match e {
             NotFound => Status::not_found(e.to_string()),
             AlreadyExists => Status::failed_precondition(e.to_string()),
+            Timeout(_) => {
+                error!("Blob repository operation failed: {e}");
+                Status::deadline_exceeded("storage request timed out")
+            }
             Encode(_) | Decode(_) | Io(_) | Internal(_) => {
                 error!("Blob repository operation failed: {e}");
                 Status::internal("internal error")
@@ -62,7 +66,7 @@ impl From<ReceiptVerificationError> for Status {
         use ReceiptVerificationError::*;
         match e {
             InvalidSignature => Status::invalid_argument(e.to_string()),
-            QuoteExpired | ReusedNonce => Status::failed_precondition(e.to_string()),
+            QuoteExpired | NotYetValid | ReusedNonce => Status::failed_precondition(e.to_string()),
             Internal(e) => {
                 error!("Failed to verify receipt: {e}");
                 Status::internal("internal error")
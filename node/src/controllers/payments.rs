@@ -1,6 +1,6 @@
 //! The payments gRPC API.
 
-use super::compute::ComputeHandles;
+use super::compute::ComputeApiHandles;
 use crate::{
     controllers::TraceRequest,
     services::payments::{
@@ -12,6 +12,7 @@ use async_trait::async_trait;
 use chrono::Days;
 use grpc_channel::auth::AuthenticateRequest;
 use node_api::{
+    compute::{TECDSA_DKG_PROGRAM_ID, TEDDSA_DKG_PROGRAM_ID},
     errors::{ErrorDetails, PreconditionViolation, StatusExt},
     payments::{
         proto::{self, balance::AddFundsRequest},
@@ -19,7 +20,7 @@ use node_api::{
     },
     ConvertProto, TryIntoRust,
 };
-use std::{ops::Add, sync::Arc};
+use std::{collections::HashMap, ops::Add, sync::Arc};
 use tonic::{Code, Request, Response, Status};
 use tracing::{error, info, instrument};
 use uuid::Uuid;
@@ -32,36 +33,51 @@ pub(crate) struct PaymentsApiServices {
 /// The payments API.
 pub(crate) struct PaymentsApi {
     services: PaymentsApiServices,
-    compute_handles: ComputeHandles,
+    compute_handles: ComputeApiHandles,
     max_concurrent_computes: usize,
+    max_concurrent_per_operation: HashMap<String, usize>,
     balance_expiration: Days,
 }
 
 impl PaymentsApi {
     pub(crate) fn new(
-        compute_handles: ComputeHandles,
+        compute_handles: ComputeApiHandles,
         max_concurrent_computes: usize,
+        max_concurrent_per_operation: HashMap<String, usize>,
         services: PaymentsApiServices,
         balance_expiration: Days,
     ) -> Self {
-        Self { services, compute_handles, max_concurrent_computes, balance_expiration }
+        Self { services, compute_handles, max_concurrent_computes, max_concurrent_per_operation, balance_expiration }
     }
 
     async fn validate_max_computes(&self, quote: &PriceQuote) -> tonic::Result<()> {
-        if let PriceQuoteRequest::InvokeCompute { .. } = &quote.request {
-            let compute_count = self.compute_handles.lock().await.len();
-            let max = self.max_concurrent_computes;
-            if compute_count > max {
-                info!(
-                    "Rejecting compute request because number of concurrent computes exceeds maximum: {compute_count} > {max}"
-                );
-                return Err(Status::unavailable("too many compute operations running, try again later"));
-            } else {
-                info!("Allowing execution because we have {compute_count} <= {max} active computes");
+        if let PriceQuoteRequest::InvokeCompute(request) = &quote.request {
+            // The handles only go away once the action finishes, so they track the actions that are still running.
+            let general_compute = self.compute_handles.general_compute.lock().await.len();
+            let ecdsa_dkg = self.compute_handles.ecdsa_dkg.lock().await.len();
+            let eddsa_dkg = self.compute_handles.eddsa_dkg.lock().await.len();
+            let (operation, running) = match request.program_id.as_str() {
+                TECDSA_DKG_PROGRAM_ID => ("ecdsa_dkg", ecdsa_dkg),
+                TEDDSA_DKG_PROGRAM_ID => ("eddsa_dkg", eddsa_dkg),
+                _ => ("compute", general_compute),
+            };
+            if let Some(max) = self.max_concurrent_per_operation.get(operation) {
+                Self::validate_running_actions(operation, running, *max)?;
             }
+            let total = general_compute.saturating_add(ecdsa_dkg).saturating_add(eddsa_dkg);
+            Self::validate_running_actions("any", total, self.max_concurrent_computes)?;
         }
         Ok(())
     }
+
+    fn validate_running_actions(operation: &str, running: usize, max: usize) -> tonic::Result<()> {
+        if running >= max {
+            info!("Rejecting compute request because {running} {operation} actions are running, maximum is {max}");
+            return Err(Status::unavailable("too many compute operations running, try again later"));
+        }
+        info!("Allowing compute request because {running} {operation} actions are running, maximum is {max}");
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -182,7 +198,7 @@ impl From<PaymentVerificationError> for Status {
     fn from(e: PaymentVerificationError) -> Self {
         use PaymentVerificationError::*;
         match e {
-            QuoteExpired | ReusedNonce => Self::failed_precondition(e.to_string()),
+            QuoteExpired | QuoteNotYetValid | ReusedNonce => Self::failed_precondition(e.to_string()),
             NotEnoughFunds => {
                 let mut details = ErrorDetails::new();
                 details.set_precondition_failure(vec![PreconditionViolation::new(
@@ -287,13 +303,21 @@ mod tests {
 
     struct ServiceBuilder {
         payments: MockPaymentService,
-        compute_handles: ComputeHandles,
+        compute_handles: ComputeApiHandles,
+        max_concurrent_computes: usize,
+        max_concurrent_per_operation: HashMap<String, usize>,
         balance_expiration: Days,
     }
 
     impl Default for ServiceBuilder {
         fn default() -> Self {
-            Self { payments: Default::default(), compute_handles: Default::default(), balance_expiration: Days::new(1) }
+            Self {
+                payments: Default::default(),
+                compute_handles: Default::default(),
+                max_concurrent_computes: usize::MAX,
+                max_concurrent_per_operation: Default::default(),
+                balance_expiration: Days::new(1),
+            }
         }
     }
 
@@ -301,7 +325,8 @@ mod tests {
         fn build(self) -> PaymentsApi {
             PaymentsApi::new(
                 self.compute_handles,
-                0,
+                self.max_concurrent_computes,
+                self.max_concurrent_per_operation,
                 PaymentsApiServices { payments: Arc::new(self.payments) },
                 self.balance_expiration,
             )
@@ -382,7 +407,7 @@ mod tests {
             .preprocessing_requirement(PreprocessingElement::Compare, 100)
             .build();
         let request = quote.receipt_request(&tx_hash, &keypair);
-        let mut builder = ServiceBuilder::default();
+        let mut builder = ServiceBuilder { max_concurrent_computes: 1, ..Default::default() };
 
         let quote = PriceQuote::try_decode(&request.signed_quote.quote).expect("invalid quote");
         builder
@@ -395,6 +420,84 @@ mod tests {
         // pretend like there's one running
         builder
             .compute_handles
+            .general_compute
+            .lock()
+            .await
+            .insert(Uuid::new_v4(), StateMachineHandle { init_sender: channel(1).0, join_handle: spawn(async {}) });
+
+        let api = builder.build();
+        let response =
+            api.payment_receipt(Request::new(request.into_proto())).await.expect_err("processing request succeed");
+        assert_eq!(response.code(), Code::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn too_many_computes_for_operation() {
+        let keypair = SigningKey::generate_secp256k1();
+        let tx_hash = "hash".to_string();
+        let compute_quote = PriceQuoteBuilder::new(PriceQuoteRequest::InvokeCompute(InvokeCompute {
+            program_id: "foo".into(),
+            values_payload_size: 0,
+        }))
+        .build();
+        let dkg_quote = PriceQuoteBuilder::new(PriceQuoteRequest::InvokeCompute(InvokeCompute {
+            program_id: TECDSA_DKG_PROGRAM_ID.into(),
+            values_payload_size: 0,
+        }))
+        .build();
+        let compute_request = compute_quote.receipt_request(&tx_hash, &keypair);
+        let dkg_request = dkg_quote.receipt_request(&tx_hash, &keypair);
+        let mut builder = ServiceBuilder {
+            max_concurrent_computes: 10,
+            max_concurrent_per_operation: HashMap::from([("compute".to_string(), 1)]),
+            ..Default::default()
+        };
+        builder
+            .payments
+            .expect_verify_decode_quote()
+            .with(eq(compute_request.signed_quote.clone()))
+            .return_once(|signed_quote| Ok(PriceQuote::try_decode(&signed_quote.quote).expect("invalid quote")));
+        builder.expect_payment_verification(&dkg_request, OperationMetadata::PoolStatus);
+
+        // pretend like there's one compute running
+        builder
+            .compute_handles
+            .general_compute
+            .lock()
+            .await
+            .insert(Uuid::new_v4(), StateMachineHandle { init_sender: channel(1).0, join_handle: spawn(async {}) });
+
+        // The second compute is rejected until the running one finishes, but other operations proceed.
+        let api = builder.build();
+        let response = api
+            .payment_receipt(Request::new(compute_request.into_proto()))
+            .await
+            .expect_err("processing request succeed");
+        assert_eq!(response.code(), Code::Unavailable);
+        api.payment_receipt(Request::new(dkg_request.into_proto())).await.expect("processing request failed");
+    }
+
+    #[tokio::test]
+    async fn running_dkg_counts_towards_max_computes() {
+        let keypair = SigningKey::generate_secp256k1();
+        let tx_hash = "hash".to_string();
+        let quote = PriceQuoteBuilder::new(PriceQuoteRequest::InvokeCompute(InvokeCompute {
+            program_id: "foo".into(),
+            values_payload_size: 0,
+        }))
+        .build();
+        let request = quote.receipt_request(&tx_hash, &keypair);
+        let mut builder = ServiceBuilder { max_concurrent_computes: 1, ..Default::default() };
+        builder
+            .payments
+            .expect_verify_decode_quote()
+            .with(eq(request.signed_quote.clone()))
+            .return_once(|signed_quote| Ok(PriceQuote::try_decode(&signed_quote.quote).expect("invalid quote")));
+
+        // pretend like there's one dkg running
+        builder
+            .compute_handles
+            .ecdsa_dkg
             .lock()
             .await
             .insert(Uuid::new_v4(), StateMachineHandle { init_sender: channel(1).0, join_handle: spawn(async {}) });
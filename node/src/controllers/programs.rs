@@ -8,7 +8,10 @@ use crate::{
 };
 use async_trait::async_trait;
 use grpc_channel::auth::AuthenticateRequest;
-use nada_compiler_backend::mir::{proto::ConvertProto, ProgramMIR};
+use nada_compiler_backend::mir::{
+    proto::{ConvertProto, LimitedDecodeError},
+    ProgramMIR,
+};
 use node_api::{
     payments::rust::{OperationMetadata, StoreProgram},
     programs::{
@@ -36,12 +39,13 @@ pub(crate) struct ProgramsApiServices {
 /// The programs API.
 pub(crate) struct ProgramsApi {
     services: ProgramsApiServices,
+    max_program_operations: usize,
 }
 
 impl ProgramsApi {
     /// Construct a new programs service.
-    pub(crate) fn new(services: ProgramsApiServices) -> Self {
-        Self { services }
+    pub(crate) fn new(services: ProgramsApiServices, max_program_operations: usize) -> Self {
+        Self { services, max_program_operations }
     }
 
     fn validate_hash(contents: &[u8], expected_hash: &[u8]) -> tonic::Result<()> {
@@ -84,8 +88,13 @@ impl proto::programs_server::Programs for ProgramsApi {
         Self::validate_name(&name)?;
         Self::validate_hash(&request.program, &contents_sha256)?;
 
-        let program = ProgramMIR::try_decode(&request.program)
-            .map_err(|_| Status::invalid_argument("malformed program (invalid sdk version?)"))?;
+        let program =
+            ProgramMIR::try_decode_limited(&request.program, self.max_program_operations).map_err(|e| match e {
+                LimitedDecodeError::Proto(_) => Status::invalid_argument("malformed program (invalid sdk version?)"),
+                LimitedDecodeError::TooManyOperations { .. } => {
+                    Status::invalid_argument(format!("program is too large: {e}"))
+                }
+            })?;
 
         let program_id = ProgramId::Uploaded { user_id, name, sha256: contents_sha256 };
         let request = ProgramAuditorRequest::from_mir(&program)
@@ -120,7 +129,12 @@ mod tests {
         services::{programs::MockProgramService, receipts::MockReceiptsService},
     };
     use mockall::predicate::always;
-    use node_api::{auth::rust::UserId, payments::rust::ProgramMetadata, ConvertProto};
+    use node_api::{
+        auth::rust::UserId,
+        payments::rust::{ProgramMetadata, Receipt},
+        ConvertProto,
+    };
+    use node_config::default_max_program_operations;
     use proto::programs_server::Programs;
     use std::{cell::RefCell, rc::Rc};
     use test_programs::PROGRAMS;
@@ -133,18 +147,19 @@ mod tests {
 
     impl ServiceBuilder {
         fn build(self) -> ProgramsApi {
-            ProgramsApi::new(ProgramsApiServices {
-                programs: Arc::new(self.programs),
-                receipts: Arc::new(self.receipts),
-            })
+            self.build_with_max_operations(default_max_program_operations() as usize)
+        }
+
+        fn build_with_max_operations(self, max_program_operations: usize) -> ProgramsApi {
+            ProgramsApi::new(
+                ProgramsApiServices { programs: Arc::new(self.programs), receipts: Arc::new(self.receipts) },
+                max_program_operations,
+            )
         }
     }
 
-    #[tokio::test]
-    async fn store_program() {
-        let program = PROGRAMS.metadata("simple").unwrap().raw_mir();
-        let user_id = UserId::from_bytes("bob");
-        let receipt = ReceiptBuilder::new(StoreProgram {
+    fn make_receipt(program: &[u8]) -> Receipt {
+        ReceiptBuilder::new(StoreProgram {
             metadata: ProgramMetadata {
                 program_size: 0,
                 memory_size: 0,
@@ -153,10 +168,17 @@ mod tests {
                 preprocessing_requirements: Default::default(),
                 auxiliary_material_requirements: Default::default(),
             },
-            contents_sha256: Sha256::digest(&program).to_vec(),
+            contents_sha256: Sha256::digest(program).to_vec(),
             name: "test".into(),
         })
-        .build();
+        .build()
+    }
+
+    #[tokio::test]
+    async fn store_program() {
+        let program = PROGRAMS.metadata("simple").unwrap().raw_mir();
+        let user_id = UserId::from_bytes("bob");
+        let receipt = make_receipt(&program);
         let mut builder = ServiceBuilder::default();
         builder.receipts.expect_verify_payment_receipt().return_once(move |_| Ok(receipt));
         builder.programs.expect_audit().return_once(|_| Ok(()));
@@ -179,6 +201,25 @@ mod tests {
         assert_eq!(response.program_id, *program_id.borrow().as_ref().unwrap().to_string());
     }
 
+    #[tokio::test]
+    async fn store_program_too_many_operations() {
+        let program = PROGRAMS.metadata("simple").unwrap().raw_mir();
+        let user_id = UserId::from_bytes("bob");
+        let receipt = make_receipt(&program);
+        let mut builder = ServiceBuilder::default();
+        builder.receipts.expect_verify_payment_receipt().return_once(move |_| Ok(receipt));
+        builder.programs.expect_audit().never();
+        builder.programs.expect_upsert().never();
+
+        let api = builder.build_with_max_operations(2);
+        let request =
+            Request::new(StoreProgramRequest { program, signed_receipt: empty_signed_receipt() }.into_proto())
+                .authenticated(user_id);
+        let status = api.store_program(request).await.expect_err("program stored");
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("program is too large"), "unexpected message: {}", status.message());
+    }
+
     #[test]
     fn name_validation() {
         ProgramsApi::validate_name("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890+.:_-")
@@ -1,12 +1,18 @@
 use async_trait::async_trait;
+use bytes::Bytes;
 use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
 use metrics::prelude::*;
 use once_cell::sync::Lazy;
-use std::time::{Duration, Instant};
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
 use tonic::{
-    body::BoxBody,
+    body::{boxed, BoxBody},
     codegen::http::{Request, Response},
-    Code,
+    Code, Status,
 };
 use tonic_middleware::{Middleware, ServiceBound};
 use tracing::info;
@@ -38,6 +44,10 @@ where
 {
     async fn call(&self, req: Request<BoxBody>, mut service: S) -> Result<Response<BoxBody>, S::Error> {
         let uri = req.uri().path().to_string();
+        let req = req.map(|body| {
+            let uri = uri.clone();
+            boxed(SizeReportingBody::new(body, move |size| METRICS.observe_payload_size(&uri, "request", size)))
+        });
         let start_time = Instant::now();
         let response = service.call(req).await?;
         let elapsed = start_time.elapsed();
@@ -66,13 +76,79 @@ where
             };
             METRICS.observe_request_duration(&uri, status_code, elapsed);
             info!("Request to {uri} processed in {elapsed:?}, status code: {status_code}");
+            let response = response.map(|body| {
+                boxed(SizeReportingBody::new(body, move |size| METRICS.observe_payload_size(&uri, "response", size)))
+            });
+            return Ok(response);
         }
         Ok(response)
     }
 }
 
+/// A body that reports its size, in bytes, once it's been read.
+///
+/// The size is reported when the end of the body is reached or, if it's dropped before that, when dropped as long as
+/// any data was read. Bodies that are never read, e.g. the ones for unimplemented methods, are not reported.
+struct SizeReportingBody<F: FnOnce(u64)> {
+    inner: BoxBody,
+    size: u64,
+    on_end: Option<F>,
+}
+
+impl<F: FnOnce(u64)> SizeReportingBody<F> {
+    fn new(inner: BoxBody, on_end: F) -> Self {
+        Self { inner, size: 0, on_end: Some(on_end) }
+    }
+
+    fn report(&mut self) {
+        if let Some(on_end) = self.on_end.take() {
+            on_end(self.size);
+        }
+    }
+}
+
+impl<F: FnOnce(u64) + Unpin> Body for SizeReportingBody<F> {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Status>>> {
+        let this = self.get_mut();
+        let frame = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    this.size = this.size.saturating_add(data.len() as u64);
+                }
+                if this.inner.is_end_stream() {
+                    this.report();
+                }
+            }
+            Some(Err(_)) => (),
+            None => this.report(),
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<F: FnOnce(u64)> Drop for SizeReportingBody<F> {
+    fn drop(&mut self) {
+        if self.size > 0 {
+            self.report();
+        }
+    }
+}
+
 struct Metrics {
     request_duration: MaybeMetric<Histogram<Duration>>,
+    payload_size: MaybeMetric<Histogram<f64>>,
 }
 
 impl Default for Metrics {
@@ -84,8 +160,15 @@ impl Default for Metrics {
             TimingBuckets::sub_ten_seconds(),
         )
         .into();
+        let payload_size = Histogram::new(
+            "grpc_payload_size_bytes",
+            "Size of each grpc request and response payload in bytes",
+            &["method", "direction"],
+            &[1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8],
+        )
+        .into();
 
-        Self { request_duration }
+        Self { request_duration, payload_size }
     }
 }
 
@@ -93,4 +176,70 @@ impl Metrics {
     fn observe_request_duration(&self, uri: &str, status_code: &str, elapsed: Duration) {
         self.request_duration.with_labels([("method", uri), ("status_code", status_code)]).observe(&elapsed);
     }
+
+    fn observe_payload_size(&self, uri: &str, direction: &str, size: u64) {
+        self.payload_size.with_labels([("method", uri), ("direction", direction)]).observe(&(size as f64));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::{BodyExt, Full};
+    use metrics::metrics::MetricsRegistry;
+    use std::{
+        collections::HashMap,
+        convert::Infallible,
+        sync::{Arc, Mutex},
+    };
+
+    fn reporting_body(payload: Vec<u8>) -> (SizeReportingBody<impl FnOnce(u64) + Unpin>, Arc<Mutex<Option<u64>>>) {
+        let reported = Arc::new(Mutex::new(None));
+        let body = SizeReportingBody::new(boxed(Full::new(Bytes::from(payload))), {
+            let reported = reported.clone();
+            move |size| *reported.lock().unwrap() = Some(size)
+        });
+        (body, reported)
+    }
+
+    #[tokio::test]
+    async fn payload_size_reported() {
+        let (body, reported) = reporting_body(vec![42; 1024]);
+        let payload = body.collect().await.unwrap().to_bytes();
+        assert_eq!(payload.len(), 1024);
+        assert_eq!(*reported.lock().unwrap(), Some(1024));
+    }
+
+    #[test]
+    fn unread_payload_not_reported() {
+        let (body, reported) = reporting_body(vec![42; 1024]);
+        drop(body);
+        assert_eq!(*reported.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn middleware_reports_payload_sizes() {
+        let registry = metrics::initialize(HashMap::new()).unwrap();
+        let service = tower::service_fn(|request: Request<BoxBody>| async move {
+            let payload = request.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(payload.len(), 1234);
+            Ok::<_, Infallible>(Response::new(boxed(Full::new(Bytes::from(vec![42; 567])))))
+        });
+        let request =
+            Request::builder().uri("/test.Service/Method").body(boxed(Full::new(Bytes::from(vec![42; 1234])))).unwrap();
+
+        let response = MetricsMiddleware.call(request, service).await.unwrap();
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(payload.len(), 567);
+
+        let output = registry.encode_metrics().unwrap();
+        for expected in [
+            r#"grpc_payload_size_bytes_sum{direction="request",method="/test.Service/Method"} 1234"#,
+            r#"grpc_payload_size_bytes_count{direction="request",method="/test.Service/Method"} 1"#,
+            r#"grpc_payload_size_bytes_sum{direction="response",method="/test.Service/Method"} 567"#,
+            r#"grpc_payload_size_bytes_count{direction="response",method="/test.Service/Method"} 1"#,
+        ] {
+            assert!(output.contains(expected), "{expected} not found in {output}");
+        }
+    }
 }
@@ -0,0 +1,228 @@
+//! Node health reporting.
+
+use crate::{
+    services::offsets::ElementOffsetsService,
+    storage::{repositories::blob::BlobRepository, sqlite::SqliteDb},
+    PreprocessingConfigExt,
+};
+use node_api::preprocessing::rust::PreprocessingElement;
+use node_config::PreprocessingConfig;
+use std::{sync::Arc, time::Duration};
+use tokio::time::timeout;
+use tracing::warn;
+
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A summary of the health of a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeHealth {
+    /// Whether the database is reachable.
+    pub database_reachable: bool,
+
+    /// Whether the object storage is reachable.
+    pub object_storage_reachable: bool,
+
+    /// The state of the preprocessing material pool.
+    pub preprocessing: PreprocessingHealth,
+}
+
+impl NodeHealth {
+    /// Whether the node is ready to serve requests.
+    pub fn is_ready(&self) -> bool {
+        self.database_reachable && self.object_storage_reachable
+    }
+
+    /// Whether the node is ready to run computations.
+    pub fn is_ready_for_compute(&self) -> bool {
+        self.is_ready() && self.preprocessing.is_ready()
+    }
+}
+
+/// The state of the preprocessing material pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreprocessingHealth {
+    /// This node is not the leader so it doesn't keep track of the preprocessing material pool.
+    NotTracked,
+
+    /// This node is the leader but preprocessing generation is not configured.
+    NotConfigured,
+
+    /// Every preprocessing element is available above its generation threshold.
+    Ready,
+
+    /// These preprocessing elements are available below their generation threshold.
+    BelowThreshold(Vec<PreprocessingElement>),
+
+    /// The preprocessing offsets could not be looked up.
+    Unknown,
+}
+
+impl PreprocessingHealth {
+    /// Whether there's enough preprocessing material to run computations.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::NotTracked | Self::Ready)
+    }
+}
+
+/// The preprocessing pool information the health checker has access to.
+pub(crate) enum PreprocessingPool {
+    /// This node is not the leader.
+    NotLeader,
+
+    /// This node is the leader but has no preprocessing configuration.
+    Unconfigured,
+
+    /// This node is the leader and tracks offsets for every element.
+    Offsets { offsets: Arc<dyn ElementOffsetsService>, config: PreprocessingConfig },
+}
+
+/// Computes the health of a node.
+pub(crate) struct HealthChecker {
+    sqlite: SqliteDb,
+    object_storage: Arc<dyn BlobRepository<u32>>,
+    preprocessing: PreprocessingPool,
+}
+
+impl HealthChecker {
+    pub(crate) fn new(
+        sqlite: SqliteDb,
+        object_storage: Arc<dyn BlobRepository<u32>>,
+        preprocessing: PreprocessingPool,
+    ) -> Self {
+        Self { sqlite, object_storage, preprocessing }
+    }
+
+    /// Compute the node's readiness.
+    ///
+    /// Every check is bounded by a short timeout and none of them performs any writes.
+    pub(crate) async fn readiness(&self) -> NodeHealth {
+        let (database_reachable, object_storage_reachable, preprocessing) =
+            tokio::join!(self.check_database(), self.check_object_storage(), self.check_preprocessing());
+        NodeHealth { database_reachable, object_storage_reachable, preprocessing }
+    }
+
+    async fn check_database(&self) -> bool {
+        match timeout(HEALTH_CHECK_TIMEOUT, self.sqlite.ping()).await {
+            Ok(Ok(())) => true,
+            Ok(Err(e)) => {
+                warn!("Database is not reachable: {e}");
+                false
+            }
+            Err(_) => {
+                warn!("Timed out checking database health");
+                false
+            }
+        }
+    }
+
+    async fn check_object_storage(&self) -> bool {
+        match timeout(HEALTH_CHECK_TIMEOUT, self.object_storage.check_reachable()).await {
+            Ok(Ok(())) => true,
+            Ok(Err(e)) => {
+                warn!("Object storage is not reachable: {e}");
+                false
+            }
+            Err(_) => {
+                warn!("Timed out checking object storage health");
+                false
+            }
+        }
+    }
+
+    async fn check_preprocessing(&self) -> PreprocessingHealth {
+        let (offsets, config) = match &self.preprocessing {
+            PreprocessingPool::NotLeader => return PreprocessingHealth::NotTracked,
+            PreprocessingPool::Unconfigured => return PreprocessingHealth::NotConfigured,
+            PreprocessingPool::Offsets { offsets, config } => (offsets, config),
+        };
+        let offsets = match timeout(HEALTH_CHECK_TIMEOUT, offsets.all_offsets()).await {
+            Ok(Ok(offsets)) => offsets,
+            Ok(Err(e)) => {
+                warn!("Failed to fetch preprocessing offsets: {e}");
+                return PreprocessingHealth::Unknown;
+            }
+            Err(_) => {
+                warn!("Timed out fetching preprocessing offsets");
+                return PreprocessingHealth::Unknown;
+            }
+        };
+        let depleted: Vec<_> = offsets
+            .into_iter()
+            .filter(|(element, offsets)| {
+                let threshold =
+                    config.element_config(element).map(|config| config.generation_threshold).unwrap_or_default();
+                (offsets.available().count() as u64) < threshold
+            })
+            .map(|(element, _)| element)
+            .collect();
+        if depleted.is_empty() { PreprocessingHealth::Ready } else { PreprocessingHealth::BelowThreshold(depleted) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        services::offsets::MockElementOffsetsService,
+        storage::repositories::{blob::MemoryBlobRepository, offsets::PreprocessingOffsets},
+    };
+    use node_config::PreprocessingProtocolConfig;
+    use std::collections::BTreeMap;
+
+    fn make_offsets(element: PreprocessingElement, committed: u64, latest: u64) -> PreprocessingOffsets {
+        PreprocessingOffsets {
+            element,
+            target: latest,
+            latest,
+            committed,
+            next_batch_id: 0,
+            deleted_offset: 0,
+            delete_candidate_offset: 0,
+        }
+    }
+
+    async fn make_checker(available: u64) -> HealthChecker {
+        let mut offsets = MockElementOffsetsService::default();
+        offsets.expect_all_offsets().returning(move || {
+            Ok(BTreeMap::from([
+                (PreprocessingElement::Compare, make_offsets(PreprocessingElement::Compare, 10, 100)),
+                (
+                    PreprocessingElement::Modulo,
+                    make_offsets(PreprocessingElement::Modulo, 10, 10_u64.wrapping_add(available)),
+                ),
+            ]))
+        });
+        let config = PreprocessingConfig::new(PreprocessingProtocolConfig {
+            batch_size: 10,
+            generation_threshold: 50,
+            target_offset_jump: 10,
+        });
+        HealthChecker::new(
+            SqliteDb::in_memory().await.expect("failed to create db"),
+            Arc::new(MemoryBlobRepository::default()),
+            PreprocessingPool::Offsets { offsets: Arc::new(offsets), config },
+        )
+    }
+
+    #[tokio::test]
+    async fn preprocessing_above_threshold() {
+        let health = make_checker(50).await.readiness().await;
+        assert_eq!(
+            health,
+            NodeHealth {
+                database_reachable: true,
+                object_storage_reachable: true,
+                preprocessing: PreprocessingHealth::Ready
+            }
+        );
+        assert!(health.is_ready_for_compute());
+    }
+
+    #[tokio::test]
+    async fn preprocessing_below_threshold() {
+        let health = make_checker(49).await.readiness().await;
+        assert_eq!(health.preprocessing, PreprocessingHealth::BelowThreshold(vec![PreprocessingElement::Modulo]));
+        assert!(health.is_ready());
+        assert!(!health.is_ready_for_compute());
+    }
+}
@@ -19,6 +19,7 @@ pub mod builder;
 pub(crate) mod channels;
 pub mod controllers;
 pub(crate) mod grpc;
+pub mod health;
 pub mod observability;
 pub mod services;
 pub(crate) mod stateful;
@@ -28,31 +29,88 @@ pub use node_config as config;
 
 use node_api::preprocessing::rust::PreprocessingElement;
 
+/// The name of the config field of the preprocessing elements that have a dedicated one.
+const ELEMENT_CONFIG_FIELDS: [(PreprocessingElement, &str); 9] = [
+    (PreprocessingElement::Compare, "compare"),
+    (PreprocessingElement::DivisionSecretDivisor, "division_integer_secret"),
+    (PreprocessingElement::Modulo, "modulo"),
+    (PreprocessingElement::EqualityPublicOutput, "public_output_equality"),
+    (PreprocessingElement::TruncPr, "truncpr"),
+    (PreprocessingElement::Trunc, "trunc"),
+    (PreprocessingElement::EqualitySecretOutput, "equals_integer_secret"),
+    (PreprocessingElement::RandomInteger, "random_integer"),
+    (PreprocessingElement::RandomBoolean, "random_boolean"),
+];
+
+/// Get the name of the protocol an element's config is looked up by.
+///
+/// Elements that don't have a dedicated field are looked up by their own name, e.g. `Compare`, which makes
+/// [config::PreprocessingConfig::protocol] fall back to the extra protocols.
+fn element_config_name(
+    fields: &[(PreprocessingElement, &'static str)],
+    element: &PreprocessingElement,
+) -> &'static str {
+    fields
+        .iter()
+        .find_map(|(field_element, field)| (field_element == element).then_some(*field))
+        .unwrap_or(element.name())
+}
+
 pub(crate) trait PreprocessingConfigExt {
-    /// Get the batch size for a preprocessing element.
-    fn batch_size(&self, element: &PreprocessingElement) -> u64;
+    /// Get the batch size for a preprocessing element, if it's configured.
+    fn batch_size(&self, element: &PreprocessingElement) -> Option<u64>;
 
-    /// Get the config for a prepreocessing element.
-    fn element_config(&self, element: &PreprocessingElement) -> &config::PreprocessingProtocolConfig;
+    /// Get the config for a prepreocessing element, if it's configured.
+    fn element_config(&self, element: &PreprocessingElement) -> Option<&config::PreprocessingProtocolConfig>;
 }
 
 impl PreprocessingConfigExt for config::PreprocessingConfig {
-    fn batch_size(&self, element: &PreprocessingElement) -> u64 {
-        let config = self.element_config(element);
-        config.batch_size
+    fn batch_size(&self, element: &PreprocessingElement) -> Option<u64> {
+        let config = self.element_config(element)?;
+        Some(config.batch_size)
     }
 
-    fn element_config(&self, element: &PreprocessingElement) -> &config::PreprocessingProtocolConfig {
-        match element {
-            PreprocessingElement::Compare => &self.compare,
-            PreprocessingElement::DivisionSecretDivisor => &self.division_integer_secret,
-            PreprocessingElement::Modulo => &self.modulo,
-            PreprocessingElement::EqualityPublicOutput => &self.public_output_equality,
-            PreprocessingElement::TruncPr => &self.truncpr,
-            PreprocessingElement::Trunc => &self.trunc,
-            PreprocessingElement::EqualitySecretOutput => &self.equals_integer_secret,
-            PreprocessingElement::RandomInteger => &self.random_integer,
-            PreprocessingElement::RandomBoolean => &self.random_boolean,
+    fn element_config(&self, element: &PreprocessingElement) -> Option<&config::PreprocessingProtocolConfig> {
+        self.protocol(element_config_name(&ELEMENT_CONFIG_FIELDS, element))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashSet, ptr};
+
+    #[test]
+    fn preprocessing_elements_map_to_config_fields() {
+        let config = config::PreprocessingConfig::default();
+        let protocols = config.protocols();
+        let mut mapped_fields = HashSet::new();
+        for element in PreprocessingElement::all() {
+            let element_config =
+                config.element_config(&element).unwrap_or_else(|| panic!("{} has no config", element.name()));
+            let (field, _) = protocols
+                .iter()
+                .find(|(_, protocol)| ptr::eq(*protocol, element_config))
+                .unwrap_or_else(|| panic!("{} not mapped to a config field", element.name()));
+            assert!(mapped_fields.insert(*field), "{field} mapped by more than one element");
         }
+        let all_fields: HashSet<_> = protocols.iter().map(|(field, _)| *field).collect();
+        assert_eq!(mapped_fields, all_fields, "orphaned config fields");
+    }
+
+    #[test]
+    fn element_without_field_uses_extra_protocol() {
+        let custom = config::PreprocessingProtocolConfig { batch_size: 42, ..Default::default() };
+        let mut config = config::PreprocessingConfig::default();
+        config.extra.insert("Compare".to_string(), custom);
+
+        // Without a dedicated field the element is looked up by its name in the extra protocols.
+        let name = element_config_name(&[], &PreprocessingElement::Compare);
+        assert_eq!(name, "Compare");
+        assert_eq!(config.protocol(name).map(|config| config.batch_size), Some(42));
+        assert!(config.protocol(element_config_name(&[], &PreprocessingElement::Modulo)).is_none());
+
+        // Elements with a dedicated field ignore it.
+        assert_eq!(config.batch_size(&PreprocessingElement::Compare), Some(0));
     }
 }
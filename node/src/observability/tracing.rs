@@ -6,13 +6,15 @@ use std::{
     io,
     path::{Path, PathBuf},
 };
+use tracing::{span, subscriber::Interest, Metadata, Subscriber};
 use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
 use tracing_subscriber::{
     fmt::{
         format::{FmtSpan, Format, Json, JsonFields},
-        Layer,
+        Layer, MakeWriter,
     },
-    layer::SubscriberExt,
+    layer::{self, Filter, Layer as _, SubscriberExt},
+    registry::LookupSpan,
     util::SubscriberInitExt,
     EnvFilter,
 };
@@ -37,35 +39,237 @@ impl Default for TracingConsumer {
 impl TracingConsumer {
     /// Set up tracing.
     pub fn new(config: TracingConfig) -> Result<Self, Error> {
-        let TracingConfig { json_path, stdout } = config;
-        let (json_layer, json_guard) = Self::setup_json_layer(json_path)?;
+        let (subscriber, consumer) = Self::build(config)?;
+        subscriber.init();
+        Ok(consumer)
+    }
+
+    /// Build the subscriber for a tracing configuration without installing it.
+    fn build(config: TracingConfig) -> Result<(impl Subscriber + Send + Sync, Self), Error> {
+        let TracingConfig { json_path, json_lines, stdout, sample_ratio, include_targets, exclude_targets } = config;
+        let (json_layer, json_guard) = Self::setup_json_layer(json_path, json_lines)?;
         let flat = match stdout {
             true => Some(tracing_subscriber::fmt::layer().with_writer(io::stdout)),
             false => None,
         };
+        let filter = SamplingFilter { sample_ratio: sample_ratio.unwrap_or(1.0), include_targets, exclude_targets };
 
-        let registry = tracing_subscriber::registry().with(EnvFilter::from_default_env()).with(json_layer).with(flat);
-        registry.init();
-        Ok(Self { _json_appender_guard: json_guard })
+        let subscriber = tracing_subscriber::registry()
+            .with(EnvFilter::from_default_env())
+            .with(layer::Layer::and_then(json_layer, flat).with_filter(filter));
+        Ok((subscriber, Self { _json_appender_guard: json_guard }))
     }
 
-    fn setup_json_layer<S>(json_path: Option<PathBuf>) -> Result<(Option<JsonLayer<S>>, Option<WorkerGuard>), Error> {
+    fn setup_json_layer<S>(
+        json_path: Option<PathBuf>,
+        json_lines: bool,
+    ) -> Result<(Option<JsonLayer<S>>, Option<WorkerGuard>), Error> {
         if let Some(json_path) = json_path {
             let json_log = Path::new(&json_path);
 
-            let appender = tracing_appender::rolling::never(
-                json_log.parent().with_context(|| format!("failed to start log on file {:#?}", json_path))?,
-                json_log.file_name().with_context(|| format!("failed to start log on file {:#?}", json_path))?,
-            );
+            let appender = if json_log.is_dir() {
+                tracing_appender::rolling::daily(json_log, JSON_LOG_FILE_PREFIX)
+            } else {
+                tracing_appender::rolling::never(
+                    json_log.parent().with_context(|| format!("failed to start log on file {:#?}", json_path))?,
+                    json_log.file_name().with_context(|| format!("failed to start log on file {:#?}", json_path))?,
+                )
+            };
 
             let (non_blocking_appender, guard) = tracing_appender::non_blocking(appender);
-            let json_layer = tracing_subscriber::fmt::layer()
-                .json()
-                .with_span_events(FmtSpan::CLOSE)
-                .with_writer(non_blocking_appender);
-            Ok((Some(json_layer), Some(guard)))
+            Ok((Some(json_layer(non_blocking_appender, json_lines)), Some(guard)))
         } else {
             Ok((None, None))
         }
     }
 }
+
+/// The prefix of the daily rotated JSON trace files when the JSON path is a directory.
+const JSON_LOG_FILE_PREFIX: &str = "node.json.log";
+
+/// Build a layer that writes spans and events as JSON, one per line.
+///
+/// When `json_lines` is set, event fields are flattened into the top level object of each line.
+fn json_layer<S, W>(writer: W, json_lines: bool) -> Layer<S, JsonFields, Format<Json>, W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .flatten_event(json_lines)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(writer)
+}
+
+/// A filter that samples traces at their root span and filters out targets.
+///
+/// Nested spans and events follow the decision taken for the span they're in, so traces are either recorded
+/// entirely or not at all.
+struct SamplingFilter {
+    sample_ratio: f64,
+    include_targets: Vec<String>,
+    exclude_targets: Vec<String>,
+}
+
+/// Marks a span that was sampled, along with every span and event within it.
+struct Sampled;
+
+impl SamplingFilter {
+    fn target_enabled(&self, target: &str) -> bool {
+        let included = self.include_targets.is_empty()
+            || self.include_targets.iter().any(|prefix| target.starts_with(prefix.as_str()));
+        included && !self.exclude_targets.iter().any(|prefix| target.starts_with(prefix.as_str()))
+    }
+
+    fn sample(&self) -> bool {
+        self.sample_ratio >= 1.0 || rand::random::<f64>() < self.sample_ratio
+    }
+}
+
+impl<S> Filter<S> for SamplingFilter
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn enabled(&self, metadata: &Metadata<'_>, cx: &layer::Context<'_, S>) -> bool {
+        if !self.target_enabled(metadata.target()) {
+            return false;
+        }
+        if cx.current_span().id().is_none() {
+            // Only new root spans are sampled, events outside of a span are always kept.
+            return !metadata.is_span() || self.sample();
+        }
+        // The context is filtered so this is the innermost span this filter enabled, skipping the ones whose target
+        // was filtered out.
+        cx.lookup_current().is_some_and(|span| span.extensions().get::<Sampled>().is_some())
+    }
+
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, cx: layer::Context<'_, S>) {
+        if let Some(span) = cx.span(id) {
+            span.extensions_mut().insert(Sampled);
+        }
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        match self.target_enabled(metadata.target()) {
+            true => Interest::sometimes(),
+            false => Interest::never(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::{error, error_span, info, info_span};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_lines_output() {
+        let buffer = SharedBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(json_layer(move || writer.clone(), true));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = info_span!("request", user = "alice");
+            let _entered = span.enter();
+            info!(count = 3, "first");
+            info!("second");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> =
+            output.lines().map(|line| serde_json::from_str(line).expect("invalid JSON line")).collect();
+        // Two events and the span close.
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["message"], "first");
+        assert_eq!(lines[0]["count"], 3);
+        assert_eq!(lines[1]["message"], "second");
+    }
+
+    fn sampled_lines(filter: SamplingFilter, f: impl FnOnce()) -> Vec<serde_json::Value> {
+        let buffer = SharedBuffer::default();
+        let writer = buffer.clone();
+        let subscriber =
+            tracing_subscriber::registry().with(json_layer(move || writer.clone(), true).with_filter(filter));
+        tracing::subscriber::with_default(subscriber, f);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        output.lines().map(|line| serde_json::from_str(line).expect("invalid JSON line")).collect()
+    }
+
+    #[test]
+    fn consumer_with_json_sampling_and_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("traces.json");
+        let config = TracingConfig {
+            json_path: Some(json_path.clone()),
+            json_lines: true,
+            stdout: true,
+            sample_ratio: Some(1.0),
+            include_targets: vec!["node".into()],
+            exclude_targets: vec![],
+        };
+        let (subscriber, consumer) = TracingConsumer::build(config).unwrap();
+        // The environment filter only lets errors through by default.
+        tracing::subscriber::with_default(subscriber, || {
+            let span = error_span!(target: "node::test", "request");
+            let _entered = span.enter();
+            error!(target: "node::test", "kept");
+            error!(target: "other", "dropped");
+        });
+        // Dropping the consumer flushes the JSON appender.
+        drop(consumer);
+
+        let output = std::fs::read_to_string(json_path).unwrap();
+        let lines: Vec<serde_json::Value> =
+            output.lines().map(|line| serde_json::from_str(line).expect("invalid JSON line")).collect();
+        // The kept event and the request span close.
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["message"], "kept");
+        assert_eq!(lines[1]["span"]["name"], "request");
+    }
+
+    #[test]
+    fn unsampled_traces_dropped() {
+        let filter = SamplingFilter { sample_ratio: 0.0, include_targets: vec![], exclude_targets: vec![] };
+        let lines = sampled_lines(filter, || {
+            let span = info_span!("request");
+            let _entered = span.enter();
+            info!("inside");
+            let child = info_span!("child");
+            let _entered = child.enter();
+            info!("nested");
+        });
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn sampled_traces_skip_excluded_spans() {
+        let filter =
+            SamplingFilter { sample_ratio: 1.0, include_targets: vec![], exclude_targets: vec!["excluded".into()] };
+        let lines = sampled_lines(filter, || {
+            let span = info_span!("request");
+            let _entered = span.enter();
+            let excluded = info_span!(target: "excluded", "hidden");
+            let _entered = excluded.enter();
+            info!("nested");
+        });
+        // The event and the request span close, the excluded span doesn't show up.
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["message"], "nested");
+        assert_eq!(lines[1]["span"]["name"], "request");
+    }
+}
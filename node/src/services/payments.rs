@@ -163,6 +163,9 @@ pub(crate) enum PaymentVerificationError {
     #[error("quote expired")]
     QuoteExpired,
 
+    #[error("quote not yet valid")]
+    QuoteNotYetValid,
+
     #[error("nonce in payment doens't match nonce in quote")]
     NonceMismatch,
 
@@ -298,6 +301,7 @@ pub(crate) struct PaymentsServiceConfig {
     pub(crate) preprocessing: PreprocessingConfig,
     pub(crate) quote_ttl: Duration,
     pub(crate) receipt_ttl: Duration,
+    pub(crate) clock_skew_tolerance: Duration,
     pub(crate) minimum_add_funds_credits: Decimal,
 }
 
@@ -372,7 +376,9 @@ impl DefaultPaymentService {
     fn validate_requirements(&self, requirements: &[PreprocessingRequirement]) -> Result<(), QuoteError> {
         for element_requirement in requirements {
             let PreprocessingRequirement { element, count } = element_requirement;
-            let config = self.config.preprocessing.element_config(element);
+            let Some(config) = self.config.preprocessing.element_config(element) else {
+                return Err(QuoteError::UnsatisfiablePreprocessingRequirements(*element, 0));
+            };
             // Note that in reality we have slightly more than this but this is the "safe bet" that
             // we will eventually generate for sure.
             if *count > config.generation_threshold {
@@ -404,6 +410,8 @@ impl DefaultPaymentService {
                 total_instructions: operation.metadata.instruction_count,
                 instructions: operation.metadata.instructions.clone(),
                 preprocessing_requirements: Self::convert_requirements(&operation.metadata.preprocessing_requirements),
+                // The program isn't available when quoting so its inputs can't be checked.
+                unused_inputs: Vec::new(),
             };
 
             self.dependencies
@@ -537,7 +545,9 @@ impl DefaultPaymentService {
         quote: PriceQuote,
         ctx: &mut TransactionContext<'a>,
     ) -> Result<OperationMetadata, PaymentVerificationError> {
-        let nonce = ExpireableNonce::new_quote(Nonce(quote.nonce), quote.expires_at);
+        // Keep the nonce around for as long as the quote can still be accepted.
+        let nonce =
+            ExpireableNonce::new_quote(Nonce(quote.nonce), quote.expires_at.add(self.config.clock_skew_tolerance));
         let metadata = match quote.request {
             PriceQuoteRequest::PoolStatus => OperationMetadata::PoolStatus,
             PriceQuoteRequest::RetrievePermissions(request) => OperationMetadata::RetrievePermissions(request),
@@ -576,13 +586,13 @@ impl DefaultPaymentService {
         info!("Assigning offsets {offsets:?} to request {}", hex::encode(&nonce.nonce.0));
         let offsets = offsets
             .into_iter()
-            .map(|(element, range)| SelectedPreprocessingOffsets {
-                element,
-                start: range.start,
-                end: range.end,
-                batch_size: self.config.preprocessing.batch_size(&element),
+            .map(|(element, range)| {
+                let batch_size = self.config.preprocessing.batch_size(&element).ok_or_else(|| {
+                    PaymentVerificationError::Internal(format!("no preprocessing config for {element}"))
+                })?;
+                Ok(SelectedPreprocessingOffsets { element, start: range.start, end: range.end, batch_size })
             })
-            .collect();
+            .collect::<Result<_, PaymentVerificationError>>()?;
         let auxiliary_materials = auxiliary_materials_requirements
             .into_iter()
             .map(|r| SelectedAuxiliaryMaterial { material: r.material, version: r.version })
@@ -610,7 +620,7 @@ impl PaymentService for DefaultPaymentService {
 
     fn verify_decode_quote(&self, signed_quote: SignedQuote) -> Result<PriceQuote, PaymentVerificationError> {
         let _timer = METRICS.operation_timer("verify_decode_quote");
-        let signature = Signature::from(signed_quote.signature);
+        let signature = Signature::new(self.signing_key.kind(), signed_quote.signature);
         self.signing_key
             .public_key()
             .verify(&signature, &signed_quote.quote)
@@ -629,10 +639,14 @@ impl PaymentService for DefaultPaymentService {
     ) -> Result<OperationMetadata, PaymentVerificationError> {
         let _timer = METRICS.operation_timer("verify_payment");
         let now = self.dependencies.time_service.current_time();
-        if quote.expires_at < now {
+        if quote.expires_at.add(self.config.clock_skew_tolerance) < now {
             info!("Rejecting expired quote: {now} vs {}", quote.expires_at);
             return Err(PaymentVerificationError::QuoteExpired);
         }
+        if quote.expires_at > now.add(self.config.quote_ttl).add(self.config.clock_skew_tolerance) {
+            info!("Rejecting quote issued in the future: {now} vs {}", quote.expires_at);
+            return Err(PaymentVerificationError::QuoteNotYetValid);
+        }
         let tx = self.fetch_transaction(&tx_hash).await?;
 
         if tx.nonce.0 != quote.nonce {
@@ -958,6 +972,7 @@ mod test {
             }),
             quote_ttl: Duration::from_secs(60),
             receipt_ttl: Duration::from_secs(60),
+            clock_skew_tolerance: Duration::from_secs(5),
             minimum_add_funds_credits: 1.into(),
         }
     }
@@ -1052,6 +1067,21 @@ mod test {
         assert!(matches!(result, Err(PaymentVerificationError::NonceMismatch)));
     }
 
+    #[tokio::test]
+    async fn reject_quote_issued_beyond_clock_skew_tolerance() {
+        let now = Utc::now();
+        let mut quote = default_quote();
+        // Issued 10 seconds from now, more than the tolerated skew.
+        quote.expires_at = now.add(Duration::from_secs(70));
+
+        let mut time_service = MockTimeService::default();
+        time_service.expect_current_time().return_once(move || now);
+
+        let service = ServiceBuilder { time_service, ..Default::default() }.build();
+        let result = service.verify_payment(quote, "my-hash".to_string()).await;
+        assert!(matches!(result, Err(PaymentVerificationError::QuoteNotYetValid)));
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn reject_underpayment() {
@@ -1223,7 +1253,10 @@ mod test {
         builder
             .used_nonces_repo
             .expect_insert()
-            .with(eq(ExpireableNonce::new_quote(Nonce(quote.nonce.clone()), quote.expires_at.clone())), always())
+            .with(
+                eq(ExpireableNonce::new_quote(Nonce(quote.nonce.clone()), quote.expires_at + Duration::from_secs(5))),
+                always(),
+            )
             .return_once(|_, _| Ok(()));
         builder.build().deduct_payment_from_balance(quote, &user_id).await.expect("deduct failed");
     }
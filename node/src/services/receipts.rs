@@ -10,7 +10,7 @@ use node_api::{
     payments::rust::{Receipt, SignedReceipt},
     ConvertProto,
 };
-use std::sync::Arc;
+use std::{ops::Add, sync::Arc, time::Duration};
 use tracing::info;
 use user_keypair::{PublicKey, Signature};
 
@@ -30,6 +30,9 @@ pub(crate) enum ReceiptVerificationError {
     #[error("quote expired")]
     QuoteExpired,
 
+    #[error("receipt not yet valid")]
+    NotYetValid,
+
     #[error("nonce already used")]
     ReusedNonce,
 
@@ -49,6 +52,8 @@ impl From<RecordNonceError> for ReceiptVerificationError {
 
 pub(crate) struct DefaultReceiptsService {
     leader_public_key: PublicKey,
+    receipt_ttl: Duration,
+    clock_skew_tolerance: Duration,
     time_service: Arc<dyn TimeService>,
     nonce_service: Arc<dyn NonceService>,
 }
@@ -56,17 +61,19 @@ pub(crate) struct DefaultReceiptsService {
 impl DefaultReceiptsService {
     pub(crate) fn new(
         leader_public_key: PublicKey,
+        receipt_ttl: Duration,
+        clock_skew_tolerance: Duration,
         time_service: Arc<dyn TimeService>,
         nonce_service: Arc<dyn NonceService>,
     ) -> Self {
-        Self { leader_public_key, time_service, nonce_service }
+        Self { leader_public_key, receipt_ttl, clock_skew_tolerance, time_service, nonce_service }
     }
 }
 
 #[async_trait]
 impl ReceiptsService for DefaultReceiptsService {
     async fn verify_payment_receipt(&self, signed_receipt: SignedReceipt) -> Result<Receipt, ReceiptVerificationError> {
-        let signature = Signature::from(signed_receipt.signature);
+        let signature = Signature::new(self.leader_public_key.kind(), signed_receipt.signature);
         self.leader_public_key
             .verify(&signature, &signed_receipt.receipt)
             .map_err(|_| ReceiptVerificationError::InvalidSignature)?;
@@ -75,9 +82,15 @@ impl ReceiptsService for DefaultReceiptsService {
 
         let nonce = Nonce(receipt.identifier.clone());
         info!("Marking nonce {nonce} as used");
-        self.nonce_service.record_nonce(&ExpireableNonce::new_receipt(nonce, receipt.expires_at)).await?;
-        if receipt.expires_at < self.time_service.current_time() {
+        // Keep the nonce around for as long as the receipt can still be accepted.
+        let nonce_expires_at = receipt.expires_at.add(self.clock_skew_tolerance);
+        self.nonce_service.record_nonce(&ExpireableNonce::new_receipt(nonce, nonce_expires_at)).await?;
+        let now = self.time_service.current_time();
+        if receipt.expires_at.add(self.clock_skew_tolerance) < now {
             Err(ReceiptVerificationError::QuoteExpired)
+        } else if receipt.expires_at > now.add(self.receipt_ttl).add(self.clock_skew_tolerance) {
+            // The receipt was issued further in the future than the tolerated skew.
+            Err(ReceiptVerificationError::NotYetValid)
         } else {
             Ok(receipt)
         }
@@ -91,11 +104,12 @@ mod tests {
     use chrono::Utc;
     use mockall::predicate::eq;
     use node_api::{payments::rust::OperationMetadata, Message};
-    use std::time::Duration;
     use user_keypair::SigningKey;
 
     struct ServiceBuilder {
         leader_public_key: PublicKey,
+        receipt_ttl: Duration,
+        clock_skew_tolerance: Duration,
         time_service: MockTimeService,
         nonce_service: MockNonceService,
     }
@@ -104,6 +118,8 @@ mod tests {
         fn build(self) -> DefaultReceiptsService {
             DefaultReceiptsService::new(
                 self.leader_public_key,
+                self.receipt_ttl,
+                self.clock_skew_tolerance,
                 Arc::new(self.time_service),
                 Arc::new(self.nonce_service),
             )
@@ -116,6 +132,8 @@ mod tests {
             time_service.expect_current_time().returning(|| Utc::now());
             Self {
                 leader_public_key: SigningKey::generate_secp256k1().public_key(),
+                receipt_ttl: Duration::from_secs(60),
+                clock_skew_tolerance: Duration::from_secs(5),
                 time_service,
                 nonce_service: Default::default(),
             }
@@ -136,13 +154,104 @@ mod tests {
         builder
             .nonce_service
             .expect_record_nonce()
-            .with(eq(ExpireableNonce::new_receipt(Nonce(nonce.clone()), expires_at)))
+            .with(eq(ExpireableNonce::new_receipt(Nonce(nonce.clone()), expires_at + Duration::from_secs(5))))
             .return_once(|_| Ok(()));
         let service = builder.build();
         let decoded_receipt = service.verify_payment_receipt(signed_receipt).await.expect("validation failed");
         assert_eq!(decoded_receipt, receipt);
     }
 
+    #[tokio::test]
+    async fn verify_payment_receipt_within_clock_skew_tolerance() {
+        let keypair = SigningKey::generate_secp256k1();
+        let nonce = vec![1, 2, 3];
+        let now = Utc::now();
+        let expires_at = now - Duration::from_secs(3);
+        let receipt = Receipt { identifier: nonce.clone(), metadata: OperationMetadata::PoolStatus, expires_at };
+        let serialized_receipt = receipt.clone().into_proto().encode_to_vec();
+        let signature = keypair.sign(&serialized_receipt).into();
+        let signed_receipt = SignedReceipt { receipt: serialized_receipt, signature };
+
+        let mut time_service = MockTimeService::default();
+        time_service.expect_current_time().return_once(move || now);
+        let mut builder = ServiceBuilder {
+            leader_public_key: keypair.public_key(),
+            clock_skew_tolerance: Duration::from_secs(5),
+            time_service,
+            ..Default::default()
+        };
+        builder.nonce_service.expect_record_nonce().return_once(|_| Ok(()));
+        let service = builder.build();
+        let decoded_receipt = service.verify_payment_receipt(signed_receipt).await.expect("validation failed");
+        assert_eq!(decoded_receipt, receipt);
+    }
+
+    #[tokio::test]
+    async fn verify_payment_receipt_beyond_clock_skew_tolerance() {
+        let keypair = SigningKey::generate_secp256k1();
+        let now = Utc::now();
+        let expires_at = now - Duration::from_secs(10);
+        let receipt = Receipt { identifier: vec![1, 2, 3], metadata: OperationMetadata::PoolStatus, expires_at };
+        let serialized_receipt = receipt.into_proto().encode_to_vec();
+        let signature = keypair.sign(&serialized_receipt).into();
+        let signed_receipt = SignedReceipt { receipt: serialized_receipt, signature };
+
+        let mut time_service = MockTimeService::default();
+        time_service.expect_current_time().return_once(move || now);
+        let mut builder = ServiceBuilder {
+            leader_public_key: keypair.public_key(),
+            clock_skew_tolerance: Duration::from_secs(5),
+            time_service,
+            ..Default::default()
+        };
+        builder.nonce_service.expect_record_nonce().return_once(|_| Ok(()));
+        let service = builder.build();
+        let result = service.verify_payment_receipt(signed_receipt).await;
+        assert!(matches!(result, Err(ReceiptVerificationError::QuoteExpired)));
+    }
+
+    #[tokio::test]
+    async fn verify_payment_receipt_issued_within_clock_skew_tolerance() {
+        let keypair = SigningKey::generate_secp256k1();
+        let now = Utc::now();
+        // Issued 3 seconds from now by a node whose clock is ahead of ours.
+        let expires_at = now + Duration::from_secs(63);
+        let receipt = Receipt { identifier: vec![1, 2, 3], metadata: OperationMetadata::PoolStatus, expires_at };
+        let serialized_receipt = receipt.clone().into_proto().encode_to_vec();
+        let signature = keypair.sign(&serialized_receipt).into();
+        let signed_receipt = SignedReceipt { receipt: serialized_receipt, signature };
+
+        let mut time_service = MockTimeService::default();
+        time_service.expect_current_time().return_once(move || now);
+        let mut builder =
+            ServiceBuilder { leader_public_key: keypair.public_key(), time_service, ..Default::default() };
+        builder.nonce_service.expect_record_nonce().return_once(|_| Ok(()));
+        let service = builder.build();
+        let decoded_receipt = service.verify_payment_receipt(signed_receipt).await.expect("validation failed");
+        assert_eq!(decoded_receipt, receipt);
+    }
+
+    #[tokio::test]
+    async fn verify_payment_receipt_issued_beyond_clock_skew_tolerance() {
+        let keypair = SigningKey::generate_secp256k1();
+        let now = Utc::now();
+        // Issued 10 seconds from now, more than the tolerated skew.
+        let expires_at = now + Duration::from_secs(70);
+        let receipt = Receipt { identifier: vec![1, 2, 3], metadata: OperationMetadata::PoolStatus, expires_at };
+        let serialized_receipt = receipt.into_proto().encode_to_vec();
+        let signature = keypair.sign(&serialized_receipt).into();
+        let signed_receipt = SignedReceipt { receipt: serialized_receipt, signature };
+
+        let mut time_service = MockTimeService::default();
+        time_service.expect_current_time().return_once(move || now);
+        let mut builder =
+            ServiceBuilder { leader_public_key: keypair.public_key(), time_service, ..Default::default() };
+        builder.nonce_service.expect_record_nonce().return_once(|_| Ok(()));
+        let service = builder.build();
+        let result = service.verify_payment_receipt(signed_receipt).await;
+        assert!(matches!(result, Err(ReceiptVerificationError::NotYetValid)));
+    }
+
     #[tokio::test]
     async fn verify_payment_receipt_invalid_signature() {
         let receipt = Receipt {
@@ -10,7 +10,7 @@ use reqwest::Client as HttpClient;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::{collections::HashMap, time::Duration};
-use tokio::sync::Mutex;
+use tokio::{sync::Mutex, time::Instant};
 use tracing::info;
 
 const SIMPLE_PRICE_URL: &str = "https://pro-api.coingecko.com/api/v3/simple/price";
@@ -33,39 +33,75 @@ pub trait TokenDollarConversionService: Send + Sync + 'static {
     async fn token_dollar_price(&self) -> Result<Decimal, TokenDollarConversionError>;
 }
 
+/// The last known token price.
+///
+/// The price is refreshed at most once per refresh interval. If a refresh fails, the last known price keeps being
+/// used until it's older than the maximum staleness, after which the fallback price is used instead.
+pub struct PriceCache {
+    refresh_interval: Duration,
+    max_staleness: Duration,
+    fallback_price: Decimal,
+    last_refresh: Option<Instant>,
+    last_price: Option<(Instant, Decimal)>,
+}
+
+impl PriceCache {
+    pub fn new(refresh_interval: Duration, max_staleness: Duration, fallback_price: Decimal) -> Self {
+        Self { refresh_interval, max_staleness, fallback_price, last_refresh: None, last_price: None }
+    }
+
+    /// Returns true if the price should be refreshed.
+    fn needs_refresh(&self, now: Instant) -> bool {
+        self.last_refresh
+            .map_or(true, |last_refresh| now.saturating_duration_since(last_refresh) >= self.refresh_interval)
+    }
+
+    /// Returns the last known price, or the fallback one if it's too old.
+    fn price(&self, now: Instant) -> Decimal {
+        match self.last_price {
+            Some((fetched_at, price)) if now.saturating_duration_since(fetched_at) <= self.max_staleness => price,
+            _ => self.fallback_price,
+        }
+    }
+
+    /// Updates the cache with the result of a refresh and returns the price to be used.
+    fn update(&mut self, now: Instant, result: Result<Decimal, TokenDollarConversionError>) -> Decimal {
+        self.last_refresh = Some(now);
+        match result {
+            Ok(price) => {
+                self.last_price = Some((now, price));
+                price
+            }
+            Err(e) => {
+                let price = self.price(now);
+                warn!("Failed to refresh token price, using {price}: {e}");
+                price
+            }
+        }
+    }
+}
+
 /// Token Dollar Conversion CoinGecko service.
 pub struct TokenDollarConversionCoinGeckoService {
     http_client: HttpClient,
     coingecko_api_key: String,
     coin_id: String,
     simple_price_url: &'static str,
-    last_check_and_value: Mutex<(tokio::time::Instant, Decimal)>,
+    cache: Mutex<PriceCache>,
 }
 
 impl TokenDollarConversionCoinGeckoService {
-    pub fn new(coingecko_api_key: String, coin_id: String) -> Self {
+    pub fn new(coingecko_api_key: String, coin_id: String, cache: PriceCache) -> Self {
         Self {
             http_client: HttpClient::new(),
             coingecko_api_key,
             coin_id,
             simple_price_url: SIMPLE_PRICE_URL,
-            #[allow(clippy::arithmetic_side_effects)]
-            last_check_and_value: Mutex::new((tokio::time::Instant::now() - Duration::from_secs(61), Decimal::from(0))),
+            cache: Mutex::new(cache),
         }
     }
-}
-
-#[async_trait::async_trait]
-impl TokenDollarConversionService for TokenDollarConversionCoinGeckoService {
-    async fn token_dollar_price(&self) -> Result<Decimal, TokenDollarConversionError> {
-        let now = tokio::time::Instant::now();
-        let mut last_check_and_value = self.last_check_and_value.lock().await;
-
-        #[allow(clippy::arithmetic_side_effects)]
-        if now - last_check_and_value.0 < Duration::from_secs(60) {
-            return Ok(last_check_and_value.1);
-        }
 
+    async fn fetch_price(&self) -> Result<Decimal, TokenDollarConversionError> {
         let params = [("ids", self.coin_id.as_str()), ("vs_currencies", "usd")];
         info!("Fetching token price from CoinGecko");
         let timer = METRICS.price_query_timer();
@@ -101,12 +137,23 @@ impl TokenDollarConversionService for TokenDollarConversionCoinGeckoService {
         }
 
         info!("Token price from CoinGecko: {price}");
-
-        *last_check_and_value = (now, price);
         Ok(price)
     }
 }
 
+#[async_trait::async_trait]
+impl TokenDollarConversionService for TokenDollarConversionCoinGeckoService {
+    async fn token_dollar_price(&self) -> Result<Decimal, TokenDollarConversionError> {
+        let now = Instant::now();
+        let mut cache = self.cache.lock().await;
+        if !cache.needs_refresh(now) {
+            return Ok(cache.price(now));
+        }
+        let result = self.fetch_price().await;
+        Ok(cache.update(now, result))
+    }
+}
+
 /// A conversion service that uses a hardcoded price.
 ///
 /// This is only used in devnets and testing networks.
@@ -181,12 +228,46 @@ mod test {
             coingecko_api_key,
             coin_id,
             simple_price_url: DEMO_SIMPLE_PRICE_URL,
-            last_check_and_value: Mutex::new((tokio::time::Instant::now() - Duration::from_secs(61), Decimal::from(0))),
+            cache: Mutex::new(PriceCache::new(Duration::from_secs(60), Duration::from_secs(60), Decimal::from(0))),
         };
         let price = service.token_dollar_price().await.unwrap();
 
         assert!(price > Decimal::from(0));
     }
 
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn cached_price_on_failed_refresh() {
+        let fixed = Decimal::from(1);
+        let mut cache = PriceCache::new(Duration::from_secs(60), Duration::from_secs(300), fixed);
+        let start = Instant::now();
+        assert!(cache.needs_refresh(start));
+        assert_eq!(cache.update(start, Ok(Decimal::from(5))), Decimal::from(5));
+
+        let now = start + Duration::from_secs(30);
+        assert!(!cache.needs_refresh(now));
+        assert_eq!(cache.price(now), Decimal::from(5));
+
+        // Within the staleness window the last known price is used.
+        let now = start + Duration::from_secs(120);
+        assert!(cache.needs_refresh(now));
+        let error = TokenDollarConversionError::Internal("unavailable".into());
+        assert_eq!(cache.update(now, Err(error.clone())), Decimal::from(5));
+
+        // Beyond it we fall back to the fixed price.
+        let now = start + Duration::from_secs(301);
+        assert_eq!(cache.update(now, Err(error)), fixed);
+    }
+
+    #[test]
+    fn fallback_price_without_refresh() {
+        let fixed = Decimal::from(1);
+        let mut cache = PriceCache::new(Duration::from_secs(60), Duration::from_secs(300), fixed);
+        let now = Instant::now();
+        let error = TokenDollarConversionError::Internal("unavailable".into());
+        assert_eq!(cache.update(now, Err(error)), fixed);
+        assert!(!cache.needs_refresh(now));
+    }
+
     const DEMO_SIMPLE_PRICE_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
 }
@@ -7,7 +7,6 @@ use futures::future;
 use node_api::preprocessing::rust::{CleanupUsedElementsRequest, PreprocessingElement};
 use node_config::{PreprocessingConfig, PreprocessingProtocolConfig};
 use std::{sync::Arc, time::Duration};
-use strum::IntoEnumIterator;
 use tokio::time::{sleep, timeout};
 use tracing::{error, info, instrument, warn};
 
@@ -27,10 +26,13 @@ impl UsedPreprocessingCleanup {
         offsets: Arc<dyn ElementOffsetsService>,
         config: PreprocessingConfig,
     ) {
-        for element in PreprocessingElement::iter() {
+        for element in PreprocessingElement::all() {
+            let Some(config) = config.element_config(&element).cloned() else {
+                warn!("No preprocessing config for {element}, not cleaning it up");
+                continue;
+            };
             let channels = channels.clone();
             let offsets = offsets.clone();
-            let config = config.element_config(&element).clone();
             tokio::spawn(async move {
                 let cleanup = UsedPreprocessingCleanup { element, channels, offsets, config };
                 cleanup.run().await
@@ -9,7 +9,6 @@ use futures::future;
 use node_api::preprocessing::rust::{GeneratePreprocessingRequest, PreprocessingElement};
 use node_config::{PreprocessingConfig, PreprocessingProtocolConfig};
 use std::{collections::HashMap, iter, sync::Arc, time::Duration};
-use strum::IntoEnumIterator;
 use tokio::{
     sync::watch::{channel, Receiver, Sender},
     time::{sleep, timeout},
@@ -71,9 +70,12 @@ impl PreprocessingScheduler {
         cancel_token: CancellationToken,
     ) -> SchedulerHandle {
         let mut senders = HashMap::new();
-        for element in PreprocessingElement::iter() {
+        for element in PreprocessingElement::all() {
+            let Some(config) = config.element_config(&element).cloned() else {
+                warn!("No preprocessing config for {element}, not scheduling it");
+                continue;
+            };
             let (sender, receiver) = channel(());
-            let config = config.element_config(&element).clone();
             let services = services.clone();
             let cancel_token = cancel_token.clone();
             let channels = channels.clone();
@@ -8,16 +8,20 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::{
     collections::HashMap,
     fmt::Debug,
+    future::Future,
     io::{self, ErrorKind},
     marker::PhantomData,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 use tokio::{
     fs::{self},
     io::AsyncWriteExt,
     sync::Mutex,
+    time::{sleep, timeout, Instant},
 };
+use tracing::debug;
 
 fn bincode_options() -> impl bincode::Options {
     #[allow(clippy::arithmetic_side_effects)]
@@ -71,6 +75,11 @@ pub(crate) trait BlobRepository<T>: Send + Sync + 'static {
 
     /// Checks the permissions on the underlying storage.
     async fn check_permissions(&self) -> Result<(), BlobRepositoryError>;
+
+    /// Checks that the underlying storage is reachable.
+    ///
+    /// Unlike [BlobRepository::check_permissions], this performs no writes and is cheap enough to be called often.
+    async fn check_reachable(&self) -> Result<(), BlobRepositoryError>;
 }
 
 pub(crate) struct MemoryBlobRepository<T>(Arc<Mutex<HashMap<String, T>>>);
@@ -112,6 +121,10 @@ impl<T: BinarySerde + Clone> BlobRepository<T> for MemoryBlobRepository<T> {
     async fn check_permissions(&self) -> Result<(), BlobRepositoryError> {
         Ok(())
     }
+
+    async fn check_reachable(&self) -> Result<(), BlobRepositoryError> {
+        Ok(())
+    }
 }
 
 /// A filesystem based blob repository.
@@ -179,6 +192,16 @@ impl<T: BinarySerde> BlobRepository<T> for FilesystemBlobRepository<T> {
     async fn check_permissions(&self) -> Result<(), BlobRepositoryError> {
         Ok(())
     }
+
+    async fn check_reachable(&self) -> Result<(), BlobRepositoryError> {
+        // The root directory is lazily created on the first write so it not existing yet is fine.
+        match fs::metadata(&self.0).await {
+            Ok(metadata) if metadata.is_dir() => Ok(()),
+            Ok(_) => Err(BlobRepositoryError::Internal(format!("{} is not a directory", self.0.display()))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(BlobRepositoryError::Io(e)),
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -248,6 +271,139 @@ impl<T: BinarySerde> BlobRepository<T> for ObjectStoreRepository<T> {
         self.object_store.delete(&path).await?;
         Ok(())
     }
+
+    async fn check_reachable(&self) -> Result<(), BlobRepositoryError> {
+        let path =
+            ObjectStorePath::parse("check-reachable").map_err(|e| BlobRepositoryError::Internal(e.to_string()))?;
+        match self.object_store.head(&path).await {
+            Ok(_) | Err(ObjectStoreError::NotFound { .. }) => Ok(()),
+            Err(e) => Err(BlobRepositoryError::Internal(e.to_string())),
+        }
+    }
+}
+
+/// A blob repository that fails any request that takes longer than a timeout.
+pub(crate) struct TimeoutBlobRepository<T> {
+    inner: Box<dyn BlobRepository<T>>,
+    timeout: Duration,
+}
+
+impl<T> TimeoutBlobRepository<T> {
+    /// Wraps a repository so that its requests time out after the given duration.
+    pub(crate) fn new(inner: Box<dyn BlobRepository<T>>, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+
+    async fn with_timeout<O>(
+        &self,
+        request: impl Future<Output = Result<O, BlobRepositoryError>>,
+    ) -> Result<O, BlobRepositoryError> {
+        timeout(self.timeout, request).await.map_err(|_| BlobRepositoryError::Timeout(self.timeout))?
+    }
+}
+
+#[async_trait]
+impl<T: BinarySerde> BlobRepository<T> for TimeoutBlobRepository<T> {
+    async fn create(&self, key: &str, value: T) -> Result<(), BlobRepositoryError> {
+        self.with_timeout(self.inner.create(key, value)).await
+    }
+
+    async fn upsert(&self, key: &str, value: T) -> Result<(), BlobRepositoryError> {
+        self.with_timeout(self.inner.upsert(key, value)).await
+    }
+
+    async fn read(&self, key: &str) -> Result<T, BlobRepositoryError> {
+        self.with_timeout(self.inner.read(key)).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobRepositoryError> {
+        self.with_timeout(self.inner.delete(key)).await
+    }
+
+    async fn check_permissions(&self) -> Result<(), BlobRepositoryError> {
+        self.with_timeout(self.inner.check_permissions()).await
+    }
+
+    async fn check_reachable(&self) -> Result<(), BlobRepositoryError> {
+        self.with_timeout(self.inner.check_reachable()).await
+    }
+}
+
+/// A blob repository that retries reads that don't find an object that was recently written.
+///
+/// Some object storage backends don't guarantee read-after-write consistency, so a read right after a write may not
+/// find the object. Only reads for keys written through this repository within the retry window are retried, any
+/// other missing object is reported right away.
+pub(crate) struct ReadAfterWriteRetryBlobRepository<T> {
+    inner: Box<dyn BlobRepository<T>>,
+    attempts: u32,
+    delay: Duration,
+    window: Duration,
+    recent_writes: Mutex<HashMap<String, Instant>>,
+}
+
+impl<T> ReadAfterWriteRetryBlobRepository<T> {
+    /// Wraps a repository so that reads for recently written keys are retried.
+    pub(crate) fn new(inner: Box<dyn BlobRepository<T>>, attempts: u32, delay: Duration, window: Duration) -> Self {
+        Self { inner, attempts, delay, window, recent_writes: Default::default() }
+    }
+
+    async fn record_write(&self, key: &str) {
+        let now = Instant::now();
+        let mut recent_writes = self.recent_writes.lock().await;
+        recent_writes.retain(|_, written_at| now.saturating_duration_since(*written_at) < self.window);
+        recent_writes.insert(key.to_string(), now);
+    }
+
+    async fn recently_written(&self, key: &str) -> bool {
+        match self.recent_writes.lock().await.get(key) {
+            Some(written_at) => written_at.elapsed() < self.window,
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: BinarySerde> BlobRepository<T> for ReadAfterWriteRetryBlobRepository<T> {
+    async fn create(&self, key: &str, value: T) -> Result<(), BlobRepositoryError> {
+        self.inner.create(key, value).await?;
+        self.record_write(key).await;
+        Ok(())
+    }
+
+    async fn upsert(&self, key: &str, value: T) -> Result<(), BlobRepositoryError> {
+        self.inner.upsert(key, value).await?;
+        self.record_write(key).await;
+        Ok(())
+    }
+
+    async fn read(&self, key: &str) -> Result<T, BlobRepositoryError> {
+        let mut attempt = 0;
+        loop {
+            match self.inner.read(key).await {
+                Err(BlobRepositoryError::NotFound) if attempt < self.attempts && self.recently_written(key).await => {
+                    attempt = attempt.saturating_add(1);
+                    debug!("Object {key} not found right after being written, retrying (attempt {attempt})");
+                    sleep(self.delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobRepositoryError> {
+        self.inner.delete(key).await?;
+        self.recent_writes.lock().await.remove(key);
+        Ok(())
+    }
+
+    async fn check_permissions(&self) -> Result<(), BlobRepositoryError> {
+        self.inner.check_permissions().await
+    }
+
+    async fn check_reachable(&self) -> Result<(), BlobRepositoryError> {
+        self.inner.check_reachable().await
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -269,6 +425,9 @@ pub(crate) enum BlobRepositoryError {
 
     #[error("decoding: {0}")]
     Decode(BinarySerdeError),
+
+    #[error("request timed out after {0:?}")]
+    Timeout(Duration),
 }
 
 impl From<io::Error> for BlobRepositoryError {
@@ -450,6 +609,150 @@ mod test {
         thread_rng().sample_iter(&Alphanumeric).take(10).map(char::from).collect()
     }
 
+    /// A repository that delays every request before forwarding it to an in-memory one.
+    struct SlowBlobRepository {
+        inner: MemoryBlobRepository<u32>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl BlobRepository<u32> for SlowBlobRepository {
+        async fn create(&self, key: &str, value: u32) -> Result<(), BlobRepositoryError> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.create(key, value).await
+        }
+
+        async fn upsert(&self, key: &str, value: u32) -> Result<(), BlobRepositoryError> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.upsert(key, value).await
+        }
+
+        async fn read(&self, key: &str) -> Result<u32, BlobRepositoryError> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.read(key).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), BlobRepositoryError> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.delete(key).await
+        }
+
+        async fn check_permissions(&self) -> Result<(), BlobRepositoryError> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.check_permissions().await
+        }
+
+        async fn check_reachable(&self) -> Result<(), BlobRepositoryError> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.check_reachable().await
+        }
+    }
+
+    /// A repository that doesn't find objects for a number of reads after they're written.
+    struct EventuallyConsistentBlobRepository {
+        inner: MemoryBlobRepository<u32>,
+        missing_reads: std::sync::Mutex<u32>,
+    }
+
+    impl EventuallyConsistentBlobRepository {
+        fn new(missing_reads: u32) -> Self {
+            Self { inner: MemoryBlobRepository::default(), missing_reads: std::sync::Mutex::new(missing_reads) }
+        }
+    }
+
+    #[async_trait]
+    impl BlobRepository<u32> for EventuallyConsistentBlobRepository {
+        async fn create(&self, key: &str, value: u32) -> Result<(), BlobRepositoryError> {
+            self.inner.create(key, value).await
+        }
+
+        async fn upsert(&self, key: &str, value: u32) -> Result<(), BlobRepositoryError> {
+            self.inner.upsert(key, value).await
+        }
+
+        async fn read(&self, key: &str) -> Result<u32, BlobRepositoryError> {
+            {
+                let mut missing_reads = self.missing_reads.lock().unwrap();
+                if *missing_reads > 0 {
+                    *missing_reads = missing_reads.saturating_sub(1);
+                    return Err(BlobRepositoryError::NotFound);
+                }
+            }
+            self.inner.read(key).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), BlobRepositoryError> {
+            self.inner.delete(key).await
+        }
+
+        async fn check_permissions(&self) -> Result<(), BlobRepositoryError> {
+            self.inner.check_permissions().await
+        }
+
+        async fn check_reachable(&self) -> Result<(), BlobRepositoryError> {
+            self.inner.check_reachable().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_after_write_retry() {
+        let inner = EventuallyConsistentBlobRepository::new(2);
+        let repo = ReadAfterWriteRetryBlobRepository::new(
+            Box::new(inner),
+            3,
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+        );
+        repo.upsert("key", 42).await.expect("failed to create object");
+        assert_eq!(repo.read("key").await.expect("failed to read object"), 42);
+    }
+
+    #[tokio::test]
+    async fn test_read_after_write_retry_exhausted() {
+        let inner = EventuallyConsistentBlobRepository::new(5);
+        let repo = ReadAfterWriteRetryBlobRepository::new(
+            Box::new(inner),
+            3,
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+        );
+        repo.upsert("key", 42).await.expect("failed to create object");
+        let err = repo.read("key").await.expect_err("read succeeded");
+        assert!(matches!(err, BlobRepositoryError::NotFound), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_read_without_write_not_retried() {
+        let inner = EventuallyConsistentBlobRepository::new(1);
+        let repo = ReadAfterWriteRetryBlobRepository::new(
+            Box::new(inner),
+            3,
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+        );
+        repo.inner.upsert("key", 42).await.expect("failed to create object");
+        let err = repo.read("key").await.expect_err("read was retried");
+        assert!(matches!(err, BlobRepositoryError::NotFound), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let slow = SlowBlobRepository { inner: MemoryBlobRepository::default(), delay: Duration::from_secs(5) };
+        let repo = TimeoutBlobRepository::new(Box::new(slow), Duration::from_millis(50));
+        let err = repo.upsert("key", 42).await.expect_err("request didn't time out");
+        assert!(matches!(err, BlobRepositoryError::Timeout(_)), "{err:?}");
+        let err = repo.read("key").await.expect_err("request didn't time out");
+        assert!(matches!(err, BlobRepositoryError::Timeout(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_request_within_timeout() {
+        let slow = SlowBlobRepository { inner: MemoryBlobRepository::default(), delay: Duration::from_millis(10) };
+        let repo = TimeoutBlobRepository::new(Box::new(slow), Duration::from_secs(5));
+        repo.upsert("key", 42).await.expect("failed to create object");
+        assert_eq!(repo.read("key").await.expect("failed to read object"), 42);
+    }
+
     #[rstest]
     #[case::memory(make_memory_repository())]
     #[case::filesystem(make_filesystem_repository())]
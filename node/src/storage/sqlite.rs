@@ -109,6 +109,13 @@ impl SqliteDb {
         }
     }
 
+    /// Checks that the database can be reached by running a trivial query on it.
+    pub(crate) async fn ping(&self) -> Result<(), DatabaseError> {
+        let mut conn = self.acquire_read().await?;
+        sqlx::query("SELECT 1").execute(&mut *conn).await.map_err(DatabaseError::Execution)?;
+        Ok(())
+    }
+
     pub(crate) async fn export_table_metrics(&self, table: &str) -> anyhow::Result<()> {
         #[derive(FromRow)]
         struct TableSize {
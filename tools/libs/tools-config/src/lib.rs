@@ -77,6 +77,49 @@ pub trait ToolConfig {
         Ok(configs)
     }
 
+    /// Reads all configurations, without failing if any of them can't be loaded.
+    ///
+    /// Returns the configurations that were successfully loaded, along with the name and error for each
+    /// one that couldn't be.
+    fn read_all_lossy() -> (Vec<NamedConfig<Self>>, Vec<(String, anyhow::Error)>)
+    where
+        Self: Sized + DeserializeOwned,
+    {
+        let dir = Self::root_config_path();
+        let mut configs = Vec::new();
+        let mut errors = Vec::new();
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                errors.push((dir.display().to_string(), e.into()));
+                return (configs, errors);
+            }
+        };
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    errors.push((dir.display().to_string(), e.into()));
+                    continue;
+                }
+            };
+            let path = entry.path();
+            let is_file = entry.file_type().map(|file_type| file_type.is_file()).unwrap_or(false);
+            if !is_file || path.extension() != Some(OsStr::new("yaml")) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(OsStr::to_str) else {
+                errors.push((path.display().to_string(), anyhow!("invalid file name found: {path:?}")));
+                continue;
+            };
+            match Self::read_from_config(name) {
+                Ok(config) => configs.push(NamedConfig { name: name.to_string(), config }),
+                Err(e) => errors.push((name.to_string(), e)),
+            }
+        }
+        (configs, errors)
+    }
+
     fn remove_config(name: &str) -> anyhow::Result<()> {
         let path = Self::config_path(name)?;
         fs::remove_file(path)?;
@@ -108,3 +151,40 @@ pub struct NamedConfig<C> {
     /// The configuration itself.
     pub config: C,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::sync::OnceLock;
+    use tempfile::TempDir;
+
+    static CONFIG_DIR: OnceLock<TempDir> = OnceLock::new();
+
+    #[derive(Deserialize)]
+    struct DummyConfig {
+        #[allow(dead_code)]
+        value: u32,
+    }
+
+    impl ToolConfig for DummyConfig {
+        fn root_config_path() -> PathBuf {
+            CONFIG_DIR.get_or_init(|| TempDir::new().expect("failed to create temp dir")).path().to_path_buf()
+        }
+    }
+
+    #[test]
+    fn read_all_lossy() {
+        let dir = DummyConfig::root_config_path();
+        fs::write(dir.join("valid.yaml"), "value: 42").expect("failed to write config");
+        fs::write(dir.join("corrupt.yaml"), "value: [").expect("failed to write config");
+
+        let (configs, errors) = DummyConfig::read_all_lossy();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].name, "valid");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "corrupt");
+
+        assert!(DummyConfig::read_all().is_err());
+    }
+}
@@ -1,26 +1,35 @@
-use anyhow::{anyhow, bail, Error};
+use anyhow::{anyhow, Context, Error};
+use bytecode_evaluator::{EvaluationError, Evaluator, EvaluatorOptions};
 use clap::Parser;
 use clap_utils::ParserExt;
 use client_metrics::{fields, ClientMetrics};
+use jit_compiler::models::bytecode::Operation;
 use log::{debug, error};
-use math_lib::modular::{SafePrime, U128SafePrime, U256SafePrime, U64SafePrime};
+use math_lib::modular::{Overflow, SafePrime, U128SafePrime, U256SafePrime, U64SafePrime};
 use metrics::metrics::MetricsRegistry;
 use mpc_vm::{
     protocols::MPCProtocol,
     vm::{
         simulator::{
-            ExecutionMetrics, InputGenerator, MetricsFormat, ProgramSimulator, SimulationParameters,
-            StaticInputGeneratorBuilder,
+            ExecutionMetrics, InputGenerator, MetricsFormat, ProgramSimulator, RandomInputGenerator,
+            SimulationParameters, StaticInputGeneratorBuilder,
         },
         ExecutionMetricsConfig, ExecutionVmConfig,
     },
-    JitCompiler, MPCCompiler, Program,
+    JitCompiler, MPCCompiler, Program, ProgramBytecode,
+};
+use nada_compiler_backend::{
+    mir::{proto::ConvertProto, ProgramMIR},
+    program_contract::ProgramContract,
+};
+use nada_value::{
+    clear::Clear,
+    errors::{ClearToEncryptedError, EncodingError},
+    NadaValue,
 };
-use nada_compiler_backend::mir::{proto::ConvertProto, ProgramMIR};
-use nada_value::{clear::Clear, NadaValue};
 use nada_values_args::NadaValueArgs;
 use shamir_sharing::secret_sharer::{SafePrimeSecretSharer, ShamirSecretSharer};
-use std::{collections::HashMap, fs, fs::File, io::Read};
+use std::{collections::HashMap, fmt, fs, fs::File, io::Read, str::FromStr};
 
 #[derive(Parser)]
 #[clap(author = "Nillion", version, about = "A tool that executes programs under a simulated Nillion network.")]
@@ -28,9 +37,19 @@ struct Cli {
     /// Program path.
     program_path: String,
 
-    /// Prime size in bits.
-    #[clap(short, long, default_value_t = 256)]
-    prime_size: u32,
+    /// Prime size in bits: 64, 128 or 256.
+    #[clap(short, long, default_value_t = PrimeSize::Bits256)]
+    prime_size: PrimeSize,
+
+    /// Run the program under every supported prime size and check that all of them produce the same outputs.
+    ///
+    /// This is useful to catch programs whose results depend on the prime size, e.g. because of overflows.
+    #[clap(long, conflicts_with = "prime_size")]
+    all_primes: bool,
+
+    /// If the program overflows, suggest the prime sizes that could fit its values.
+    #[clap(long)]
+    explain_overflow: bool,
 
     /// The size of the simulated network.
     #[clap(short, long, default_value_t = 3)]
@@ -44,6 +63,16 @@ struct Cli {
     #[clap(flatten)]
     values: NadaValueArgs,
 
+    /// Generate random values for any program inputs that aren't explicitly provided.
+    #[clap(long)]
+    auto_inputs: bool,
+
+    /// The seed to use when generating inputs via `--auto-inputs`.
+    ///
+    /// A random seed is used if none is provided.
+    #[clap(long, requires = "auto_inputs")]
+    seed: Option<u64>,
+
     /// Print protocol runtime information.
     /// Protocols are displayed in execution order.
     /// By default, text metrics are displayed on stdout, JSON metrics in a metrics.json file and YAML metrics in a
@@ -72,7 +101,87 @@ struct Cli {
     pub metrics_execution_plan: bool,
 }
 
-fn build_inputs(cli: &Cli) -> Result<InputGenerator, Error> {
+/// The size of the prime used to run a program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PrimeSize {
+    Bits64,
+    Bits128,
+    Bits256,
+}
+
+impl PrimeSize {
+    const ALL: [PrimeSize; 3] = [PrimeSize::Bits64, PrimeSize::Bits128, PrimeSize::Bits256];
+
+    fn bits(&self) -> u32 {
+        match self {
+            PrimeSize::Bits64 => 64,
+            PrimeSize::Bits128 => 128,
+            PrimeSize::Bits256 => 256,
+        }
+    }
+}
+
+impl fmt::Display for PrimeSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+
+impl FromStr for PrimeSize {
+    type Err = InvalidPrimeSize;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = s.parse::<u32>().map_err(|_| InvalidPrimeSize)?;
+        Self::ALL.into_iter().find(|size| size.bits() == bits).ok_or(InvalidPrimeSize)
+    }
+}
+
+/// A prime size that isn't supported.
+#[derive(Debug)]
+struct InvalidPrimeSize;
+
+impl fmt::Display for InvalidPrimeSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sizes: Vec<_> = PrimeSize::ALL.iter().map(ToString::to_string).collect();
+        write!(f, "prime size must be one of {}", sizes.join(", "))
+    }
+}
+
+impl std::error::Error for InvalidPrimeSize {}
+
+/// The outputs of a program differ between prime sizes.
+#[derive(Debug)]
+struct OutputsDiverged;
+
+impl fmt::Display for OutputsDiverged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "program outputs differ across prime sizes")
+    }
+}
+
+impl std::error::Error for OutputsDiverged {}
+
+/// The prime size a program failed to run with.
+#[derive(Debug)]
+struct FailedPrime(PrimeSize);
+
+impl fmt::Display for FailedPrime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to run program using a {} bit prime", self.0)
+    }
+}
+
+fn build_inputs(cli: &Cli, contract: &ProgramContract) -> Result<InputGenerator, Error> {
+    if cli.auto_inputs {
+        let seed = cli.seed.unwrap_or_else(rand::random);
+        let mut builder = RandomInputGenerator::with_seed(seed);
+        builder.extend(cli.values.parse()?);
+        println!("Auto-generating inputs using seed {seed}");
+        for (input_name, value) in builder.generate(contract)? {
+            println!("Input ({input_name}): {value}");
+        }
+        return builder.build(contract);
+    }
     let mut builder = StaticInputGeneratorBuilder::default();
     builder.extend(cli.values.parse()?);
 
@@ -99,6 +208,125 @@ where
     simulator.run()
 }
 
+fn simulate_with_prime(
+    prime_size: PrimeSize,
+    cli: &Cli,
+    program: Program<MPCProtocol>,
+    parameters: SimulationParameters,
+    inputs: &InputGenerator,
+    client_metrics: &ClientMetrics,
+) -> Result<(HashMap<String, NadaValue<Clear>>, ExecutionMetrics), Error> {
+    client_metrics.send_event_sync("run", fields! { "prime_size" => prime_size.to_string() });
+    match prime_size {
+        PrimeSize::Bits64 => {
+            simulate::<U64SafePrime>(program, parameters, inputs, cli.metrics_message_size, cli.metrics_execution_plan)
+        }
+        PrimeSize::Bits128 => {
+            simulate::<U128SafePrime>(program, parameters, inputs, cli.metrics_message_size, cli.metrics_execution_plan)
+        }
+        PrimeSize::Bits256 => {
+            simulate::<U256SafePrime>(program, parameters, inputs, cli.metrics_message_size, cli.metrics_execution_plan)
+        }
+    }
+}
+
+/// Runs the program under every supported prime size and reports any outputs that differ between them.
+fn run_all_primes(
+    cli: &Cli,
+    program: Program<MPCProtocol>,
+    parameters: SimulationParameters,
+    inputs: &InputGenerator,
+    client_metrics: &ClientMetrics,
+) -> Result<(), Error> {
+    let mut results = Vec::new();
+    for prime_size in PrimeSize::ALL {
+        debug!("Running program using a {prime_size} bit prime");
+        // Each run gets its own copy so generators backed by a PRNG produce the same inputs every time.
+        let inputs = inputs.clone();
+        let (outputs, _) =
+            simulate_with_prime(prime_size, cli, program.clone(), parameters.clone(), &inputs, client_metrics)
+                .context(FailedPrime(prime_size))?;
+        results.push((prime_size, outputs));
+    }
+
+    let Some((_, expected)) = results.first() else {
+        return Err(anyhow!("no prime sizes to run"));
+    };
+    let mut output_names: Vec<_> = expected.keys().collect();
+    output_names.sort();
+    let mut diverged = false;
+    for output_name in output_names {
+        let values: Vec<_> =
+            results.iter().map(|(prime_size, outputs)| (prime_size, outputs.get(output_name))).collect();
+        if values.iter().all(|(_, value)| *value == expected.get(output_name)) {
+            continue;
+        }
+        diverged = true;
+        println!("Output ({output_name}) diverges across prime sizes:");
+        for (prime_size, value) in values {
+            match value {
+                Some(value) => println!("  {prime_size} bits: {value}"),
+                None => println!("  {prime_size} bits: <missing>"),
+            }
+        }
+    }
+    if diverged {
+        return Err(OutputsDiverged.into());
+    }
+
+    let sizes: Vec<_> = PrimeSize::ALL.iter().map(ToString::to_string).collect();
+    println!("Outputs match across prime sizes {}", sizes.join(", "));
+    if let Some((_, outputs)) = results.into_iter().next() {
+        print_output(outputs);
+    }
+    Ok(())
+}
+
+/// Evaluates the program in the clear and fails if any addition, subtraction or multiplication overflows the prime.
+///
+/// The simulator only sees the wrapped around results, so this is the only way to point at the operation that
+/// overflowed.
+fn check_overflows<T: SafePrime>(
+    bytecode: &ProgramBytecode,
+    contract: &ProgramContract,
+    inputs: &InputGenerator,
+) -> Result<(), Error> {
+    // The evaluator's random values span the whole field, so any arithmetic on them would look like an overflow.
+    if bytecode.operations().any(|operation| matches!(operation, Operation::Random(_))) {
+        debug!("Skipping overflow checks, the program uses random values");
+        return Ok(());
+    }
+    // Use a copy so generators backed by a PRNG produce the same inputs for the simulation.
+    let generator = inputs.clone();
+    let mut values = HashMap::new();
+    for input in &contract.inputs {
+        values.insert(input.name.clone(), generator.create(&input.name, input.ty.clone())?);
+    }
+    let options = EvaluatorOptions { detect_overflow: true, ..Default::default() };
+    match Evaluator::<T>::run_with(bytecode, values, options) {
+        Ok(_) => Ok(()),
+        Err(e) if is_overflow(&e) => Err(e),
+        // Anything else will be reported by the simulation itself.
+        Err(e) => {
+            debug!("Failed to evaluate program in the clear: {e}");
+            Ok(())
+        }
+    }
+}
+
+fn check_overflows_with_prime(
+    prime_size: PrimeSize,
+    bytecode: &ProgramBytecode,
+    contract: &ProgramContract,
+    inputs: &InputGenerator,
+) -> Result<(), Error> {
+    match prime_size {
+        PrimeSize::Bits64 => check_overflows::<U64SafePrime>(bytecode, contract, inputs),
+        PrimeSize::Bits128 => check_overflows::<U128SafePrime>(bytecode, contract, inputs),
+        PrimeSize::Bits256 => check_overflows::<U256SafePrime>(bytecode, contract, inputs),
+    }
+}
+
 fn run(cli: Cli) -> Result<(), Error> {
     debug!("Loading program's MIR from {}", cli.program_path);
     let mut program = vec![];
@@ -108,10 +336,11 @@ fn run(cli: Cli) -> Result<(), Error> {
     let program_mir = ProgramMIR::try_decode(&program).map_err(|e| anyhow!("failed to parse program's MIR: {e}"))?;
 
     debug!("Parsing program");
-    let program = MPCCompiler::compile(program_mir).map_err(|e| anyhow!("failed to compile program's MIR: {e}"))?;
+    let (program, bytecode) =
+        MPCCompiler::compile_with_bytecode(program_mir).map_err(|e| anyhow!("failed to compile program's MIR: {e}"))?;
 
     debug!("Loading secrets");
-    let inputs = build_inputs(&cli)?;
+    let inputs = build_inputs(&cli, &program.contract)?;
     let parameters = SimulationParameters {
         network_size: cli.network_size,
         polynomial_degree: cli.polynomial_degree,
@@ -121,40 +350,15 @@ fn run(cli: Cli) -> Result<(), Error> {
     let client_metrics = ClientMetrics::new_default("nada-run");
 
     debug!("Running program");
-    let (result, metrics) = match cli.prime_size {
-        64 => {
-            client_metrics.send_event_sync("run", fields! { "prime_size" => "64" });
-            simulate::<U64SafePrime>(
-                program,
-                parameters,
-                &inputs,
-                cli.metrics_message_size,
-                cli.metrics_execution_plan,
-            )?
-        }
-        128 => {
-            client_metrics.send_event_sync("run", fields! { "prime_size" => "128" });
-            simulate::<U128SafePrime>(
-                program,
-                parameters,
-                &inputs,
-                cli.metrics_message_size,
-                cli.metrics_execution_plan,
-            )?
-        }
-        256 => {
-            client_metrics.send_event_sync("run", fields! { "prime_size" => "256" });
-            simulate::<U256SafePrime>(
-                program,
-                parameters,
-                &inputs,
-                cli.metrics_message_size,
-                cli.metrics_execution_plan,
-            )?
-        }
-        _ => bail!("invalid prime size"),
-    };
+    if cli.all_primes {
+        return run_all_primes(&cli, program, parameters, &inputs, &client_metrics);
+    }
+    check_overflows_with_prime(cli.prime_size, &bytecode, &program.contract, &inputs)?;
+    let (result, metrics) = simulate_with_prime(cli.prime_size, &cli, program, parameters, &inputs, &client_metrics)?;
 
+    if cli.prometheus_metrics {
+        metrics.register_metrics();
+    }
     metrics.standard_output(cli.metrics, cli.metrics_filepath.as_deref())?;
 
     print_output(result);
@@ -165,8 +369,47 @@ fn run(cli: Cli) -> Result<(), Error> {
 /// Print outputs in human format not modular.
 fn print_output(outputs: HashMap<String, NadaValue<Clear>>) {
     for (output_name, value) in outputs {
-        println!("Output ({output_name}): {value:?}");
+        println!("Output ({output_name}): {value}");
+    }
+}
+
+/// Returns whether an error was caused by a value that doesn't fit in the prime used to run the program.
+fn is_overflow(e: &Error) -> bool {
+    e.chain().any(|cause| {
+        cause.is::<Overflow>()
+            || matches!(
+                cause.downcast_ref::<ClearToEncryptedError>(),
+                Some(
+                    ClearToEncryptedError::Overflow(_)
+                        | ClearToEncryptedError::EncodingError(EncodingError::PrimeTooSmall)
+                )
+            )
+            || matches!(cause.downcast_ref::<EncodingError>(), Some(EncodingError::PrimeTooSmall))
+            || matches!(cause.downcast_ref::<EvaluationError>(), Some(EvaluationError::ArithmeticOverflow { .. }))
+    })
+}
+
+/// Builds the message reported when a program overflows.
+fn overflow_message(e: &Error, prime_size: PrimeSize, explain: bool) -> String {
+    let mut message = format!("program overflowed using a {prime_size} bit prime: {e:#}");
+    if explain {
+        let larger: Vec<_> = PrimeSize::ALL
+            .iter()
+            .filter(|size| size.bits() > prime_size.bits())
+            .map(|size| format!("--prime-size {size}"))
+            .collect();
+        if larger.is_empty() {
+            message.push_str(&format!(
+                "\nvalues must fit in a {prime_size} bit prime, which is the largest one supported"
+            ));
+        } else {
+            message.push_str(&format!(
+                "\nthe values don't fit in a {prime_size} bit prime, try running the program with a larger one using {}",
+                larger.join(" or ")
+            ));
+        }
     }
+    message
 }
 
 /// The driver function that parses the arguments and runs the simulator.
@@ -174,9 +417,20 @@ pub fn driver() -> Result<(), Error> {
     let metrics_registry = metrics::initialize(HashMap::new())?;
     let args = Cli::parse_with_version();
     let prometheus_metrics = args.prometheus_metrics;
+    let prime_size = args.prime_size;
+    let explain_overflow = args.explain_overflow;
 
+    let mut failure = None;
     if let Err(e) = run(args) {
-        error!("Failed to run program: {e}");
+        if is_overflow(&e) {
+            // With `--all-primes` the error carries the prime size the program failed with.
+            let prime_size = e.downcast_ref::<FailedPrime>().map(|failed| failed.0).unwrap_or(prime_size);
+            failure = Some(anyhow!(overflow_message(&e, prime_size, explain_overflow)));
+        } else if e.is::<OutputsDiverged>() {
+            failure = Some(e);
+        } else {
+            error!("Failed to run program: {e}");
+        }
     }
 
     if prometheus_metrics {
@@ -184,5 +438,8 @@ pub fn driver() -> Result<(), Error> {
         fs::write("prometheus.txt", metrics_registry.encode_metrics()?)?;
     }
 
-    Ok(())
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
 }
@@ -30,7 +30,7 @@ fn addition_simple_public_public() -> Result<(), Box<dyn std::error::Error>> {
         .arg("public_my_int2=34")
         .arg(file.path());
     // expected result of the command when passed with the above arguments
-    cmd.assert().success().stdout(predicate::str::contains("Output (my_output): Integer(NadaInt(57))"));
+    cmd.assert().success().stdout(predicate::str::contains("Output (my_output): Integer(57)"));
 
     Ok(())
 }
@@ -53,7 +53,7 @@ fn addition_simple() -> Result<(), Box<dyn std::error::Error>> {
         .arg("my_int2=34")
         .arg(file.path());
     // expected result of the command when passed with the above arguments
-    cmd.assert().success().stdout(predicate::str::contains("Output (my_output): SecretInteger(NadaInt(57))"));
+    cmd.assert().success().stdout(predicate::str::contains("Output (my_output): SecretInteger(57)"));
 
     Ok(())
 }
@@ -73,7 +73,9 @@ fn map_simple() -> Result<(), Box<dyn std::error::Error>> {
         .arg("my_int=1")
         .arg(file.path());
     // expected result of the command when passed with the above arguments
-    cmd.assert().success().stdout(predicate::str::contains("Output (my_output): Array { inner_type: SecretInteger, values: [SecretInteger(NadaInt(2)), SecretInteger(NadaInt(3)), SecretInteger(NadaInt(4))] }"));
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Output (my_output): Array(SecretInteger(2), SecretInteger(3), SecretInteger(4))",
+    ));
 
     Ok(())
 }
@@ -93,7 +95,9 @@ fn map_simple_unsigned() -> Result<(), Box<dyn std::error::Error>> {
         .arg("my_int=1")
         .arg(file.path());
     // expected result of the command when passed with the above arguments
-    cmd.assert().success().stdout(predicate::str::contains("Output (my_output): Array { inner_type: SecretUnsignedInteger, values: [SecretUnsignedInteger(NadaUint(2)), SecretUnsignedInteger(NadaUint(3)), SecretUnsignedInteger(NadaUint(4))] }"));
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Output (my_output): Array(SecretUnsignedInteger(2), SecretUnsignedInteger(3), SecretUnsignedInteger(4))",
+    ));
 
     Ok(())
 }
@@ -116,7 +120,124 @@ fn map_simple_public() -> Result<(), Box<dyn std::error::Error>> {
         .arg("my_int=1")
         .arg(file.path());
     // expected result of the command when passed with the above arguments
-    cmd.assert().success().stdout(predicate::str::contains("Output (my_output): Array { inner_type: SecretInteger, values: [SecretInteger(NadaInt(2)), SecretInteger(NadaInt(3)), SecretInteger(NadaInt(4))] }"));
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Output (my_output): Array(SecretInteger(2), SecretInteger(3), SecretInteger(4))",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn auto_inputs_same_seed() -> Result<(), Box<dyn std::error::Error>> {
+    let file = load_program("addition_simple")?;
+    let run = || -> Result<String, Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("nada-run")?;
+        cmd.arg("--prime-size").arg("128").arg("--auto-inputs").arg("--seed").arg("42").arg(file.path());
+        let output = cmd.assert().success().get_output().stdout.clone();
+        Ok(String::from_utf8(output)?)
+    };
+
+    let first = run()?;
+    let second = run()?;
+    assert!(first.contains("Input (my_int1): SecretInteger"), "unexpected output: {first}");
+    assert!(first.contains("Input (my_int2): SecretInteger"), "unexpected output: {first}");
+    assert_eq!(first, second);
+
+    Ok(())
+}
+
+#[test]
+fn invalid_prime_size() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("nada-run")?;
+
+    // the prime size is rejected while parsing arguments so the program is never loaded
+    cmd.arg("--prime-size").arg("100").arg("missing.nada.bin");
+    cmd.assert().failure().stderr(predicate::str::contains("prime size must be one of 64, 128, 256"));
+
+    Ok(())
+}
+
+#[test]
+fn all_primes_matching_outputs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("nada-run")?;
+    let file = load_program("addition_simple")?;
+
+    cmd.arg("--all-primes")
+        .arg("--secret-integer")
+        .arg("my_int1=23")
+        .arg("--secret-integer")
+        .arg("my_int2=34")
+        .arg(file.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Outputs match across prime sizes 64, 128, 256"))
+        .stdout(predicate::str::contains("Output (my_output): SecretInteger(57)"));
+
+    Ok(())
+}
+
+#[test]
+fn all_primes_diverging_outputs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("nada-run")?;
+    let file = load_program("multiplication_simple")?;
+
+    // 2^40 * 2^40 doesn't fit in the 64 bit prime field so that run wraps around
+    cmd.arg("--all-primes")
+        .arg("--secret-integer")
+        .arg("my_int1=1099511627776")
+        .arg("--secret-integer")
+        .arg("my_int2=1099511627776")
+        .arg(file.path());
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Output (my_output) diverges across prime sizes"))
+        .stdout(predicate::str::contains("128 bits: SecretInteger(1208925819614629174706176)"))
+        .stdout(predicate::str::contains("Outputs match").not())
+        .stderr(predicate::str::contains("program outputs differ across prime sizes"));
+
+    Ok(())
+}
+
+#[test]
+fn explain_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("nada-run")?;
+    let file = load_program("addition_simple")?;
+
+    // 2^70 doesn't fit in the 64 bit prime field
+    cmd.arg("--prime-size")
+        .arg("64")
+        .arg("--explain-overflow")
+        .arg("--secret-integer")
+        .arg("my_int1=1180591620717411303424")
+        .arg("--secret-integer")
+        .arg("my_int2=1")
+        .arg(file.path());
+    let suggestion = "try running the program with a larger one using --prime-size 128 or --prime-size 256";
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("program overflowed using a 64 bit prime"))
+        .stderr(predicate::str::contains(suggestion));
+
+    Ok(())
+}
+
+#[test]
+fn operation_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("nada-run")?;
+    let file = load_program("multiplication_simple")?;
+
+    // Both inputs fit in the 64 bit prime field but 2^80 doesn't
+    cmd.arg("--prime-size")
+        .arg("64")
+        .arg("--secret-integer")
+        .arg("my_int1=1099511627776")
+        .arg("--secret-integer")
+        .arg("my_int2=1099511627776")
+        .arg(file.path());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("program overflowed using a 64 bit prime"))
+        .stderr(predicate::str::contains("multiplication overflowed: 1099511627776 * 1099511627776"));
 
     Ok(())
 }
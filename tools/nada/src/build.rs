@@ -10,6 +10,7 @@ pub fn build_program(program_conf: &ProgramConf, mir_json: bool) -> Result<()> {
             mir_bin: true,
             mir_json,
         },
+        ..Default::default()
     };
     let target_dir = get_target_path()?;
     // Let's make sure that the target dir exists. Otherwise the user might see a strange error later.
@@ -42,7 +42,7 @@ pub async fn compute_test(network: &String, test: Box<dyn TestCase>) -> Result<(
         .into_eyre()?
         .map_err(|e| eyre!("{e:?}"))?;
     for (output_name, value) in outputs {
-        println!("Output ({output_name}): {value:?}");
+        println!("Output ({output_name}): {value}");
     }
 
     Ok(())
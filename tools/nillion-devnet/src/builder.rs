@@ -3,17 +3,18 @@ use mpc_vm::{requirements::MPCProgramRequirements, vm::ExecutionVmConfig};
 use node::{
     builder::{NodeBuilder, NodeHandle, PreprocessingMode},
     config::{
-        default_max_payload_size, default_quote_ttl, default_receipt_ttl, Cluster, GrpcConfig, GrpcTlsConfig,
-        NetworkConfig, ObjectStorageConfig, PaymentsConfig, PreprocessingConfig, PreprocessingProtocolConfig,
-        PricingConfig, StorageConfig,
+        default_clock_skew_tolerance, default_max_payload_size, default_max_program_operations, default_quote_ttl,
+        default_receipt_ttl, Cluster, GrpcConfig, GrpcTlsConfig, NetworkConfig, ObjectStorageConfig, PaymentsConfig,
+        PreprocessingConfig, PreprocessingProtocolConfig, PricingConfig, StorageConfig,
     },
 };
 use node_config::{
+    default_shutdown_grace_period, default_storage_read_after_write_retry, default_storage_request_timeout,
     AuxiliaryMaterialConfig, AuxiliaryMaterialProtocolConfig, IdentityConfig, KeyKind, PrivateKeyConfig, RuntimeConfig,
 };
 use program_auditor::ProgramAuditorConfig;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::create_dir_all,
     net::{IpAddr, SocketAddr},
     path::PathBuf,
@@ -23,37 +24,44 @@ use user_keypair::SigningKey;
 const DB_FILENAME: &str = "db.sqlite";
 
 // Note: these are hardcoded as the end user shouldn't care about this when testing.
-const DEFAULT_PREPROCESSING_CONFIG: PreprocessingConfig = PreprocessingConfig {
-    compare: PreprocessingProtocolConfig { batch_size: 128, generation_threshold: 1_000, target_offset_jump: 1_000 },
-    division_integer_secret: PreprocessingProtocolConfig {
-        batch_size: 32,
-        generation_threshold: 250,
-        target_offset_jump: 25,
-    },
-    modulo: PreprocessingProtocolConfig { batch_size: 32, generation_threshold: 250, target_offset_jump: 25 },
-    public_output_equality: PreprocessingProtocolConfig {
-        batch_size: 32,
-        generation_threshold: 1_000,
-        target_offset_jump: 100,
-    },
-    equals_integer_secret: PreprocessingProtocolConfig {
-        batch_size: 32,
-        generation_threshold: 1_000,
-        target_offset_jump: 100,
-    },
-    truncpr: PreprocessingProtocolConfig { batch_size: 32, generation_threshold: 1_000, target_offset_jump: 100 },
-    trunc: PreprocessingProtocolConfig { batch_size: 32, generation_threshold: 1_000, target_offset_jump: 100 },
-    random_integer: PreprocessingProtocolConfig {
-        batch_size: 1024,
-        generation_threshold: 1_000_000,
-        target_offset_jump: 100_000,
-    },
-    random_boolean: PreprocessingProtocolConfig {
-        batch_size: 1024,
-        generation_threshold: 1_000_000,
-        target_offset_jump: 100_000,
-    },
-};
+fn default_preprocessing_config() -> PreprocessingConfig {
+    PreprocessingConfig {
+        compare: PreprocessingProtocolConfig {
+            batch_size: 128,
+            generation_threshold: 1_000,
+            target_offset_jump: 1_000,
+        },
+        division_integer_secret: PreprocessingProtocolConfig {
+            batch_size: 32,
+            generation_threshold: 250,
+            target_offset_jump: 25,
+        },
+        modulo: PreprocessingProtocolConfig { batch_size: 32, generation_threshold: 250, target_offset_jump: 25 },
+        public_output_equality: PreprocessingProtocolConfig {
+            batch_size: 32,
+            generation_threshold: 1_000,
+            target_offset_jump: 100,
+        },
+        equals_integer_secret: PreprocessingProtocolConfig {
+            batch_size: 32,
+            generation_threshold: 1_000,
+            target_offset_jump: 100,
+        },
+        truncpr: PreprocessingProtocolConfig { batch_size: 32, generation_threshold: 1_000, target_offset_jump: 100 },
+        trunc: PreprocessingProtocolConfig { batch_size: 32, generation_threshold: 1_000, target_offset_jump: 100 },
+        random_integer: PreprocessingProtocolConfig {
+            batch_size: 1024,
+            generation_threshold: 1_000_000,
+            target_offset_jump: 100_000,
+        },
+        random_boolean: PreprocessingProtocolConfig {
+            batch_size: 1024,
+            generation_threshold: 1_000_000,
+            target_offset_jump: 100_000,
+        },
+        extra: HashMap::new(),
+    }
+}
 
 // Note: The program auditor configuration is harcoded with the same values as production.
 fn default_program_auditor_config(program_auditor_disabled: bool) -> ProgramAuditorConfig {
@@ -69,6 +77,8 @@ fn default_program_auditor_config(program_auditor_disabled: bool) -> ProgramAudi
             .with_public_output_equality_elements(1000)
             .with_trunc_elements(1000)
             .with_truncpr_elements(1000),
+        reject_unused_inputs: false,
+        disabled_policies: HashSet::new(),
         disable: program_auditor_disabled,
     }
 }
@@ -178,18 +188,26 @@ impl DevnetNodeBuilder {
         let config = node_config::Config {
             identity: IdentityConfig { private_key: PrivateKeyConfig::Raw { key: raw_key, kind: key_kind } },
             network: NetworkConfig {
-                preprocessing: Some(DEFAULT_PREPROCESSING_CONFIG.clone()),
+                preprocessing: Some(default_preprocessing_config()),
                 auxiliary_material: Some(AuxiliaryMaterialConfig {
                     cggmp21_aux_info: AuxiliaryMaterialProtocolConfig { enabled: true, version: 0 },
                 }),
                 max_payload_size: default_max_payload_size(),
+                max_program_operations: default_max_program_operations(),
             },
             cluster,
             storage: StorageConfig {
                 object_storage: ObjectStorageConfig::Filesystem { path: repository_path },
                 db_url,
+                request_timeout: default_storage_request_timeout(),
+                read_after_write_retry: default_storage_read_after_write_retry(),
+            },
+            runtime: RuntimeConfig {
+                max_concurrent_actions: 100,
+                max_concurrent_per_operation: Default::default(),
+                grpc,
+                shutdown_grace_period: default_shutdown_grace_period(),
             },
-            runtime: RuntimeConfig { max_concurrent_actions: 100, grpc },
             payments: PaymentsConfig {
                 rpc_endpoint: payments_rpc_endpoint,
                 pricing: PricingConfig {
@@ -204,6 +222,7 @@ impl DevnetNodeBuilder {
                 },
                 quote_ttl: default_quote_ttl(),
                 receipt_ttl: default_receipt_ttl(),
+                clock_skew_tolerance: default_clock_skew_tolerance(),
                 minimum_add_funds_payment: 1,
                 account_balance_expiration_days: 365,
                 prefunded_accounts: vec![],
@@ -16,7 +16,7 @@ async fn main() -> Result<()> {
     let cli = Cli::parse_with_version();
     let _tracing_guard = match cli.enable_tracing {
         true => {
-            let tracing_config = TracingConfig { stdout: true, json_path: None };
+            let tracing_config = TracingConfig { stdout: true, ..Default::default() };
             Some(TracingConsumer::new(tracing_config)?)
         }
         false => {
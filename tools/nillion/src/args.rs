@@ -1,3 +1,4 @@
+use crate::extend_with_values_file;
 use anyhow::{anyhow, Error, Result};
 use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_utils::shell_completions::ShellCompletionsArgs;
@@ -295,6 +296,12 @@ pub struct StoreValuesArgs {
     #[clap(flatten)]
     values: NadaValueArgs,
 
+    /// A path to a YAML or JSON file containing values to be stored, which supports compound values.
+    ///
+    /// A value can't be provided both in this file and as an argument.
+    #[clap(long)]
+    pub values_file: Option<PathBuf>,
+
     /// The time to live for the values in days. If not set, then will default to TTL set by the cluster.
     #[clap(short, long)]
     pub ttl_days: Option<u32>,
@@ -319,7 +326,11 @@ pub struct StoreValuesArgs {
 impl StoreValuesArgs {
     /// Collect all secrets.
     pub fn values(&self) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
-        self.values.parse()
+        let mut values = self.values.parse()?;
+        if let Some(path) = &self.values_file {
+            extend_with_values_file(&mut values, path)?;
+        }
+        Ok(values)
     }
 }
 
@@ -374,6 +385,12 @@ pub struct ComputeArgs {
     #[clap(flatten)]
     pub values: NadaValueArgs,
 
+    /// A path to a YAML or JSON file containing compute-time values, which supports compound values.
+    ///
+    /// A value can't be provided both in this file and as an argument.
+    #[clap(long)]
+    pub values_file: Option<PathBuf>,
+
     /// Only get a price quote for the operation.
     #[clap(long)]
     pub quote: bool,
@@ -382,7 +399,11 @@ pub struct ComputeArgs {
 impl ComputeArgs {
     /// Collect all compute values.
     pub fn values(&self) -> Result<HashMap<String, NadaValue<Clear>>, Error> {
-        self.values.parse()
+        let mut values = self.values.parse()?;
+        if let Some(path) = &self.values_file {
+            extend_with_values_file(&mut values, path)?;
+        }
+        Ok(values)
     }
 }
 
@@ -546,6 +567,9 @@ pub enum ConfigCommand {
 
     /// Get the cluster configuration
     Cluster(ClusterConfigArgs),
+
+    /// Validate a configuration file, reporting any problems found.
+    Validate(ValidateConfigArgs),
 }
 
 /// Cluster configuration arguments.
@@ -556,6 +580,13 @@ pub struct ClusterConfigArgs {
     pub node_id: Option<NodeId>,
 }
 
+/// Validate configuration arguments.
+#[derive(Args)]
+pub struct ValidateConfigArgs {
+    /// The path to the configuration file to be validated.
+    pub path: PathBuf,
+}
+
 /// The NUC command.
 #[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
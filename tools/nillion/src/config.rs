@@ -1,11 +1,15 @@
 use crate::args::CommandOutputFormat;
-use serde::Deserialize;
-use std::path::PathBuf;
+use serde::{de::IgnoredAny, Deserialize};
+use std::{collections::BTreeMap, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub output: OutputConfig,
+
+    /// Any keys that aren't part of the configuration, kept only so they can be reported by [`Config::validate`].
+    #[serde(flatten)]
+    unknown: BTreeMap<String, IgnoredAny>,
 }
 
 impl Config {
@@ -17,10 +21,27 @@ impl Config {
             .build()?;
         config.try_deserialize()
     }
+
+    /// Validates this configuration, returning every problem found.
+    ///
+    /// Values that can't be parsed are already rejected when loading the configuration, so this reports keys that
+    /// would otherwise be silently ignored.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let problems: Vec<_> = self
+            .unknown
+            .keys()
+            .map(|key| format!("unknown key '{key}'"))
+            .chain(self.output.unknown.keys().map(|key| format!("unknown key 'output.{key}'")))
+            .collect();
+        if problems.is_empty() { Ok(()) } else { Err(problems) }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct OutputConfig {
     #[serde(default)]
     pub format: Option<CommandOutputFormat>,
+
+    #[serde(flatten)]
+    unknown: BTreeMap<String, IgnoredAny>,
 }
@@ -0,0 +1,24 @@
+use super::HandlerResult;
+use crate::{args::ValidateConfigArgs, config::Config, parse_input_file};
+use anyhow::{bail, Context};
+use serde::Serialize;
+
+pub struct ConfigHandler;
+
+impl ConfigHandler {
+    /// Validates a configuration file, failing if it can't be loaded or any problems are found in it.
+    pub fn validate(args: ValidateConfigArgs) -> HandlerResult {
+        #[derive(Serialize)]
+        struct Output {
+            valid: bool,
+        }
+
+        let ValidateConfigArgs { path } = args;
+        let config: Config =
+            parse_input_file(&path).with_context(|| format!("failed to load config file {}", path.display()))?;
+        if let Err(problems) = config.validate() {
+            bail!("config file {} is invalid: {}", path.display(), problems.join(", "));
+        }
+        Ok(Box::new(Output { valid: true }))
+    }
+}
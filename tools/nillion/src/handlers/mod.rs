@@ -2,6 +2,7 @@ use crate::serialize::SerializeAsAny;
 use anyhow::{anyhow, Result};
 use std::{env, path::Path};
 
+pub mod config;
 pub mod context;
 pub mod identities;
 pub mod networks;
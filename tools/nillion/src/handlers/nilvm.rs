@@ -57,7 +57,8 @@ impl NilvmHandler {
             Command::Balance(BalanceCommand::AddFunds(args)) => self.add_funds(args).await,
             Command::Config(ConfigCommand::Payments) => self.payments_config().await,
             Command::Config(ConfigCommand::Cluster(args)) => self.cluster_config(args).await,
-            Command::Nilauth(_)
+            Command::Config(ConfigCommand::Validate(_))
+            | Command::Nilauth(_)
             | Command::IdentityGen(_)
             | Command::Identities(_)
             | Command::Networks(_)
@@ -1,6 +1,8 @@
 use anyhow::{bail, Context};
+use nillion_client::{Clear, NadaValue};
 use serde::de::DeserializeOwned;
-use std::{fs::File, io::BufReader, path::Path};
+use serialize::ValueFile;
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
 
 pub mod args;
 pub mod config;
@@ -24,3 +26,26 @@ where
         _ => bail!("invalid file extension: supported extensions are 'yaml', 'yml', or 'json'"),
     }
 }
+
+/// Parse a file containing values that follow the [`ValueFile`] schema.
+///
+/// Values are validated while the file is parsed so any invalid value causes the entire file to be rejected.
+pub(crate) fn parse_values_file(path: &Path) -> anyhow::Result<HashMap<String, NadaValue<Clear>>> {
+    let values: ValueFile = parse_input_file(path).with_context(|| format!("failed to load values file {path:?}"))?;
+    Ok(values.0)
+}
+
+/// Add the values in a file that follows the [`ValueFile`] schema to the ones provided as arguments.
+///
+/// A value can't be provided both as an argument and in the file.
+pub(crate) fn extend_with_values_file(
+    values: &mut HashMap<String, NadaValue<Clear>>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let file_values = parse_values_file(path)?;
+    if let Some(name) = file_values.keys().find(|name| values.contains_key(*name)) {
+        bail!("value '{name}' is provided both as an argument and in values file {path:?}");
+    }
+    values.extend(file_values);
+    Ok(())
+}
@@ -2,12 +2,12 @@ use anyhow::{Context, Result};
 use clap::{error::ErrorKind, CommandFactory};
 use clap_utils::ParserExt;
 use nillion::{
-    args::{Cli, Command},
+    args::{Cli, Command, ConfigCommand},
     config::Config,
     context::ContextConfig,
     handlers::{
-        context::ContextHandler, identities::IdentitiesHandler, networks::NetworksHandler, nilauth::NilauthHandler,
-        nilvm::NilvmHandler, nuc::NucHandler,
+        config::ConfigHandler, context::ContextHandler, identities::IdentitiesHandler, networks::NetworksHandler,
+        nilauth::NilauthHandler, nilvm::NilvmHandler, nuc::NucHandler,
     },
     serialize::{serialize_error, serialize_output, NoOutput, SerializeAsAny},
 };
@@ -39,6 +39,7 @@ async fn run(cli: Cli) -> Result<Box<dyn SerializeAsAny>> {
         Command::Identities(command) => IdentitiesHandler::handle(command),
         Command::Networks(command) => NetworksHandler::handle(command),
         Command::Context(command) => ContextHandler::handle(command),
+        Command::Config(ConfigCommand::Validate(args)) => ConfigHandler::validate(args),
         Command::Nuc(command) => {
             let parameters = build_parameters(identity, network);
             NucHandler::new(parameters).handle(command)
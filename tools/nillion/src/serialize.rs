@@ -1,8 +1,11 @@
 use crate::args::CommandOutputFormat;
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use erased_serde::serialize_trait_object;
-use serde::Serialize;
-use std::any::Any;
+use indexmap::IndexMap;
+use nada_value::{BigInt, BigUint};
+use nillion_client::{Clear, NadaValue};
+use serde::{Deserialize, Serialize};
+use std::{any::Any, collections::HashMap, str::FromStr};
 
 pub trait SerializeAsAny: erased_serde::Serialize + Any {}
 impl<T: erased_serde::Serialize + Any> SerializeAsAny for T {}
@@ -42,3 +45,224 @@ pub fn serialize_error(format: &CommandOutputFormat, e: &anyhow::Error) -> Strin
     let error_response = ErrorOutput { error, causes };
     serialize_output(format, &error_response).unwrap_or_else(|_| format!("{e:#}"))
 }
+
+/// A file containing named values.
+///
+/// Every value is tagged with its type, which allows expressing compound values. For example:
+///
+/// ```yaml
+/// my_array:
+///   type: array
+///   values:
+///     - type: secret-integer
+///       value: 1
+///     - type: secret-integer
+///       value: 2
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "IndexMap<String, FileValue>")]
+pub struct ValueFile(pub HashMap<String, NadaValue<Clear>>);
+
+impl TryFrom<IndexMap<String, FileValue>> for ValueFile {
+    type Error = String;
+
+    fn try_from(values: IndexMap<String, FileValue>) -> Result<Self, Self::Error> {
+        let values = values
+            .into_iter()
+            .map(|(name, value)| {
+                let value = value.into_nada_value(&name)?;
+                Ok((name, value))
+            })
+            .collect::<Result<_>>()
+            // Serde only keeps the error's message so the whole chain is flattened into it.
+            .map_err(|e| format!("{e:#}"))?;
+        Ok(Self(values))
+    }
+}
+
+/// A value in a [`ValueFile`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum FileValue {
+    /// A public integer.
+    Integer { value: FileNumber },
+
+    /// A public unsigned integer.
+    UnsignedInteger { value: FileNumber },
+
+    /// A public boolean.
+    Boolean { value: bool },
+
+    /// A secret integer.
+    SecretInteger { value: FileNumber },
+
+    /// A secret unsigned integer.
+    SecretUnsignedInteger { value: FileNumber },
+
+    /// A secret boolean.
+    SecretBoolean { value: bool },
+
+    /// A non empty array of homogeneous values.
+    Array { values: Vec<FileValue> },
+
+    /// A tuple.
+    Tuple { left: Box<FileValue>, right: Box<FileValue> },
+
+    /// An object.
+    Object { values: IndexMap<String, FileValue> },
+}
+
+impl FileValue {
+    fn into_nada_value(self, key: &str) -> Result<NadaValue<Clear>> {
+        let value = match self {
+            FileValue::Integer { value } => NadaValue::new_integer(value.parse_integer(key)?),
+            FileValue::UnsignedInteger { value } => NadaValue::new_unsigned_integer(value.parse_unsigned(key)?),
+            FileValue::Boolean { value } => NadaValue::new_boolean(value),
+            FileValue::SecretInteger { value } => NadaValue::new_secret_integer(value.parse_integer(key)?),
+            FileValue::SecretUnsignedInteger { value } => {
+                NadaValue::new_secret_unsigned_integer(value.parse_unsigned(key)?)
+            }
+            FileValue::SecretBoolean { value } => NadaValue::new_secret_boolean(value),
+            FileValue::Array { values } => {
+                let values = values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| value.into_nada_value(&format!("{key}[{index}]")))
+                    .collect::<Result<_>>()?;
+                NadaValue::new_array_non_empty(values).with_context(|| format!("invalid array '{key}'"))?
+            }
+            FileValue::Tuple { left, right } => {
+                let left = left.into_nada_value(&format!("{key}.left"))?;
+                let right = right.into_nada_value(&format!("{key}.right"))?;
+                NadaValue::new_tuple(left, right).with_context(|| format!("invalid tuple '{key}'"))?
+            }
+            FileValue::Object { values } => {
+                let values = values
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let value = value.into_nada_value(&format!("{key}.{name}"))?;
+                        Ok((name, value))
+                    })
+                    .collect::<Result<_>>()?;
+                NadaValue::new_object(values).with_context(|| format!("invalid object '{key}'"))?
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// A number in a [`ValueFile`].
+///
+/// Numbers can be written either as YAML/JSON numbers or as strings, the latter being useful for numbers that
+/// don't fit in 64 bits.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum FileNumber {
+    /// A signed number.
+    Signed(i64),
+
+    /// An unsigned number.
+    Unsigned(u64),
+
+    /// A number encoded as a string.
+    String(String),
+}
+
+impl FileNumber {
+    fn parse_integer(self, key: &str) -> Result<BigInt> {
+        match self {
+            FileNumber::Signed(value) => Ok(value.into()),
+            FileNumber::Unsigned(value) => Ok(value.into()),
+            FileNumber::String(value) => {
+                BigInt::from_str(&value).map_err(|_| anyhow!("invalid integer for '{key}': {value}"))
+            }
+        }
+    }
+
+    fn parse_unsigned(self, key: &str) -> Result<BigUint> {
+        match self {
+            FileNumber::Signed(value) => u64::try_from(value)
+                .map(Into::into)
+                .map_err(|_| anyhow!("invalid unsigned integer for '{key}': {value}")),
+            FileNumber::Unsigned(value) => Ok(value.into()),
+            FileNumber::String(value) => {
+                BigUint::from_str(&value).map_err(|_| anyhow!("invalid unsigned integer for '{key}': {value}"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extend_with_values_file, parse_values_file};
+    use std::io::Write;
+    use tempfile::{Builder, NamedTempFile};
+
+    fn write_yaml(contents: &str) -> NamedTempFile {
+        let mut file = Builder::new().suffix(".yaml").tempfile().expect("failed to create file");
+        file.write_all(contents.as_bytes()).expect("failed to write file");
+        file
+    }
+
+    #[test]
+    fn load_secret_integer_array() {
+        let file = write_yaml(
+            r#"
+my_array:
+  type: array
+  values:
+    - type: secret-integer
+      value: 1
+    - type: secret-integer
+      value: "-2"
+"#,
+        );
+
+        let values = parse_values_file(file.path()).expect("failed to load values");
+        let expected =
+            NadaValue::new_array_non_empty(vec![NadaValue::new_secret_integer(1), NadaValue::new_secret_integer(-2)])
+                .expect("invalid array");
+        assert_eq!(values.get("my_array"), Some(&expected));
+    }
+
+    #[test]
+    fn invalid_nested_value_reports_key() {
+        let file = write_yaml(
+            r#"
+my_array:
+  type: array
+  values:
+    - type: secret-unsigned-integer
+      value: "-1"
+"#,
+        );
+
+        let err = parse_values_file(file.path()).expect_err("loading succeeded");
+        assert!(format!("{err:#}").contains("my_array[0]"), "unexpected error: {err:#}");
+    }
+
+    #[test]
+    fn values_file_conflicts_with_arguments() {
+        let file = write_yaml(
+            r#"
+my_int:
+  type: secret-integer
+  value: 2
+another_int:
+  type: secret-integer
+  value: 3
+"#,
+        );
+
+        let mut values = HashMap::from([("my_int".to_string(), NadaValue::new_secret_integer(1))]);
+        let err = extend_with_values_file(&mut values, file.path()).expect_err("extending succeeded");
+        assert!(err.to_string().contains("my_int"), "unexpected error: {err:#}");
+        // Nothing is added when there's a conflict.
+        assert_eq!(values, HashMap::from([("my_int".to_string(), NadaValue::new_secret_integer(1))]));
+
+        let mut values = HashMap::from([("other".to_string(), NadaValue::new_secret_integer(1))]);
+        extend_with_values_file(&mut values, file.path()).expect("extending failed");
+        assert_eq!(values.get("my_int"), Some(&NadaValue::new_secret_integer(2)));
+    }
+}
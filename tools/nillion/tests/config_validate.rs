@@ -0,0 +1,29 @@
+use std::{fs, path::Path, process::Command};
+use tempfile::TempDir;
+
+fn validate(dir: &Path, contents: &str) -> i32 {
+    let path = dir.join("nillion-cli.yaml");
+    fs::write(&path, contents).expect("failed to write config");
+    let output = Command::new(env!("CARGO_BIN_EXE_nillion"))
+        .arg("--config-path")
+        .arg(dir.join("missing.yaml"))
+        .args(["config", "validate"])
+        .arg(&path)
+        .output()
+        .expect("failed to run nillion");
+    output.status.code().expect("no exit code")
+}
+
+#[test]
+fn validate_good_config() {
+    let dir = TempDir::new().expect("failed to create dir");
+    assert_eq!(validate(dir.path(), "output:\n  format: json\n"), 0);
+}
+
+#[test]
+fn validate_bad_config() {
+    let dir = TempDir::new().expect("failed to create dir");
+    assert_eq!(validate(dir.path(), "output:\n  format: xml\n"), 1);
+    assert_eq!(validate(dir.path(), "output:\n  fromat: json\n"), 1);
+    assert_eq!(validate(dir.path(), "outputs: {}\n"), 1);
+}